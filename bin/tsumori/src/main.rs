@@ -1,5 +1,3 @@
-use http;
-
 use clap::{Parser, Subcommand, ValueEnum};
 
 pub(crate) const SHORT_VERSION: &str = concat!("v", env!("CARGO_PKG_VERSION"));
@@ -37,6 +35,22 @@ struct ServerCommand {
     /// Log level
     #[clap(short, long, value_name = "LOG_LEVEL", default_value_t = LogLevel::Info)]
     log_level: LogLevel,
+
+    /// Default bridge routing strategy; `cheapest`, `fastest`, `preferred:<provider>` or `weighted:<provider>=<weight>,...`
+    #[clap(long, value_name = "STRATEGY", default_value = "preferred:across")]
+    routing_strategy: bridge::routing::RoutingStrategy,
+
+    /// Orders database url; `sqlite://<path>?mode=rwc` for single-node deployments, or `postgres://...`
+    #[clap(
+        long,
+        value_name = "DATABASE_URL",
+        default_value = "sqlite://tsumori.db?mode=rwc"
+    )]
+    database_url: String,
+
+    /// Redis url of the upstream responses cache, e.g. `redis://127.0.0.1:6379`; cached in memory if not set
+    #[clap(long, value_name = "REDIS_URL")]
+    redis_url: Option<String>,
 }
 
 #[derive(Debug, Copy, Clone, ValueEnum, Eq, PartialEq)]
@@ -62,11 +76,14 @@ impl core::fmt::Display for LogLevel {
 
 impl ServerCommand {
     fn execute(&self) -> Result<(), String> {
-        crate::http::run_server(http::ServerConfig {
+        http::run_server(http::ServerConfig {
             port: self.port,
             req_timeout: self.req_timeout,
             metrics_port: self.metrics_port,
             log_level: self.log_level.to_string(),
+            routing_strategy: self.routing_strategy.clone(),
+            database_url: self.database_url.clone(),
+            redis_url: self.redis_url.clone(),
         });
         Ok(())
     }
@@ -1,7 +1,7 @@
 use reqwest::blocking::Client;
 
-/// https://docs.across.to/integration-guides/across-bridge-integration#checking-limits
-/// https://docs.across.to/reference/api#api-endpoints
+// https://docs.across.to/integration-guides/across-bridge-integration#checking-limits
+// https://docs.across.to/reference/api#api-endpoints
 
 fn main() {
     // ORIGIN_CHAIN_ID=42161
@@ -23,8 +23,9 @@ async fn main() {
         destination_chain_id: utils::Chain::Arbitrum as u32, // Arbitrum
         output_token: "0xaf88d065e77c8cC2239327C5EDb3A432268e5831", // USDC Arbitrum
         // destination_token: &hex!("FF970A61A04b1cA14834A43f5dE4533eBDDB5CC8"), // USDC.e
-        recipient: "0x000007357111E4789005d4eBfF401a18D99770cE", // recipient
-        amount: U256::from(2000_000u32),                         // 4 USDC
+        recipient: Some("0x000007357111E4789005d4eBfF401a18D99770cE"), // recipient
+        amount: U256::from(2_000_000u32),                              // 4 USDC
+        message: None,
     };
 
     // let client = reqwest::blocking::Client::new();
@@ -59,14 +60,14 @@ async fn main() {
     println!("calldata: 0x{}", calldata);
 }
 
-fn get_tx_calldata<'a>(
+fn get_tx_calldata(
     query_params: &'_ bridge::across::QuoteQueryParams<'_>,
     fees_response: &'_ bridge::across::SuggestedFeesResponse,
     block_timestamp: u64,
 ) -> String {
     let calldata = bridge::across::depositV3Call {
-        depositor: Address::from_str(query_params.recipient).unwrap(), // depositor is recipient
-        recipient: Address::from_str(query_params.recipient).unwrap(),
+        depositor: Address::from_str(query_params.recipient.unwrap()).unwrap(), // depositor is recipient
+        recipient: Address::from_str(query_params.recipient.unwrap()).unwrap(),
         inputToken: Address::from_str(query_params.input_token).unwrap(),
         outputToken: Address::from_str(query_params.output_token).unwrap(),
         inputAmount: U256::from(query_params.amount),
@@ -82,8 +83,7 @@ fn get_tx_calldata<'a>(
         exclusivityDeadline: 0,
         message: hex!("").into(),
     };
-    let data = hex::encode(calldata.abi_encode());
-    data
+    hex::encode(calldata.abi_encode())
 }
 
 async fn get_latest_block_timestamp(chain_id: &u32) -> u64 {
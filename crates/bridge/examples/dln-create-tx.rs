@@ -1,7 +1,7 @@
 use reqwest::blocking::Client;
 
-/// https://docs.dln.trade/dln-api/quick-start-guide/requesting-order-creation-transaction
-/// https://api.dln.trade/v1.0/#/DLN/DlnOrderControllerV10_createOrder
+// https://docs.dln.trade/dln-api/quick-start-guide/requesting-order-creation-transaction
+// https://api.dln.trade/v1.0/#/DLN/DlnOrderControllerV10_createOrder
 
 fn main() {
     let query_params = bridge::debridge::CreateTxQueryParams {
@@ -11,10 +11,10 @@ fn main() {
         src_chain_token_in_sender_permit: None,
         dst_chain_id: 8453,                                                // Base
         dst_chain_token_out: "0xd9aAEc86B65D86f6A7B5B1b0c42FFA531710b6CA", // USDCbC Base
-        dst_chain_token_out_recipient: "0xD79842424f797feF2B713BAd555eDdD0b6c89a80", // recipient
+        dst_chain_token_out_recipient: Some("0xD79842424f797feF2B713BAd555eDdD0b6c89a80"), // recipient
         dst_chain_token_out_amount: None,
-        src_chain_order_authority_address: "0xD79842424f797feF2B713BAd555eDdD0b6c89a80", // same EOA account has authority on both chains
-        dst_chain_order_authority_address: "0xD79842424f797feF2B713BAd555eDdD0b6c89a80", // same EOA account has authority on both chains
+        src_chain_order_authority_address: Some("0xD79842424f797feF2B713BAd555eDdD0b6c89a80"), // same EOA account has authority on both chains
+        dst_chain_order_authority_address: Some("0xD79842424f797feF2B713BAd555eDdD0b6c89a80"), // same EOA account has authority on both chains
         external_call: None,
     };
 
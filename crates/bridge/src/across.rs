@@ -2,13 +2,14 @@ use crate::U256;
 use std::{collections::HashMap, str::FromStr};
 
 use alloy::providers::{Provider, ProviderBuilder, RootProvider};
-use alloy::sol_types::SolCall;
-use alloy::{primitives::Address, sol};
+use alloy::sol_types::{SolCall, SolStruct};
+use alloy::{
+    primitives::{Address, B256},
+    sol,
+};
 use eyre::Result;
-use hex::FromHex;
 use hex_literal::hex;
 use serde::Deserialize;
-use utils::AllowanceAction;
 
 sol! {
     #[derive(Debug)]
@@ -29,8 +30,141 @@ sol! {
 
     // check approval for caller
     function allowance(address owner, address spender) external view returns (uint256);
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize)]
+    struct Fees {
+        uint256 amount;
+        address recipient;
+    }
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize)]
+    struct BaseDepositData {
+        address inputToken;
+        address outputToken;
+        uint256 outputAmount;
+        address depositor;
+        address recipient;
+        uint256 destinationChainId;
+        address exclusiveRelayer;
+        uint32 quoteTimestamp;
+        uint32 fillDeadline;
+        uint32 exclusivityParameter;
+        bytes message;
+    }
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize)]
+    struct DepositData {
+        Fees submissionFees;
+        BaseDepositData baseDepositData;
+        uint256 inputAmount;
+    }
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize)]
+    struct TokenPermissions {
+        address token;
+        uint256 amount;
+    }
+
+    /// Permit2 `ISignatureTransfer.PermitTransferFrom`, as passed to the periphery; the spender is the periphery
+    #[derive(Debug)]
+    struct PermitTransferFrom {
+        TokenPermissions permitted;
+        uint256 nonce;
+        uint256 deadline;
+    }
+
+    /// Permit2 witness transfer signed by the depositor, binding the transfer to the deposit data
+    #[derive(Debug, serde::Serialize, serde::Deserialize)]
+    struct PermitWitnessTransferFrom {
+        TokenPermissions permitted;
+        address spender;
+        uint256 nonce;
+        uint256 deadline;
+        DepositData witness;
+    }
+
+    /// `SpokePoolV3Periphery`, depositing into the spoke pool on behalf of the signature owner.
+    /// The periphery pulls `inputAmount` (plus the submission fees) of `inputToken` from the signature owner,
+    /// and calls `depositV3` on the spoke pool with the deposit data.
+    interface SpokePoolV3Periphery {
+        // EIP-2612 permit of the periphery, and a separate EIP-712 signature of the deposit data
+        function depositWithPermit(
+            address signatureOwner,
+            DepositData depositData,
+            uint256 deadline,
+            bytes permitSignature,
+            bytes depositDataSignature
+        ) external;
+
+        // permit2 witness transfer to the periphery, the deposit data being the witness
+        function depositWithPermit2(
+            address signatureOwner,
+            DepositData depositData,
+            PermitTransferFrom permit,
+            bytes signature
+        ) external;
+    }
+
+    /// SpokePool (v3) custom errors, decoded from deposit reverts
+    interface ISpokePoolErrors {
+        #[derive(Debug)]
+        error DisabledRoute();
+        #[derive(Debug)]
+        error InvalidQuoteTimestamp();
+        #[derive(Debug)]
+        error InvalidFillDeadline();
+        #[derive(Debug)]
+        error InvalidExclusiveRelayer();
+        #[derive(Debug)]
+        error InvalidExclusivityDeadline();
+        #[derive(Debug)]
+        error MsgValueDoesNotMatchInputAmount();
+        #[derive(Debug)]
+        error NotExclusiveRelayer();
+        #[derive(Debug)]
+        error NoSlowFillsInExclusivityWindow();
+        #[derive(Debug)]
+        error RelayFilled();
+        #[derive(Debug)]
+        error InvalidSlowFillRequest();
+        #[derive(Debug)]
+        error ExpiredFillDeadline();
+        #[derive(Debug)]
+        error InvalidDepositorSignature();
+        #[derive(Debug)]
+        error MaxTransferSizeExceeded();
+        #[derive(Debug)]
+        error LowLevelCallFailed(bytes data);
+    }
 }
 
+/// Decoders of the SpokePool custom errors
+pub(crate) const SPOKE_POOL_ERRORS: &[crate::revert::ErrorDecoder] = &[
+    crate::revert::decode_error::<ISpokePoolErrors::DisabledRoute>,
+    crate::revert::decode_error::<ISpokePoolErrors::InvalidQuoteTimestamp>,
+    crate::revert::decode_error::<ISpokePoolErrors::InvalidFillDeadline>,
+    crate::revert::decode_error::<ISpokePoolErrors::InvalidExclusiveRelayer>,
+    crate::revert::decode_error::<ISpokePoolErrors::InvalidExclusivityDeadline>,
+    crate::revert::decode_error::<ISpokePoolErrors::MsgValueDoesNotMatchInputAmount>,
+    crate::revert::decode_error::<ISpokePoolErrors::NotExclusiveRelayer>,
+    crate::revert::decode_error::<ISpokePoolErrors::NoSlowFillsInExclusivityWindow>,
+    crate::revert::decode_error::<ISpokePoolErrors::RelayFilled>,
+    crate::revert::decode_error::<ISpokePoolErrors::InvalidSlowFillRequest>,
+    crate::revert::decode_error::<ISpokePoolErrors::ExpiredFillDeadline>,
+    crate::revert::decode_error::<ISpokePoolErrors::InvalidDepositorSignature>,
+    crate::revert::decode_error::<ISpokePoolErrors::MaxTransferSizeExceeded>,
+    crate::revert::decode_error::<ISpokePoolErrors::LowLevelCallFailed>,
+];
+
+/// Deposit fill deadline, relative to the latest source chain block
+const FILL_DEADLINE_SECS: u64 = 60 * 2;
+
+/// Validity of a quote; the relayer fees are bound to the quote timestamp and the gas price
+const QUOTE_VALIDITY_SECS: u64 = 60;
+
+/// Max age of a deposit quote timestamp accepted by the spoke pool (`depositQuoteTimeBuffer`)
+const QUOTE_TIME_BUFFER_SECS: u64 = 60 * 60;
+
 #[derive(Debug, serde::Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct LimitQueryParams<'a> {
@@ -51,14 +185,43 @@ impl<'a> From<&'a QuoteQueryParams<'a>> for LimitQueryParams<'a> {
     }
 }
 
-#[derive(Deserialize, Debug)]
+/// Token route enabled on the spoke pools, as returned by `available-routes`
+#[derive(Deserialize, serde::Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AvailableRoute {
+    pub origin_chain_id: u32,
+    pub origin_token: String,
+    pub destination_chain_id: u32,
+    pub destination_token: String,
+}
+
+/// Rejects token routes not enabled on the spoke pools, before requesting fees and limits.
+fn check_available_route(routes: &[AvailableRoute], params: &LimitQueryParams<'_>) -> Result<()> {
+    let enabled = routes.iter().any(|route| {
+        route.origin_chain_id == params.origin_chain_id
+            && route.destination_chain_id == params.destination_chain_id
+            && route.origin_token.eq_ignore_ascii_case(params.input_token)
+            && route
+                .destination_token
+                .eq_ignore_ascii_case(params.output_token)
+    });
+    if !enabled {
+        return Err(crate::BridgeError::ProviderRejected(
+            "Across: token route is not enabled".to_string(),
+        )
+        .into());
+    }
+    Ok(())
+}
+
+#[derive(Deserialize, serde::Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct TransferLimitsResponse {
-    min_deposit: String,
-    max_deposit: String,
-    max_deposit_instant: String,
-    max_deposit_short_delay: String,
-    recommended_deposit_instant: String,
+    pub min_deposit: String,
+    pub max_deposit: String,
+    pub max_deposit_instant: String,
+    pub max_deposit_short_delay: String,
+    pub recommended_deposit_instant: String,
 }
 
 #[derive(Debug, serde::Serialize, Clone)]
@@ -68,53 +231,109 @@ pub struct QuoteQueryParams<'a> {
     pub input_token: &'a str,
     pub destination_chain_id: u32,
     pub output_token: &'a str,
-    pub recipient: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recipient: Option<&'a str>,
     pub amount: U256,
+    /// Hex-encoded message forwarded to the recipient on fill (e.g. multicall handler instructions);
+    /// required for the relayer gas fee to account for the message execution
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<&'a str>,
 }
 
 impl<'a> From<&'a crate::BridgeRequest> for QuoteQueryParams<'a> {
     fn from(request: &'a crate::BridgeRequest) -> Self {
         Self {
             origin_chain_id: request.src_chain_id,
-            input_token: request.src_token.as_str(),
+            input_token: get_deposit_token(request.src_chain_id, &request.src_token),
             destination_chain_id: request.dest_chain_id,
-            output_token: request.dest_token.as_str(),
-            recipient: request.dest_recipient.as_str(),
+            output_token: get_deposit_token(request.dest_chain_id, &request.dest_token),
+            recipient: Some(request.dest_recipient.as_str()),
             amount: request.src_amount,
+            message: request.calldata.as_deref(),
         }
     }
 }
 
+impl<'a> From<&'a crate::QuoteRequest> for QuoteQueryParams<'a> {
+    fn from(request: &'a crate::QuoteRequest) -> Self {
+        Self {
+            origin_chain_id: request.src_chain_id,
+            input_token: get_deposit_token(request.src_chain_id, &request.src_token),
+            destination_chain_id: request.dest_chain_id,
+            output_token: get_deposit_token(request.dest_chain_id, &request.dest_token),
+            recipient: request.dest_recipient.as_deref(),
+            amount: request.src_amount,
+            message: request.calldata.as_deref(),
+        }
+    }
+}
+
+/// Across deposits and fills native currency as the chain's wrapped native token;
+/// native deposits send `msg.value` equal to the input amount, and fills to EOAs are unwrapped.
+fn get_deposit_token(chain_id: u32, token: &str) -> &str {
+    if !utils::is_native_token(token) {
+        return token;
+    }
+    utils::get_supported_chains()
+        .get(&chain_id)
+        .and_then(|chain| chain.wrapped_native_token)
+        .unwrap_or(token)
+}
+
 #[derive(Deserialize, Debug)]
 pub struct FeeDetails {
-    pct: String,
+    pub pct: String,
     pub total: String,
 }
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct SuggestedFeesResponse {
-    capital_fee_pct: String,
-    capital_fee_total: String,
-    relay_gas_fee_pct: String,
-    relay_gas_fee_total: String,
-    relay_fee_pct: String,
-    relay_fee_total: String,
-    lp_fee_pct: String,
+    pub capital_fee_pct: String,
+    pub capital_fee_total: String,
+    pub relay_gas_fee_pct: String,
+    pub relay_gas_fee_total: String,
+    pub relay_fee_pct: String,
+    pub relay_fee_total: String,
+    pub lp_fee_pct: String,
     pub timestamp: String,
-    is_amount_too_low: bool,
-    quote_block: String,
+    pub is_amount_too_low: bool,
+    pub quote_block: String,
     pub spoke_pool_address: String,
     pub total_relay_fee: FeeDetails,
-    relayer_capital_fee: FeeDetails,
-    relayer_gas_fee: FeeDetails,
-    lp_fee: FeeDetails,
+    pub relayer_capital_fee: FeeDetails,
+    pub relayer_gas_fee: FeeDetails,
+    pub lp_fee: FeeDetails,
+    #[serde(default)]
+    pub estimated_fill_time_sec: Option<u64>,
 }
 
 #[derive(Debug, Clone)]
 pub struct AcrossBridge {
     client: reqwest::Client,
     providers: HashMap<u32, RootProvider<alloy::transports::http::Http<reqwest::Client>>>,
+    /// Spoke pool periphery contracts per chain, consuming permit2 signature transfers for deposits.
+    /// If not deployed on a chain, an explicit approval of the spoke pool is required.
+    peripheries: HashMap<u32, Address>,
+    cache: crate::cache::Cache,
+}
+
+/// Transfer limits only change with the pool liquidity; deposits above the limits are rejected on quoting
+pub const LIMITS_CACHE_POLICY: crate::cache::CachePolicy = crate::cache::CachePolicy {
+    ttl: std::time::Duration::from_secs(60),
+    stale_ttl: std::time::Duration::from_secs(300),
+};
+
+/// Routes are only enabled or disabled by the Across governance
+pub const ROUTES_CACHE_POLICY: crate::cache::CachePolicy = crate::cache::CachePolicy {
+    ttl: std::time::Duration::from_secs(60 * 60),
+    stale_ttl: std::time::Duration::from_secs(24 * 60 * 60),
+};
+
+impl Default for AcrossBridge {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl AcrossBridge {
@@ -128,25 +347,101 @@ impl AcrossBridge {
             })
             .collect::<HashMap<_, _>>();
 
+        let peripheries = utils::get_supported_chains()
+            .iter()
+            .filter_map(|(id, chain)| {
+                let periphery = Address::from_str(chain.across_spoke_pool_periphery?).unwrap(); // infallible
+                Some((*id, periphery))
+            })
+            .collect::<HashMap<_, _>>();
+
         Self {
             client: reqwest::Client::new(),
             providers: supported_providers,
+            peripheries,
+            cache: crate::cache::Cache::memory(),
         }
     }
 
+    /// Sets the cache of the slowly changing upstream responses (e.g. transfer limits and routes).
+    pub fn with_cache(mut self, cache: crate::cache::Cache) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Overrides the spoke pool periphery contract of a chain; defaults to the deployment of `ChainData`.
+    pub fn with_periphery(mut self, chain_id: u32, periphery: Address) -> Self {
+        self.peripheries.insert(chain_id, periphery);
+        self
+    }
+
     pub async fn get_transfer_limits(
         &self,
         params: &LimitQueryParams<'_>,
     ) -> Result<TransferLimitsResponse> {
-        let url = "https://app.across.to/api/limits";
-        let response = self.client.get(url).query(params).send().await?;
-        if !response.status().is_success() {
-            return Err(eyre::eyre!(
-                "failed to get transfer limits: {}",
-                response.text().await?
-            ));
-        }
-        Ok(response.json().await?)
+        let request = self
+            .client
+            .get("https://app.across.to/api/limits")
+            .query(params)
+            .build()?;
+        // token addresses are case insensitive; the query only holds chain ids besides
+        let key = format!(
+            "across:limits:{}",
+            request.url().query().unwrap_or_default().to_lowercase()
+        );
+        let client = self.client.clone();
+        self.cache
+            .get_or_fetch(
+                "across_limits",
+                &key,
+                LIMITS_CACHE_POLICY,
+                move || async move {
+                    let response = client.execute(request).await?;
+                    if !response.status().is_success() {
+                        return Err(crate::BridgeError::ProviderRejected(format!(
+                            "failed to get transfer limits: {}",
+                            response.text().await?
+                        ))
+                        .into());
+                    }
+                    Ok(response.json().await?)
+                },
+            )
+            .await
+    }
+
+    /// Returns the (cached) token routes enabled between the chains.
+    pub async fn get_available_routes(
+        &self,
+        origin_chain_id: u32,
+        destination_chain_id: u32,
+    ) -> Result<Vec<AvailableRoute>> {
+        let client = self.client.clone();
+        self.cache
+            .get_or_fetch(
+                "across_routes",
+                &format!("across:routes:{}:{}", origin_chain_id, destination_chain_id),
+                ROUTES_CACHE_POLICY,
+                move || async move {
+                    let response = client
+                        .get("https://app.across.to/api/available-routes")
+                        .query(&[
+                            ("originChainId", origin_chain_id),
+                            ("destinationChainId", destination_chain_id),
+                        ])
+                        .send()
+                        .await?;
+                    if !response.status().is_success() {
+                        return Err(crate::BridgeError::ProviderRejected(format!(
+                            "failed to get available routes: {}",
+                            response.text().await?
+                        ))
+                        .into());
+                    }
+                    Ok(response.json().await?)
+                },
+            )
+            .await
     }
 
     pub async fn get_suggested_fees(
@@ -156,10 +451,11 @@ impl AcrossBridge {
         let url = "https://app.across.to/api/suggested-fees";
         let response = self.client.get(url).query(params).send().await?;
         if !response.status().is_success() {
-            return Err(eyre::eyre!(
+            return Err(crate::BridgeError::ProviderRejected(format!(
                 "failed to get suggested fees: {}",
                 response.text().await?
-            ));
+            ))
+            .into());
         }
         Ok(response.json().await?)
     }
@@ -168,7 +464,7 @@ impl AcrossBridge {
         let provider = self
             .providers
             .get(&chain_id)
-            .ok_or_else(|| eyre::eyre!("unsupported chain id: {}", chain_id))?;
+            .ok_or(crate::BridgeError::UnsupportedChain(chain_id))?;
         let latest_block_number = provider.get_block_number().await?;
         let latest_block = provider
             .get_block_by_number(latest_block_number.into(), false)
@@ -177,6 +473,76 @@ impl AcrossBridge {
         Ok(latest_block.header.timestamp)
     }
 
+    /// Returns the timestamp of the block including the tx, if mined.
+    async fn get_tx_timestamp(&self, chain_id: u32, tx_hash: &str) -> Result<Option<u64>> {
+        let provider = self
+            .providers
+            .get(&chain_id)
+            .ok_or(crate::BridgeError::UnsupportedChain(chain_id))?;
+        let Some(block_number) = provider
+            .get_transaction_receipt(B256::from_str(tx_hash)?)
+            .await?
+            .and_then(|receipt| receipt.block_number)
+        else {
+            return Ok(None);
+        };
+        let block = provider
+            .get_block_by_number(block_number.into(), false)
+            .await?;
+        Ok(block.map(|block| block.header.timestamp))
+    }
+
+    /// Returns the status of a deposit; the deposit and fill timestamps are resolved from the chains.
+    pub async fn get_deposit_status(
+        &self,
+        params: &DepositStatusQueryParams<'_>,
+    ) -> Result<crate::status::OrderStatus> {
+        let url = "https://app.across.to/api/deposit/status";
+        let response = self.client.get(url).query(params).send().await?;
+        if !response.status().is_success() {
+            return Err(crate::BridgeError::ProviderRejected(format!(
+                "failed to get deposit status: {}",
+                response.text().await?
+            ))
+            .into());
+        }
+        let response: DepositStatusResponse = response.json().await?;
+
+        // timestamps are informational; rpc failures do not fail the status
+        let (created_at, filled_at) = tokio::join!(
+            async {
+                match &response.deposit_tx_hash {
+                    Some(tx_hash) => self
+                        .get_tx_timestamp(response.origin_chain_id, tx_hash)
+                        .await
+                        .ok()
+                        .flatten(),
+                    None => None,
+                }
+            },
+            async {
+                match (&response.fill_tx, response.destination_chain_id) {
+                    (Some(tx_hash), Some(chain_id)) => self
+                        .get_tx_timestamp(chain_id, tx_hash)
+                        .await
+                        .ok()
+                        .flatten(),
+                    _ => None,
+                }
+            },
+        );
+
+        Ok(crate::status::OrderStatus {
+            provider: crate::SupportedProviders::Across,
+            order_id: response.deposit_id.to_string(),
+            state: response.state()?,
+            deposit_tx_hash: response.deposit_tx_hash,
+            fill_tx_hash: response.fill_tx,
+            created_at,
+            filled_at,
+        })
+    }
+
     fn get_tx_calldata<'a>(
         caller: &'a str,
         query_params: &'_ QuoteQueryParams<'_>,
@@ -185,12 +551,36 @@ impl AcrossBridge {
         block_timestamp: u64,
         calldata: Option<&'a str>,
     ) -> Result<String> {
-        let calldata = depositV3Call {
+        let calldata = Self::get_deposit_call(
+            caller,
+            query_params,
+            fees_response_timestamp,
+            fees_response_total_relay_fee,
+            block_timestamp,
+            calldata,
+        )?;
+        let data = hex::encode(calldata.abi_encode());
+        Ok(data)
+    }
+
+    fn get_deposit_call<'a>(
+        caller: &'a str,
+        query_params: &'_ QuoteQueryParams<'_>,
+        fees_response_timestamp: u32,
+        fees_response_total_relay_fee: U256,
+        block_timestamp: u64,
+        calldata: Option<&'a str>,
+    ) -> Result<depositV3Call> {
+        Ok(depositV3Call {
             depositor: Address::from_str(caller)?,
-            recipient: Address::from_str(query_params.recipient)?,
+            recipient: Address::from_str(
+                query_params
+                    .recipient
+                    .ok_or_else(|| eyre::eyre!("deposit recipient is required"))?,
+            )?,
             inputToken: Address::from_str(query_params.input_token)?,
             outputToken: Address::from_str(query_params.output_token)?,
-            inputAmount: U256::from(query_params.amount),
+            inputAmount: query_params.amount,
             outputAmount: query_params
                 .amount
                 .checked_sub(fees_response_total_relay_fee)
@@ -199,15 +589,259 @@ impl AcrossBridge {
             exclusiveRelayer: hex!("0000000000000000000000000000000000000000").into(),
             quoteTimestamp: fees_response_timestamp,
             // block.timestamp + 21600, // fillDeadline: We reccomend a fill deadline of 6 hours out. The contract will reject this if it is beyond 8 hours from now.
-            fillDeadline: block_timestamp.saturating_add(60 * 2) as u32, // 120s
+            fillDeadline: block_timestamp.saturating_add(FILL_DEADLINE_SECS) as u32,
             exclusivityDeadline: 0,
             message: calldata
-                .map(|data| Vec::from_hex(data).unwrap_or_default())
+                .map(alloy::hex::decode)
+                .transpose()
+                .map_err(|e| eyre::eyre!("invalid message calldata: {}", e))?
                 .unwrap_or_default()
                 .into(),
+        })
+    }
+
+    /// Returns the permit2 witness transfer to be signed by the depositor, for the periphery to make the deposit;
+    /// preceded by a permit2 approval tx if required. Returns `None` if permit2 is not deployed on the chain.
+    async fn get_permit2_action(
+        &self,
+        chain_id: u32,
+        periphery: &Address,
+        deposit: depositV3Call,
+        deadline: U256,
+    ) -> Result<Option<utils::AllowanceAction>> {
+        let provider = self
+            .providers
+            .get(&chain_id)
+            .ok_or(crate::BridgeError::UnsupportedChain(chain_id))?;
+        let Some(permit2) = utils::permit2::get_permit2_address(provider).await? else {
+            return Ok(None);
         };
-        let data = hex::encode(calldata.abi_encode());
-        Ok(data)
+        let nonce = utils::permit2::get_unused_nonce(provider, &deposit.depositor).await?;
+
+        let (token, amount, owner) = (deposit.inputToken, deposit.inputAmount, deposit.depositor);
+        let deposit = depositV3Call {
+            fillDeadline: get_permit_fill_deadline(deadline)?,
+            ..deposit
+        };
+        let witness = get_permit2_witness(periphery, into_deposit_data(deposit), nonce, deadline);
+        let domain = utils::permit2::get_permit2_domain(chain_id.into(), &permit2);
+        let typed_data = alloy::dyn_abi::TypedData::from_struct(&witness, Some(domain));
+        let action = utils::get_permit2_allowance_action(
+            provider, &token, &amount, &owner, &permit2, typed_data,
+        )
+        .await?;
+        Ok(Some(action))
+    }
+
+    /// Verifies the signed permit2 witness transfer of the depositor, and returns the periphery tx consuming it.
+    /// The deposit is made at the signed quote, which must not be outbid by the current `deposit` quote.
+    async fn get_permit_tx(
+        &self,
+        chain_id: u32,
+        periphery: &Address,
+        deposit: depositV3Call,
+        permit: &crate::PermitSignature,
+        block_timestamp: u64,
+    ) -> Result<crate::TxData> {
+        let provider = self
+            .providers
+            .get(&chain_id)
+            .ok_or(crate::BridgeError::UnsupportedChain(chain_id))?;
+
+        let envelope = match permit {
+            crate::PermitSignature::EIP2612(_) => {
+                return Err(eyre::eyre!(
+                    "EIP-2612 permits are not consumed by Across deposits; sign the returned permit2 payload instead"
+                ))
+            }
+            crate::PermitSignature::Permit2(envelope) => PeripheryPermit2Envelope::from_str(envelope)?,
+        };
+        if envelope.permit.deadline <= U256::from(utils::get_unix_timestamp()) {
+            return Err(eyre::eyre!(
+                "permit2 deadline {} has passed",
+                envelope.permit.deadline
+            ));
+        }
+        if block_timestamp > u64::from(envelope.quote_timestamp) + QUOTE_TIME_BUFFER_SECS {
+            return Err(eyre::eyre!(
+                "signed quote timestamp {} has expired; request a new permit2 payload",
+                envelope.quote_timestamp
+            ));
+        }
+        // relayers would not fill the deposit if the fees have increased since the quote was signed
+        if envelope.output_amount > deposit.outputAmount {
+            return Err(eyre::eyre!(
+                "signed output amount {} exceeds the current output amount {}; request a new permit2 payload",
+                envelope.output_amount,
+                deposit.outputAmount
+            ));
+        }
+        utils::permit2::check_unused_nonce(provider, &deposit.depositor, &envelope.permit.nonce)
+            .await?;
+
+        let deposit = depositV3Call {
+            outputAmount: envelope.output_amount,
+            quoteTimestamp: envelope.quote_timestamp,
+            fillDeadline: get_permit_fill_deadline(envelope.permit.deadline)?,
+            ..deposit
+        };
+        let call = get_permit2_deposit_call(chain_id.into(), periphery, deposit, &envelope.permit)?;
+        Ok(crate::TxData {
+            data: hex::encode(call.abi_encode()),
+            to: periphery.to_string(),
+            value: "0".to_string(),
+            ..Default::default()
+        })
+    }
+}
+
+/// Signed permit2 envelope of a periphery deposit, as provided by the caller: the permit2 envelope, followed by the
+/// quote signed in the deposit witness; `abi.encodePacked(uint256 nonce, uint256 deadline, bytes signature,
+/// uint256 outputAmount, uint32 quoteTimestamp)`.
+#[derive(Debug, Clone, PartialEq)]
+struct PeripheryPermit2Envelope {
+    permit: utils::permit2::Permit2Envelope,
+    output_amount: U256,
+    quote_timestamp: u32,
+}
+
+impl FromStr for PeripheryPermit2Envelope {
+    type Err = eyre::Report;
+
+    fn from_str(envelope: &str) -> Result<Self, Self::Err> {
+        let bytes = alloy::hex::decode(envelope)?;
+        if bytes.len() != 129 + 32 + 4 {
+            return Err(eyre::eyre!(
+                "invalid permit2 envelope length: expected 165 bytes, got {}",
+                bytes.len()
+            ));
+        }
+        let (permit, quote) = bytes.split_at(129);
+        Ok(Self {
+            permit: utils::permit2::Permit2Envelope::from_str(&hex::encode(permit))?,
+            output_amount: U256::from_be_slice(&quote[..32]),
+            quote_timestamp: u32::from_be_bytes(quote[32..].try_into()?),
+        })
+    }
+}
+
+/// Fill deadline of a permit deposit; derived from the permit deadline, so that the signed deposit can be rebuilt
+fn get_permit_fill_deadline(deadline: U256) -> Result<u32> {
+    u32::try_from(deadline.saturating_add(U256::from(FILL_DEADLINE_SECS)))
+        .map_err(|_| eyre::eyre!("permit deadline {} exceeds uint32", deadline))
+}
+
+fn get_permit2_witness(
+    periphery: &Address,
+    deposit_data: DepositData,
+    nonce: U256,
+    deadline: U256,
+) -> PermitWitnessTransferFrom {
+    PermitWitnessTransferFrom {
+        permitted: TokenPermissions {
+            token: deposit_data.baseDepositData.inputToken,
+            amount: deposit_data.inputAmount + deposit_data.submissionFees.amount,
+        },
+        spender: *periphery,
+        nonce,
+        deadline,
+        witness: deposit_data,
+    }
+}
+
+/// Verifies that the permit2 witness transfer of `deposit` was signed by its depositor, and returns the periphery
+/// call consuming it.
+fn get_permit2_deposit_call(
+    chain_id: u64,
+    periphery: &Address,
+    deposit: depositV3Call,
+    permit: &utils::permit2::Permit2Envelope,
+) -> Result<SpokePoolV3Periphery::depositWithPermit2Call> {
+    let depositor = deposit.depositor;
+    let witness = get_permit2_witness(
+        periphery,
+        into_deposit_data(deposit),
+        permit.nonce,
+        permit.deadline,
+    );
+    let domain = utils::permit2::get_permit2_domain(chain_id, &utils::permit2::PERMIT2_ADDRESS);
+    let signer = permit
+        .signature
+        .recover_address_from_prehash(&witness.eip712_signing_hash(&domain))?;
+    if signer != depositor {
+        return Err(eyre::eyre!(
+            "invalid permit2 signature: signer {} does not match depositor {} for the deposit",
+            signer,
+            depositor
+        ));
+    }
+    Ok(SpokePoolV3Periphery::depositWithPermit2Call {
+        signatureOwner: signer,
+        permit: PermitTransferFrom {
+            permitted: witness.permitted,
+            nonce: witness.nonce,
+            deadline: witness.deadline,
+        },
+        depositData: witness.witness,
+        signature: permit.signature.as_bytes().into(),
+    })
+}
+
+/// Builds the quote from the suggested fees; all fees are charged in the input token.
+fn get_quote_from_fees(
+    query_params: &QuoteQueryParams<'_>,
+    fees_response: &SuggestedFeesResponse,
+    now: u64,
+) -> Result<crate::BridgeQuote> {
+    let fee = |name: &str, details: &FeeDetails| -> Result<crate::QuoteFee> {
+        Ok(crate::QuoteFee {
+            name: name.to_string(),
+            token: query_params.input_token.to_string(),
+            amount: details.total.parse()?,
+        })
+    };
+    let output_amount = query_params
+        .amount
+        .checked_sub(fees_response.total_relay_fee.total.parse::<U256>()?)
+        .ok_or_else(|| eyre::eyre!("output amount underflow"))?;
+    Ok(crate::BridgeQuote::new(
+        crate::SupportedProviders::Across,
+        query_params.amount,
+        output_amount,
+        vec![
+            fee("lpFee", &fees_response.lp_fee)?,
+            fee("relayerCapitalFee", &fees_response.relayer_capital_fee)?,
+            fee("relayerGasFee", &fees_response.relayer_gas_fee)?,
+        ],
+        // fills are bound by the deposit fill deadline
+        fees_response
+            .estimated_fill_time_sec
+            .unwrap_or(FILL_DEADLINE_SECS),
+        now.saturating_add(QUOTE_VALIDITY_SECS),
+    ))
+}
+
+/// Periphery deposit data of the deposit; no submission fees are paid, as the depositor submits the tx
+fn into_deposit_data(deposit: depositV3Call) -> DepositData {
+    DepositData {
+        submissionFees: Fees {
+            amount: U256::ZERO,
+            recipient: Address::ZERO,
+        },
+        baseDepositData: BaseDepositData {
+            inputToken: deposit.inputToken,
+            outputToken: deposit.outputToken,
+            outputAmount: deposit.outputAmount,
+            depositor: deposit.depositor,
+            recipient: deposit.recipient,
+            destinationChainId: deposit.destinationChainId,
+            exclusiveRelayer: deposit.exclusiveRelayer,
+            quoteTimestamp: deposit.quoteTimestamp,
+            fillDeadline: deposit.fillDeadline,
+            exclusivityParameter: deposit.exclusivityDeadline,
+            message: deposit.message,
+        },
+        inputAmount: deposit.inputAmount,
     }
 }
 
@@ -219,10 +853,14 @@ impl crate::BridgeProvider for AcrossBridge {
         let provider = self
             .providers
             .get(&request.src_chain_id)
-            .ok_or_else(|| eyre::eyre!("unsupported chain id: {}", request.src_chain_id))?;
+            .ok_or(crate::BridgeError::UnsupportedChain(request.src_chain_id))?;
 
         let query_params: QuoteQueryParams = request.into();
         let limits_query_params: LimitQueryParams = (&query_params).into();
+        let routes = self
+            .get_available_routes(request.src_chain_id, request.dest_chain_id)
+            .await?;
+        check_available_route(&routes, &limits_query_params)?;
 
         let fees_response_fut = self.get_suggested_fees(&query_params);
         let limits_response_fut = self.get_transfer_limits(&limits_query_params);
@@ -236,7 +874,7 @@ impl crate::BridgeProvider for AcrossBridge {
         };
 
         if request.src_amount > U256::from_str(&limits_response.max_deposit)? {
-            return Err(eyre::eyre!("requested amount exceeds max deposit limit"));
+            return Err(crate::BridgeError::AmountTooHigh.into());
         }
 
         if let Some(dest_amount) = request.dest_amount {
@@ -251,21 +889,98 @@ impl crate::BridgeProvider for AcrossBridge {
             }
         }
 
-        let allowance_action = utils::get_token_allowance_action(
-            provider,
-            &Address::from_str(&request.src_token)?,
-            &request.src_amount,
-            &Address::from_str(&request.src_caller)?,
-            &Address::from_str(&fees_response.spoke_pool_address)?,
-        )
-        .await?;
+        // native currency deposit; no allowance or permit is required, the input amount is sent as value
+        if utils::is_native_token(&request.src_token) {
+            let calldata = Self::get_tx_calldata(
+                request.src_caller.as_str(),
+                &query_params,
+                fees_response.timestamp.parse()?,
+                fees_response.total_relay_fee.total.parse::<U256>()?,
+                block_timestamp,
+                query_params.message,
+            )?;
+            return Ok(crate::BridgeResponse::new(
+                crate::SupportedProviders::Across,
+                crate::BridgeAction::BridgingTx(crate::TxData {
+                    data: calldata,
+                    to: fees_response.spoke_pool_address,
+                    value: request.src_amount.to_string(),
+                    ..Default::default()
+                }),
+            ));
+        }
+
+        let spoke_pool = Address::from_str(&fees_response.spoke_pool_address)?;
+        let periphery = self.peripheries.get(&request.src_chain_id);
+        let deposit = Self::get_deposit_call(
+            request.src_caller.as_str(),
+            &query_params,
+            fees_response.timestamp.parse()?,
+            fees_response.total_relay_fee.total.parse::<U256>()?,
+            block_timestamp,
+            query_params.message,
+        )?;
+
+        // signed permit provided by the caller; deposit through the periphery contract consuming the permit
+        if let Some(permit) = &request.src_chain_token_in_sender_permit {
+            let periphery = periphery.ok_or_else(|| {
+                eyre::eyre!(
+                    "permit deposits are not supported on chain id: {}",
+                    request.src_chain_id
+                )
+            })?;
+            let tx = self
+                .get_permit_tx(
+                    request.src_chain_id,
+                    periphery,
+                    deposit,
+                    permit,
+                    block_timestamp,
+                )
+                .await?;
+            return Ok(crate::BridgeResponse::new(
+                crate::SupportedProviders::Across,
+                crate::BridgeAction::BridgingTx(tx),
+            ));
+        }
+
+        // the spoke pool does not consume permits; these are consumed by the periphery instead
+        let allowance_action = request
+            .allowance_action(utils::get_token_allowance_action(
+                provider,
+                &deposit.inputToken,
+                &deposit.inputAmount,
+                &deposit.depositor,
+                &spoke_pool,
+                &utils::PermitOptions {
+                    spender: None,
+                    permit2_kind: None,
+                    deadline: utils::get_permit_deadline(request.permit_deadline),
+                },
+            ))
+            .await?;
+        let allowance_action = match (allowance_action, periphery) {
+            // sign a permit2 transfer to the periphery rather than approving the spoke pool, if permit2 is deployed
+            (utils::AllowanceAction::ApprovalTx(approval_tx), Some(periphery)) => self
+                .get_permit2_action(
+                    request.src_chain_id,
+                    periphery,
+                    deposit,
+                    utils::get_permit_deadline(request.permit_deadline),
+                )
+                .await?
+                .unwrap_or(utils::AllowanceAction::ApprovalTx(approval_tx)),
+            (allowance_action, _) => allowance_action,
+        };
 
         // if there is an pre-allowance tx/sig/action required, it must be returned
         // to be executed by the caller
-        if !matches!(allowance_action, AllowanceAction::Ok) {
-            // TODO: convert allowance action as-is, into BridgeResponse
-            // TODO: impl From<AllowanceAction> for BridgeResponse
-        };
+        if let Some(response) = crate::BridgeResponse::from_allowance_action(
+            crate::SupportedProviders::Across,
+            allowance_action,
+        ) {
+            return Ok(response);
+        }
 
         // no pre-aproval/signature action is required, return bridging calldata
         let calldata = Self::get_tx_calldata(
@@ -274,21 +989,77 @@ impl crate::BridgeProvider for AcrossBridge {
             fees_response.timestamp.parse().unwrap(),
             fees_response.total_relay_fee.total.parse::<U256>().unwrap(),
             block_timestamp,
-            // request.calldata.as_ref().map(|tx| tx.data.as_str()),
-            "".into(),
+            query_params.message,
         )?;
-        Ok(crate::BridgeResponse {
-            provider: crate::SupportedProviders::Across,
-            bridge_action: crate::BridgeAction::BridgingTx(crate::TxData {
+        Ok(crate::BridgeResponse::new(
+            crate::SupportedProviders::Across,
+            crate::BridgeAction::BridgingTx(crate::TxData {
                 data: calldata,
                 to: fees_response.spoke_pool_address,
                 value: "0".to_string(),
-                // value: request
-                //     .calldata
-                //     .as_ref()
-                //     .map(|tx| tx.value.clone())
-                //     .unwrap_or("0".to_string()),
+                ..Default::default()
             }),
+        ))
+    }
+
+    async fn get_quote(&self, request: &crate::QuoteRequest) -> eyre::Result<crate::BridgeQuote> {
+        let query_params: QuoteQueryParams = request.into();
+        let limits_query_params: LimitQueryParams = (&query_params).into();
+        let routes = self
+            .get_available_routes(request.src_chain_id, request.dest_chain_id)
+            .await?;
+        check_available_route(&routes, &limits_query_params)?;
+
+        // parallel requests to get fee response and limits
+        let (fees_response, limits_response) = tokio::try_join!(
+            self.get_suggested_fees(&query_params),
+            self.get_transfer_limits(&limits_query_params)
+        )?;
+
+        if fees_response.is_amount_too_low
+            || request.src_amount < U256::from_str(&limits_response.min_deposit)?
+        {
+            return Err(crate::BridgeError::AmountTooLow.into());
+        }
+        if request.src_amount > U256::from_str(&limits_response.max_deposit)? {
+            return Err(crate::BridgeError::AmountTooHigh.into());
+        }
+
+        get_quote_from_fees(&query_params, &fees_response, utils::get_unix_timestamp())
+    }
+}
+
+/// Deposit lookup; either by the origin chain id and deposit id, or the origin deposit tx hash
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositStatusQueryParams<'a> {
+    pub origin_chain_id: Option<u32>,
+    pub deposit_id: Option<u32>,
+    pub deposit_tx_hash: Option<&'a str>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositStatusResponse {
+    /// `pending`, `filled`, `slowFillRequested`, `expired` or `refunded`
+    pub status: String,
+    pub origin_chain_id: u32,
+    pub deposit_id: u32,
+    pub destination_chain_id: Option<u32>,
+    pub deposit_tx_hash: Option<String>,
+    pub fill_tx: Option<String>,
+    pub deposit_refund_tx_hash: Option<String>,
+}
+
+impl DepositStatusResponse {
+    pub fn state(&self) -> Result<crate::status::OrderState> {
+        Ok(match self.status.as_str() {
+            // slow fills are executed by the spoke pool once the relay is settled on the hub pool
+            "pending" | "slowFillRequested" => crate::status::OrderState::Pending,
+            "filled" => crate::status::OrderState::Filled,
+            "expired" => crate::status::OrderState::Expired,
+            "refunded" => crate::status::OrderState::Refunded,
+            status => return Err(eyre::eyre!("unknown deposit status: {}", status)),
         })
     }
 }
@@ -322,8 +1093,9 @@ mod tests {
             input_token: "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913", // USDC Base
             destination_chain_id: utils::Chain::Arbitrum as u32, // Arbitrum
             output_token: "0xaf88d065e77c8cC2239327C5EDb3A432268e5831", // USDC Arbitrum
-            recipient: "0x000007357111E4789005d4eBfF401a18D99770cE", // recipient
-            amount: U256::from(2000_000u32),            // 4 USDC
+            recipient: Some("0x000007357111E4789005d4eBfF401a18D99770cE"), // recipient
+            amount: U256::from(2_000_000u32),           // 4 USDC
+            message: None,
         };
 
         let response = bridge.get_suggested_fees(&params).await;
@@ -332,6 +1104,35 @@ mod tests {
         // assert!(false);
     }
 
+    #[test]
+    fn deposit_status_state() {
+        let response: DepositStatusResponse = serde_json::from_value(serde_json::json!({
+            "status": "slowFillRequested",
+            "originChainId": 8453,
+            "depositId": 1234,
+            "depositTxHash": "0x01",
+            "fillTx": null,
+            "destinationChainId": 42161
+        }))
+        .unwrap();
+        assert_eq!(
+            response.state().unwrap(),
+            crate::status::OrderState::Pending
+        );
+
+        let response: DepositStatusResponse = serde_json::from_value(serde_json::json!({
+            "status": "refunded",
+            "originChainId": 8453,
+            "depositId": 1234,
+            "depositRefundTxHash": "0x02"
+        }))
+        .unwrap();
+        assert_eq!(
+            response.state().unwrap(),
+            crate::status::OrderState::Refunded
+        );
+    }
+
     #[tokio::test]
     async fn get_latest_block_timestamp() {
         let bridge = AcrossBridge::new();
@@ -348,15 +1149,16 @@ mod tests {
             input_token: "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913", // USDC Base
             destination_chain_id: utils::Chain::Arbitrum as u32, // Arbitrum
             output_token: "0xaf88d065e77c8cC2239327C5EDb3A432268e5831", // USDC Arbitrum
-            recipient: "0x000007357111E4789005d4eBfF401a18D99770cE", // recipient
-            amount: U256::from(2000_000u32),            // 4 USDC
+            recipient: Some("0x000007357111E4789005d4eBfF401a18D99770cE"), // recipient
+            amount: U256::from(2_000_000u32),           // 4 USDC
+            message: None,
         };
         let fees_response_timestamp = "1634160000";
         let fees_response_total_relay_fee = "1000";
         let block_timestamp: u64 = 1634150000;
 
         let calldata = AcrossBridge::get_tx_calldata(
-            query_params.recipient,
+            query_params.recipient.unwrap(),
             &query_params,
             fees_response_timestamp.parse().unwrap(),
             fees_response_total_relay_fee.parse::<U256>().unwrap(),
@@ -367,6 +1169,237 @@ mod tests {
         assert_eq!(calldata, "7b939232000000000000000000000000000007357111e4789005d4ebff401a18d99770ce000000000000000000000000000007357111e4789005d4ebff401a18d99770ce000000000000000000000000833589fcd6edb6e08f4c7c32d4f71b54bda02913000000000000000000000000af88d065e77c8cc2239327c5edb3a432268e583100000000000000000000000000000000000000000000000000000000001e848000000000000000000000000000000000000000000000000000000000001e8098000000000000000000000000000000000000000000000000000000000000a4b100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000061674d8000000000000000000000000000000000000000000000000000000000616726e8000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001800000000000000000000000000000000000000000000000000000000000000000");
     }
 
+    #[test]
+    fn get_deposit_call_message() {
+        let query_params = QuoteQueryParams {
+            origin_chain_id: utils::Chain::Base as u32,
+            input_token: "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913",
+            destination_chain_id: utils::Chain::Arbitrum as u32,
+            output_token: "0xaf88d065e77c8cC2239327C5EDb3A432268e5831",
+            recipient: Some("0x000007357111E4789005d4eBfF401a18D99770cE"),
+            amount: U256::from(2_000_000u32),
+            message: Some("0xdeadbeef"),
+        };
+        let deposit = AcrossBridge::get_deposit_call(
+            query_params.recipient.unwrap(),
+            &query_params,
+            1634160000,
+            U256::from(1000),
+            1634150000,
+            query_params.message,
+        )
+        .unwrap();
+        assert_eq!(deposit.message.to_vec(), vec![0xde, 0xad, 0xbe, 0xef]);
+
+        // invalid message calldata is rejected, rather than silently dropped
+        assert!(AcrossBridge::get_deposit_call(
+            query_params.recipient.unwrap(),
+            &query_params,
+            1634160000,
+            U256::from(1000),
+            1634150000,
+            Some("0xzz"),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn permit2_witness_type() {
+        // permit2 hashes the witness transfer as `PermitWitnessTransferFrom(TokenPermissions permitted,address spender,
+        // uint256 nonce,uint256 deadline,` followed by the periphery witness type string
+        let witness_type = "DepositData witness)\
+            BaseDepositData(address inputToken,address outputToken,uint256 outputAmount,address depositor,\
+            address recipient,uint256 destinationChainId,address exclusiveRelayer,uint32 quoteTimestamp,\
+            uint32 fillDeadline,uint32 exclusivityParameter,bytes message)\
+            DepositData(Fees submissionFees,BaseDepositData baseDepositData,uint256 inputAmount)\
+            Fees(uint256 amount,address recipient)\
+            TokenPermissions(address token,uint256 amount)";
+        assert_eq!(
+            PermitWitnessTransferFrom::eip712_encode_type(),
+            format!(
+                "PermitWitnessTransferFrom(TokenPermissions permitted,address spender,uint256 nonce,uint256 deadline,{}",
+                witness_type
+            )
+        );
+    }
+
+    #[test]
+    fn get_permit_tx_periphery_calldata() {
+        use alloy::signers::k256::ecdsa::SigningKey;
+
+        let key = SigningKey::from_slice(&[1; 32]).unwrap();
+        let depositor = Address::from_private_key(&key);
+        let periphery = Address::from_str(
+            utils::get_supported_chains()
+                .get(&(utils::Chain::Base as u32))
+                .and_then(|chain| chain.across_spoke_pool_periphery)
+                .unwrap(),
+        )
+        .unwrap();
+        let query_params = QuoteQueryParams {
+            origin_chain_id: utils::Chain::Base as u32,
+            input_token: "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913",
+            destination_chain_id: utils::Chain::Arbitrum as u32,
+            output_token: "0xaf88d065e77c8cC2239327C5EDb3A432268e5831",
+            recipient: Some("0x000007357111E4789005d4eBfF401a18D99770cE"),
+            amount: U256::from(2_000_000u32),
+            message: None,
+        };
+        let deadline = U256::from(1_700_000_000u64);
+        let deposit = depositV3Call {
+            fillDeadline: get_permit_fill_deadline(deadline).unwrap(),
+            ..AcrossBridge::get_deposit_call(
+                &depositor.to_string(),
+                &query_params,
+                1_699_999_000,
+                U256::from(1000),
+                1_699_999_000,
+                None,
+            )
+            .unwrap()
+        };
+
+        // depositor signs the witness transfer returned as the permit2 payload
+        let witness = get_permit2_witness(
+            &periphery,
+            into_deposit_data(deposit.clone()),
+            U256::from(513),
+            deadline,
+        );
+        let domain = utils::permit2::get_permit2_domain(8453, &utils::permit2::PERMIT2_ADDRESS);
+        let (signature, recovery_id) = key
+            .sign_prehash_recoverable(witness.eip712_signing_hash(&domain).as_slice())
+            .unwrap();
+        let signature =
+            alloy::primitives::Signature::from_signature_and_parity(signature, recovery_id)
+                .unwrap();
+        let envelope = PeripheryPermit2Envelope::from_str(&format!(
+            "0x{}{}{}{}{}",
+            hex::encode(U256::from(513).to_be_bytes::<32>()),
+            hex::encode(deadline.to_be_bytes::<32>()),
+            hex::encode(signature.as_bytes()),
+            hex::encode(deposit.outputAmount.to_be_bytes::<32>()),
+            hex::encode(deposit.quoteTimestamp.to_be_bytes()),
+        ))
+        .unwrap();
+        assert_eq!(envelope.output_amount, U256::from(1_999_000u32));
+        assert_eq!(envelope.quote_timestamp, 1_699_999_000);
+
+        let call =
+            get_permit2_deposit_call(8453, &periphery, deposit.clone(), &envelope.permit).unwrap();
+        let call =
+            SpokePoolV3Periphery::depositWithPermit2Call::abi_decode(&call.abi_encode(), true)
+                .unwrap();
+        assert_eq!(call.signatureOwner, depositor);
+        assert_eq!(call.depositData.inputAmount, U256::from(2_000_000u32));
+        assert_eq!(
+            call.depositData.baseDepositData.outputAmount,
+            U256::from(1_999_000u32)
+        );
+        assert_eq!(call.depositData.baseDepositData.fillDeadline, 1_700_000_120);
+        assert_eq!(call.permit.permitted.amount, U256::from(2_000_000u32));
+        assert_eq!(call.permit.nonce, U256::from(513));
+        assert_eq!(call.signature.to_vec(), signature.as_bytes().to_vec());
+
+        // the signature does not cover a different deposit, e.g. at an updated quote
+        let deposit = depositV3Call {
+            outputAmount: U256::from(1_998_000u32),
+            ..deposit
+        };
+        assert!(get_permit2_deposit_call(8453, &periphery, deposit, &envelope.permit).is_err());
+    }
+
+    #[test]
+    fn get_quote_from_suggested_fees() {
+        let query_params = QuoteQueryParams {
+            origin_chain_id: utils::Chain::Base as u32,
+            input_token: "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913",
+            destination_chain_id: utils::Chain::Arbitrum as u32,
+            output_token: "0xaf88d065e77c8cC2239327C5EDb3A432268e5831",
+            recipient: None,
+            amount: U256::from(2_000_000u32),
+            message: None,
+        };
+        let fees_response: SuggestedFeesResponse = serde_json::from_value(serde_json::json!({
+            "capitalFeePct": "0", "capitalFeeTotal": "100",
+            "relayGasFeePct": "0", "relayGasFeeTotal": "700",
+            "relayFeePct": "0", "relayFeeTotal": "800",
+            "lpFeePct": "0", "timestamp": "1634160000",
+            "isAmountTooLow": false, "quoteBlock": "1",
+            "spokePoolAddress": "0x09aea4b2242abC8bb4BB78D537A67a245A7bEC64",
+            "totalRelayFee": { "pct": "0", "total": "1000" },
+            "relayerCapitalFee": { "pct": "0", "total": "100" },
+            "relayerGasFee": { "pct": "0", "total": "700" },
+            "lpFee": { "pct": "0", "total": "200" },
+            "estimatedFillTimeSec": 4
+        }))
+        .unwrap();
+
+        let quote = get_quote_from_fees(&query_params, &fees_response, 1634160000).unwrap();
+        assert_eq!(quote.input_amount, U256::from(2_000_000u32));
+        assert_eq!(quote.output_amount, U256::from(1_999_000u32));
+        assert_eq!(quote.fees.len(), 3);
+        assert_eq!(quote.estimated_fill_time_secs, 4);
+        assert_eq!(quote.expires_at, 1634160000 + QUOTE_VALIDITY_SECS);
+    }
+
+    #[test]
+    fn available_route() {
+        let routes: Vec<AvailableRoute> = serde_json::from_value(serde_json::json!([{
+            "originChainId": 8453,
+            "originToken": "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913",
+            "destinationChainId": 42161,
+            "destinationToken": "0xaf88d065e77c8cC2239327C5EDb3A432268e5831",
+            "originTokenSymbol": "USDC",
+            "destinationTokenSymbol": "USDC"
+        }]))
+        .unwrap();
+        let params = LimitQueryParams {
+            origin_chain_id: 8453,
+            // token addresses are matched case insensitively
+            input_token: "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913",
+            destination_chain_id: 42161,
+            output_token: "0xaf88d065e77c8cc2239327c5edb3a432268e5831",
+        };
+        assert!(check_available_route(&routes, &params).is_ok());
+
+        let err = check_available_route(
+            &routes,
+            &LimitQueryParams {
+                output_token: "0xFF970A61A04b1cA14834A43f5dE4533eBDDB5CC8", // USDC.e
+                ..params
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(
+            crate::BridgeError::from_report(&err),
+            crate::BridgeError::ProviderRejected(_)
+        ));
+    }
+
+    #[test]
+    fn get_deposit_token_native() {
+        let weth_base = "0x4200000000000000000000000000000000000006";
+        let native_tokens = [
+            "0x0000000000000000000000000000000000000000",
+            "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE",
+        ];
+        for token in native_tokens {
+            assert_eq!(
+                get_deposit_token(utils::Chain::Base as u32, token),
+                weth_base
+            );
+        }
+        // erc20 tokens are deposited as-is
+        assert_eq!(
+            get_deposit_token(
+                utils::Chain::Base as u32,
+                "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913"
+            ),
+            "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913"
+        );
+    }
+
     #[tokio::test]
     async fn get_bridging_data_no_inner_calldata() {
         let bridge = AcrossBridge::new();
@@ -374,14 +1407,18 @@ mod tests {
             src_chain_id: utils::Chain::Base as u32, // Base
             src_token: "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913".into(), // USDC Base
             src_caller: "0x000007357111E4789005d4eBfF401a18D99770cE".into(), // caller is recipient
-            src_amount: U256::from(2000_000u32),     // 4 USDC
+            src_amount: U256::from(2_000_000u32),    // 4 USDC
             src_chain_token_in_sender_permit: None,
             dest_chain_id: utils::Chain::Arbitrum as u32, // Arbitrum
             dest_token: "0xaf88d065e77c8cC2239327C5EDb3A432268e5831".into(), // USDC Arbitrum
             dest_recipient: "0x000007357111E4789005d4eBfF401a18D99770cE".into(), // recipient
             dest_amount: None,
+            permit_deadline: None,
             calldata: None,
+            strategy: None,
             simulate: false,
+            simulate_balance: false,
+            assume_approved: false,
         };
         let response = bridge.get_bridging_data(&request).await;
         assert!(response.is_ok());
@@ -396,16 +1433,20 @@ mod tests {
             src_chain_id: utils::Chain::Base as u32, // Base
             src_token: "0xd9aAEc86B65D86f6A7B5B1b0c42FFA531710b6CA".into(), // USDCbC Base // NOTE: not supported by across
             src_caller: "0x000007357111E4789005d4eBfF401a18D99770cE".into(), // caller is recipient
-            src_amount: crate::U256::from(2000_000u32),                     // 2 USDC
+            src_amount: crate::U256::from(2_000_000u32),                    // 2 USDC
             src_chain_token_in_sender_permit: None,
             dest_chain_id: utils::Chain::Arbitrum as u32, // Arbitrum
             dest_token: "0xaf88d065e77c8cC2239327C5EDb3A432268e5831".into(), // USDC Arbitrum
             dest_recipient: "0x000007357111E4789005d4eBfF401a18D99770cE".into(), // recipient
             dest_amount: None,
+            permit_deadline: None,
             calldata: None,
+            strategy: None,
             simulate: false,
+            simulate_balance: false,
+            assume_approved: false,
         };
         let response = bridge.get_bridging_data(&request).await;
-        assert!(!response.is_ok());
+        assert!(response.is_err());
     }
 }
@@ -0,0 +1,708 @@
+use crate::U256;
+use std::{collections::HashMap, str::FromStr};
+
+use alloy::primitives::{address, Address, Bytes};
+use alloy::providers::{network::TransactionBuilder, Provider, ProviderBuilder, RootProvider};
+use alloy::rpc::types::{BlockNumberOrTag, TransactionRequest};
+use alloy::sol;
+use alloy::sol_types::{SolCall, SolValue};
+use eyre::Result;
+
+sol! {
+    /// Arbitrum canonical token bridge router on L1; routes deposits to the token's gateway, which creates
+    /// a retryable ticket finalizing the deposit on L2
+    interface IL1GatewayRouter {
+        function outboundTransferCustomRefund(
+            address _token,
+            address _refundTo,
+            address _to,
+            uint256 _amount,
+            uint256 _maxGas,
+            uint256 _gasPriceBid,
+            bytes calldata _data
+        ) external payable returns (bytes memory);
+
+        function getGateway(address _token) external view returns (address gateway);
+        function calculateL2TokenAddress(address l1ERC20) external view returns (address);
+        function inbox() external view returns (address);
+    }
+
+    interface IL1ArbitrumGateway {
+        function getOutboundCalldata(
+            address _token,
+            address _from,
+            address _to,
+            uint256 _amount,
+            bytes memory _data
+        ) external view returns (bytes memory outboundCalldata);
+
+        function counterpartGateway() external view returns (address);
+    }
+
+    interface IInbox {
+        function calculateRetryableSubmissionFee(uint256 dataLength, uint256 baseFee) external view returns (uint256);
+    }
+
+    /// Virtual contract on Arbitrum nodes; only callable through `eth_call`/`eth_estimateGas`
+    interface INodeInterface {
+        function estimateRetryableTicket(
+            address sender,
+            uint256 deposit,
+            address to,
+            uint256 l2CallValue,
+            address excessFeeRefundAddress,
+            address callValueRefundAddress,
+            bytes calldata data
+        ) external;
+    }
+}
+
+sol! {
+    /// Arbitrum canonical token bridge router on L2; routes withdrawals to the token's gateway, which burns the
+    /// tokens and sends an L2 -> L1 message, executed through the L1 outbox after the challenge period
+    interface IL2GatewayRouter {
+        function outboundTransfer(
+            address _l1Token,
+            address _to,
+            uint256 _amount,
+            bytes calldata _data
+        ) external payable returns (bytes memory);
+
+        function getGateway(address _token) external view returns (address gateway);
+        function defaultGateway() external view returns (address);
+    }
+}
+
+const NODE_INTERFACE_ADDRESS: Address = address!("00000000000000000000000000000000000000C8");
+
+/// Buffers applied to the retryable ticket estimates, as defaulted by the arbitrum sdk; unused fees are refunded
+/// on L2 to the refund address
+const SUBMISSION_FEE_PERCENT_INCREASE: u64 = 300;
+const GAS_PRICE_PERCENT_INCREASE: u64 = 500;
+
+/// Deposit provided to `estimateRetryableTicket`, covering the simulated retryable fees (1 ETH)
+const ESTIMATE_SENDER_DEPOSIT: u128 = 1_000_000_000_000_000_000;
+
+/// Deposits are executed on L2 once the L1 tx is picked up by the sequencer, typically ~10 minutes
+const ESTIMATED_DEPOSIT_SECS: u64 = 10 * 60;
+
+/// Withdrawals (L2 -> L1) can only be executed on L1 after the ~7 day challenge period
+const WITHDRAWAL_DELAY_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Validity of a quote; the retryable fees are bound to the L1 base fee and L2 gas price
+const QUOTE_VALIDITY_SECS: u64 = 60;
+
+/// Validity of a withdrawal quote; withdrawals have no fees, the quote only depends on the route
+const WITHDRAWAL_QUOTE_VALIDITY_SECS: u64 = 5 * 60;
+
+/// Retryable ticket parameters of a deposit, paid for by the tx value
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryableParams {
+    /// Gateway of the token, transferring the deposited tokens; the spender to be approved
+    pub gateway: Address,
+    pub max_submission_cost: U256,
+    pub gas_limit: U256,
+    pub max_fee_per_gas: U256,
+}
+
+impl RetryableParams {
+    /// ETH value required by the deposit tx; any excess is refunded on L2
+    pub fn value(&self) -> U256 {
+        self.max_submission_cost + self.gas_limit * self.max_fee_per_gas
+    }
+}
+
+fn percent_increase(value: U256, percent: u64) -> U256 {
+    value + value * U256::from(percent) / U256::from(100)
+}
+
+/// Resolves the L1 gateway router of the Arbitrum destination chain; only Ethereum -> L2 deposits are supported.
+fn get_l1_gateway_router(src_chain_id: u32, dest_chain_id: u32) -> Result<Address> {
+    if src_chain_id != utils::Chain::Ethereum as u32 {
+        return Err(crate::BridgeError::UnsupportedChain(src_chain_id).into());
+    }
+    let router = utils::get_supported_chains()
+        .get(&dest_chain_id)
+        .and_then(|chain| chain.l1_gateway_router)
+        .ok_or(crate::BridgeError::UnsupportedChain(dest_chain_id))?;
+    Ok(Address::from_str(router)?)
+}
+
+/// Resolves the L2 gateway router of the Arbitrum source chain, and the L1 outbox executing its withdrawals; only
+/// L2 -> Ethereum withdrawals are supported.
+fn get_l2_gateway_router(src_chain_id: u32, dest_chain_id: u32) -> Result<(Address, Address)> {
+    if dest_chain_id != utils::Chain::Ethereum as u32 {
+        return Err(crate::BridgeError::UnsupportedChain(dest_chain_id).into());
+    }
+    let chain = utils::get_supported_chains()
+        .get(&src_chain_id)
+        .copied()
+        .ok_or(crate::BridgeError::UnsupportedChain(src_chain_id))?;
+    match (chain.l2_gateway_router, chain.l1_outbox) {
+        (Some(router), Some(outbox)) => {
+            Ok((Address::from_str(router)?, Address::from_str(outbox)?))
+        }
+        _ => Err(crate::BridgeError::UnsupportedChain(src_chain_id).into()),
+    }
+}
+
+/// Withdrawals are not delivered by the rollup; the caller executes the L2 -> L1 message on the outbox.
+fn get_withdrawal_claim(outbox: &Address) -> crate::DestinationClaim {
+    crate::DestinationClaim {
+        chain_id: utils::Chain::Ethereum as u32,
+        contract: outbox.to_string(),
+        function: "executeTransaction(bytes32[] proof, uint256 index, address l2Sender, address to, \
+                   uint256 l2Block, uint256 l1Block, uint256 l2Timestamp, uint256 value, bytes data)"
+            .to_string(),
+        description: format!(
+            "execute the `L2ToL1Tx` event of the confirmed bridging tx once the challenge period has elapsed, \
+             with the proof returned by `constructOutboxProof(uint64 size, uint64 leaf)` of the node interface \
+             ({}) on the source chain",
+            NODE_INTERFACE_ADDRESS
+        ),
+    }
+}
+
+/// Rejects the request options not supported by the gateway routers; the bridged amount is minted or released
+/// 1:1 on the destination chain.
+fn check_request_options(request: &crate::BridgeRequest) -> Result<()> {
+    if request.calldata.is_some() {
+        return Err(crate::BridgeError::ProviderRejected(
+            "Arbitrum: destination calldata is not supported".to_string(),
+        )
+        .into());
+    }
+    if request.src_chain_token_in_sender_permit.is_some() {
+        return Err(crate::BridgeError::ProviderRejected(
+            "Arbitrum: permit signatures are not supported".to_string(),
+        )
+        .into());
+    }
+    if let Some(dest_amount) = request.dest_amount {
+        if dest_amount > request.src_amount {
+            return Err(eyre::eyre!(
+                "requested dest amount is greater than output amount"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Parses the ERC-20 route tokens; native currency deposits go through the inbox rather than the router.
+fn parse_tokens(src_token: &str, dest_token: &str) -> Result<(Address, Address)> {
+    if utils::is_native_token(src_token) || utils::is_native_token(dest_token) {
+        return Err(crate::BridgeError::ProviderRejected(
+            "Arbitrum: native currency is not supported by the gateway router".to_string(),
+        )
+        .into());
+    }
+    Ok((
+        Address::from_str(src_token)?,
+        Address::from_str(dest_token)?,
+    ))
+}
+
+fn get_outbound_transfer_call(
+    token: &Address,
+    refund_to: &Address,
+    to: &Address,
+    amount: U256,
+    params: &RetryableParams,
+) -> IL1GatewayRouter::outboundTransferCustomRefundCall {
+    IL1GatewayRouter::outboundTransferCustomRefundCall {
+        _token: *token,
+        _refundTo: *refund_to,
+        _to: *to,
+        _amount: amount,
+        _maxGas: params.gas_limit,
+        _gasPriceBid: params.max_fee_per_gas,
+        // abi.encode(uint256 maxSubmissionCost, bytes callHookData)
+        _data: (params.max_submission_cost, Bytes::new())
+            .abi_encode_params()
+            .into(),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ArbitrumBridge {
+    providers: HashMap<u32, RootProvider<alloy::transports::http::Http<reqwest::Client>>>,
+}
+
+impl Default for ArbitrumBridge {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ArbitrumBridge {
+    pub fn new() -> Self {
+        let supported_providers = utils::get_supported_chains()
+            .iter()
+            .map(|(id, chain)| {
+                let rpc_url = reqwest::Url::parse(chain.rpc_url).unwrap(); // infallible
+                let provider = ProviderBuilder::new().on_http(rpc_url);
+                (*id, provider)
+            })
+            .collect::<HashMap<_, _>>();
+
+        Self {
+            providers: supported_providers,
+        }
+    }
+
+    fn get_provider(
+        &self,
+        chain_id: u32,
+    ) -> Result<&RootProvider<alloy::transports::http::Http<reqwest::Client>>> {
+        Ok(self
+            .providers
+            .get(&chain_id)
+            .ok_or(crate::BridgeError::UnsupportedChain(chain_id))?)
+    }
+
+    /// Validates that the L2 token is the token minted by the router's gateway for the L1 token; both the L1 and
+    /// L2 routers resolve the L2 token, `router_chain_id` being the chain of the router.
+    async fn check_l2_token(
+        &self,
+        router_chain_id: u32,
+        router: &Address,
+        l1_token: &Address,
+        l2_token: &Address,
+    ) -> Result<()> {
+        let provider = self.get_provider(router_chain_id)?;
+        let expected = utils::eth_call(
+            provider,
+            router,
+            IL1GatewayRouter::calculateL2TokenAddressCall { l1ERC20: *l1_token },
+        )
+        .await?
+        ._0;
+        if expected != *l2_token {
+            return Err(crate::BridgeError::ProviderRejected(format!(
+                "Arbitrum: {} is bridged as {}, not {}",
+                l1_token, expected, l2_token
+            ))
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Validates that the token is withdrawn through the default (standard ERC-20) gateway, burning the withdrawn
+    /// tokens without an approval; custom gateways may require one, e.g. to burn DAI.
+    async fn check_default_gateway(
+        &self,
+        chain_id: u32,
+        router: &Address,
+        l1_token: &Address,
+    ) -> Result<()> {
+        let provider = self.get_provider(chain_id)?;
+        let (gateway, default_gateway) = tokio::try_join!(
+            utils::eth_call(
+                provider,
+                router,
+                IL2GatewayRouter::getGatewayCall { _token: *l1_token },
+            ),
+            utils::eth_call(provider, router, IL2GatewayRouter::defaultGatewayCall {}),
+        )?;
+        if gateway.gateway != default_gateway._0 {
+            return Err(crate::BridgeError::ProviderRejected(format!(
+                "Arbitrum: {} is withdrawn through a custom gateway, which is not supported",
+                l1_token
+            ))
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Withdraws the L2 tokens to Ethereum; the funds are received once the caller executes the withdrawal on the
+    /// L1 outbox, after the challenge period.
+    async fn get_withdrawal_data(
+        &self,
+        request: &crate::BridgeRequest,
+    ) -> Result<crate::BridgeResponse> {
+        let (router, outbox) = get_l2_gateway_router(request.src_chain_id, request.dest_chain_id)?;
+        let (l2_token, l1_token) = parse_tokens(&request.src_token, &request.dest_token)?;
+        check_request_options(request)?;
+
+        let recipient = Address::from_str(&request.dest_recipient)?;
+        tokio::try_join!(
+            self.check_l2_token(request.src_chain_id, &router, &l1_token, &l2_token),
+            self.check_default_gateway(request.src_chain_id, &router, &l1_token),
+        )?;
+
+        // the default gateway burns the tokens of the caller; no approval is required
+        let calldata = IL2GatewayRouter::outboundTransferCall {
+            _l1Token: l1_token,
+            _to: recipient,
+            _amount: request.src_amount,
+            _data: Bytes::new(),
+        };
+        Ok(crate::BridgeResponse::new(
+            crate::SupportedProviders::Arbitrum,
+            crate::BridgeAction::BridgingTx(crate::TxData {
+                data: hex::encode(calldata.abi_encode()),
+                to: router.to_string(),
+                value: U256::ZERO.to_string(),
+                ..Default::default()
+            }),
+        )
+        .with_estimated_fill_time_secs(Some(WITHDRAWAL_DELAY_SECS))
+        .with_destination_claim(Some(get_withdrawal_claim(&outbox))))
+    }
+
+    async fn get_withdrawal_quote(
+        &self,
+        request: &crate::QuoteRequest,
+    ) -> Result<crate::BridgeQuote> {
+        let (router, _) = get_l2_gateway_router(request.src_chain_id, request.dest_chain_id)?;
+        let (l2_token, l1_token) = parse_tokens(&request.src_token, &request.dest_token)?;
+        if request.calldata.is_some() {
+            return Err(crate::BridgeError::ProviderRejected(
+                "Arbitrum: destination calldata is not supported".to_string(),
+            )
+            .into());
+        }
+        tokio::try_join!(
+            self.check_l2_token(request.src_chain_id, &router, &l1_token, &l2_token),
+            self.check_default_gateway(request.src_chain_id, &router, &l1_token),
+        )?;
+
+        // no bridge fees; the L1 execution is paid by the caller, once the challenge period has elapsed
+        Ok(crate::BridgeQuote::new(
+            crate::SupportedProviders::Arbitrum,
+            request.src_amount,
+            request.src_amount,
+            vec![],
+            WITHDRAWAL_DELAY_SECS,
+            utils::get_unix_timestamp().saturating_add(WITHDRAWAL_QUOTE_VALIDITY_SECS),
+        ))
+    }
+
+    /// Returns the L1 gateway of the token, transferring the deposited tokens; the spender to be approved.
+    pub async fn get_gateway(&self, router: &Address, token: &Address) -> Result<Address> {
+        Ok(utils::eth_call(
+            self.get_provider(utils::Chain::Ethereum as u32)?,
+            router,
+            IL1GatewayRouter::getGatewayCall { _token: *token },
+        )
+        .await?
+        .gateway)
+    }
+
+    /// Estimates the retryable ticket parameters of a deposit through the token `gateway`: the submission cost is
+    /// calculated by the L1 inbox, and the L2 gas limit is estimated through the node interface.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn get_retryable_params(
+        &self,
+        router: &Address,
+        gateway: Address,
+        dest_chain_id: u32,
+        token: &Address,
+        from: &Address,
+        to: &Address,
+        amount: U256,
+    ) -> Result<RetryableParams> {
+        let l1_provider = self.get_provider(utils::Chain::Ethereum as u32)?;
+        let l2_provider = self.get_provider(dest_chain_id)?;
+
+        let (outbound_calldata, counterpart_gateway, inbox, l1_base_fee) = tokio::try_join!(
+            utils::eth_call(
+                l1_provider,
+                &gateway,
+                IL1ArbitrumGateway::getOutboundCalldataCall {
+                    _token: *token,
+                    _from: *from,
+                    _to: *to,
+                    _amount: amount,
+                    _data: Bytes::new(),
+                },
+            ),
+            utils::eth_call(
+                l1_provider,
+                &gateway,
+                IL1ArbitrumGateway::counterpartGatewayCall {}
+            ),
+            utils::eth_call(l1_provider, router, IL1GatewayRouter::inboxCall {}),
+            async {
+                l1_provider
+                    .get_block_by_number(BlockNumberOrTag::Latest, false)
+                    .await?
+                    .and_then(|block| block.header.base_fee_per_gas)
+                    .ok_or_else(|| eyre::eyre!("L1 base fee not found"))
+            },
+        )?;
+        let outbound_calldata = outbound_calldata.outboundCalldata;
+
+        let estimate_tx = TransactionRequest::default()
+            .with_to(NODE_INTERFACE_ADDRESS)
+            .with_input::<Bytes>(
+                INodeInterface::estimateRetryableTicketCall {
+                    sender: gateway,
+                    deposit: U256::from(ESTIMATE_SENDER_DEPOSIT),
+                    to: counterpart_gateway._0,
+                    l2CallValue: U256::ZERO,
+                    excessFeeRefundAddress: *to,
+                    callValueRefundAddress: *to,
+                    data: outbound_calldata.clone(),
+                }
+                .abi_encode()
+                .into(),
+            );
+        let (submission_fee, gas_limit, gas_price) = tokio::try_join!(
+            utils::eth_call(
+                l1_provider,
+                &inbox._0,
+                IInbox::calculateRetryableSubmissionFeeCall {
+                    dataLength: U256::from(outbound_calldata.len()),
+                    baseFee: U256::from(l1_base_fee),
+                },
+            ),
+            async { Ok::<_, eyre::Report>(l2_provider.estimate_gas(&estimate_tx).await?) },
+            async { Ok::<_, eyre::Report>(l2_provider.get_gas_price().await?) },
+        )?;
+
+        Ok(RetryableParams {
+            gateway,
+            max_submission_cost: percent_increase(
+                submission_fee._0,
+                SUBMISSION_FEE_PERCENT_INCREASE,
+            ),
+            gas_limit: U256::from(gas_limit),
+            max_fee_per_gas: percent_increase(U256::from(gas_price), GAS_PRICE_PERCENT_INCREASE),
+        })
+    }
+}
+
+/// Ethereum -> Arbitrum deposits go through the L1 gateway router, and Arbitrum -> Ethereum withdrawals through the
+/// L2 gateway router.
+impl crate::BridgeProvider for ArbitrumBridge {
+    async fn get_bridging_data(
+        &self,
+        request: &crate::BridgeRequest,
+    ) -> eyre::Result<crate::BridgeResponse> {
+        if request.src_chain_id != utils::Chain::Ethereum as u32 {
+            return self.get_withdrawal_data(request).await;
+        }
+        let router = get_l1_gateway_router(request.src_chain_id, request.dest_chain_id)?;
+        let (l1_token, l2_token) = parse_tokens(&request.src_token, &request.dest_token)?;
+        check_request_options(request)?;
+
+        let caller = Address::from_str(&request.src_caller)?;
+        let recipient = Address::from_str(&request.dest_recipient)?;
+        let (_, gateway) = tokio::try_join!(
+            self.check_l2_token(request.src_chain_id, &router, &l1_token, &l2_token),
+            self.get_gateway(&router, &l1_token),
+        )?;
+
+        // the token gateway (rather than the router) transfers the deposited tokens; permits are not consumed
+        let allowance_action = request
+            .allowance_action(utils::get_token_allowance_action(
+                self.get_provider(request.src_chain_id)?,
+                &l1_token,
+                &request.src_amount,
+                &caller,
+                &gateway,
+                &utils::PermitOptions {
+                    spender: None,
+                    permit2_kind: None,
+                    deadline: utils::get_permit_deadline(request.permit_deadline),
+                },
+            ))
+            .await?;
+
+        // if there is an pre-allowance tx/sig/action required, it must be returned
+        // to be executed by the caller
+        if let Some(response) = crate::BridgeResponse::from_allowance_action(
+            crate::SupportedProviders::Arbitrum,
+            allowance_action,
+        ) {
+            return Ok(response
+                .with_estimated_fill_time_secs(Some(ESTIMATED_DEPOSIT_SECS))
+                .with_withdrawal_delay_secs(Some(WITHDRAWAL_DELAY_SECS)));
+        }
+
+        // the retryable fees are only estimated for the bridging tx, once approved
+        let params = self
+            .get_retryable_params(
+                &router,
+                gateway,
+                request.dest_chain_id,
+                &l1_token,
+                &caller,
+                &recipient,
+                request.src_amount,
+            )
+            .await?;
+
+        // excess retryable fees are refunded to the caller on L2
+        let calldata =
+            get_outbound_transfer_call(&l1_token, &caller, &recipient, request.src_amount, &params);
+        Ok(crate::BridgeResponse::new(
+            crate::SupportedProviders::Arbitrum,
+            crate::BridgeAction::BridgingTx(crate::TxData {
+                data: hex::encode(calldata.abi_encode()),
+                to: router.to_string(),
+                value: params.value().to_string(),
+                ..Default::default()
+            }),
+        )
+        .with_estimated_fill_time_secs(Some(ESTIMATED_DEPOSIT_SECS))
+        .with_withdrawal_delay_secs(Some(WITHDRAWAL_DELAY_SECS)))
+    }
+
+    async fn get_quote(&self, request: &crate::QuoteRequest) -> eyre::Result<crate::BridgeQuote> {
+        if request.src_chain_id != utils::Chain::Ethereum as u32 {
+            return self.get_withdrawal_quote(request).await;
+        }
+        let router = get_l1_gateway_router(request.src_chain_id, request.dest_chain_id)?;
+        let (l1_token, l2_token) = parse_tokens(&request.src_token, &request.dest_token)?;
+        if request.calldata.is_some() {
+            return Err(crate::BridgeError::ProviderRejected(
+                "Arbitrum: destination calldata is not supported".to_string(),
+            )
+            .into());
+        }
+
+        // the retryable ticket size does not depend on the addresses; the recipient is optional for quotes
+        let recipient = request
+            .dest_recipient
+            .as_deref()
+            .map(Address::from_str)
+            .transpose()?
+            .unwrap_or_default();
+        let (_, params) = tokio::try_join!(
+            self.check_l2_token(request.src_chain_id, &router, &l1_token, &l2_token),
+            async {
+                let gateway = self.get_gateway(&router, &l1_token).await?;
+                self.get_retryable_params(
+                    &router,
+                    gateway,
+                    request.dest_chain_id,
+                    &l1_token,
+                    &recipient,
+                    &recipient,
+                    request.src_amount,
+                )
+                .await
+            },
+        )?;
+
+        let retryable_fee = params.value();
+        Ok(crate::BridgeQuote::new(
+            crate::SupportedProviders::Arbitrum,
+            request.src_amount,
+            request.src_amount,
+            // max retryable fees paid as the tx value; the unused fees are refunded on L2
+            vec![crate::QuoteFee {
+                name: "retryableTicketFee".to_string(),
+                token: Address::ZERO.to_string(),
+                amount: retryable_fee,
+            }],
+            ESTIMATED_DEPOSIT_SECS,
+            utils::get_unix_timestamp().saturating_add(QUOTE_VALIDITY_SECS),
+        )
+        .with_native_fee(retryable_fee)
+        .with_withdrawal_delay_secs(Some(WITHDRAWAL_DELAY_SECS)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn l1_gateway_router() {
+        assert_eq!(
+            get_l1_gateway_router(utils::Chain::Ethereum as u32, utils::Chain::Arbitrum as u32)
+                .unwrap(),
+            address!("72Ce9c846789fdB6fC1f34aC4AD25Dd9ef7031ef")
+        );
+        assert!(get_l1_gateway_router(
+            utils::Chain::Arbitrum as u32,
+            utils::Chain::Ethereum as u32
+        )
+        .is_err());
+        assert!(
+            get_l1_gateway_router(utils::Chain::Ethereum as u32, utils::Chain::Base as u32)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn l2_gateway_router() {
+        assert_eq!(
+            get_l2_gateway_router(utils::Chain::Arbitrum as u32, utils::Chain::Ethereum as u32)
+                .unwrap(),
+            (
+                address!("5288c571Fd7aD117beA99bF60FE0846C4E84F933"),
+                address!("0B9857ae2D4A3DBe74ffE1d7DF045bb7F96E4840")
+            )
+        );
+        // deposits go through the L1 router
+        assert!(get_l2_gateway_router(
+            utils::Chain::Ethereum as u32,
+            utils::Chain::Arbitrum as u32
+        )
+        .is_err());
+        // base is not an arbitrum rollup
+        assert!(
+            get_l2_gateway_router(utils::Chain::Base as u32, utils::Chain::Ethereum as u32)
+                .is_err()
+        );
+        assert!(
+            get_l2_gateway_router(utils::Chain::Arbitrum as u32, utils::Chain::Base as u32)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn withdrawal_claim() {
+        let outbox = address!("0B9857ae2D4A3DBe74ffE1d7DF045bb7F96E4840");
+        let claim = get_withdrawal_claim(&outbox);
+        assert_eq!(claim.chain_id, utils::Chain::Ethereum as u32);
+        assert_eq!(claim.contract, outbox.to_string());
+        assert!(claim
+            .function
+            .starts_with("executeTransaction(bytes32[] proof"));
+    }
+
+    #[test]
+    fn outbound_transfer_calldata() {
+        let params = RetryableParams {
+            gateway: address!("a3A7B6F88361F48403514059F1F16C8E78d60EeC"),
+            max_submission_cost: U256::from(1_000u32),
+            gas_limit: U256::from(100_000u32),
+            max_fee_per_gas: percent_increase(
+                U256::from(10_000_000u32),
+                GAS_PRICE_PERCENT_INCREASE,
+            ),
+        };
+        assert_eq!(params.max_fee_per_gas, U256::from(60_000_000u32));
+        assert_eq!(params.value(), U256::from(6_000_000_001_000u64));
+
+        let usdc = address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+        let caller = address!("000007357111E4789005d4eBfF401a18D99770cE");
+        let call =
+            get_outbound_transfer_call(&usdc, &caller, &caller, U256::from(2_000_000u32), &params);
+        assert_eq!(call._maxGas, params.gas_limit);
+        assert_eq!(call._gasPriceBid, params.max_fee_per_gas);
+        let (max_submission_cost, extra_data) =
+            <(U256, Bytes)>::abi_decode_params(&call._data, true).unwrap();
+        assert_eq!(max_submission_cost, params.max_submission_cost);
+        assert!(extra_data.is_empty());
+    }
+
+    #[test]
+    fn native_tokens_rejected() {
+        assert!(parse_tokens(
+            "0x0000000000000000000000000000000000000000",
+            "0x0000000000000000000000000000000000000000"
+        )
+        .is_err());
+        assert!(parse_tokens(
+            "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+            "0xFF970A61A04b1cA14834A43f5dE4533eBDDB5CC8"
+        )
+        .is_ok());
+    }
+}
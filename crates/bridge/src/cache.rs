@@ -0,0 +1,342 @@
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Maximum number of entries held by the in-memory backend, before expired entries are evicted
+const MAX_MEMORY_ENTRIES: usize = 10_000;
+
+/// Caching policy of an upstream endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CachePolicy {
+    /// Duration a cached response is served as is
+    pub ttl: Duration,
+    /// Duration past the ttl, during which the stale response is served while it is revalidated in the background
+    pub stale_ttl: Duration,
+}
+
+impl CachePolicy {
+    fn expiry(&self) -> Duration {
+        self.ttl + self.stale_ttl
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CacheEntry {
+    /// Unix timestamp (in milliseconds) at which the response was fetched
+    stored_at: u64,
+    value: serde_json::Value,
+}
+
+#[derive(Clone)]
+enum Backend {
+    /// Entries with their expiry (unix timestamp in milliseconds)
+    Memory(Arc<Mutex<HashMap<String, (u64, CacheEntry)>>>),
+    Redis(redis::aio::ConnectionManager),
+}
+
+impl std::fmt::Debug for Backend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Memory(entries) => f
+                .debug_tuple("Memory")
+                .field(&entries.lock().map(|e| e.len()).unwrap_or_default())
+                .finish(),
+            Self::Redis(_) => f.debug_tuple("Redis").finish(),
+        }
+    }
+}
+
+/// Cache of slowly changing upstream responses (e.g. Across transfer limits), with stale-while-revalidate
+/// semantics; backed by an in-memory map for single-node deployments, or Redis to be shared across nodes.
+#[derive(Debug, Clone)]
+pub struct Cache {
+    backend: Backend,
+    /// Keys being revalidated in the background
+    revalidating: Arc<Mutex<HashSet<String>>>,
+}
+
+impl Default for Cache {
+    fn default() -> Self {
+        Self::memory()
+    }
+}
+
+impl Cache {
+    pub fn memory() -> Self {
+        Self {
+            backend: Backend::Memory(Default::default()),
+            revalidating: Default::default(),
+        }
+    }
+
+    /// Connects to the Redis server at `url`; e.g. `redis://127.0.0.1:6379`
+    pub async fn redis(url: &str) -> eyre::Result<Self> {
+        let client = redis::Client::open(url)?;
+        Ok(Self {
+            backend: Backend::Redis(client.get_connection_manager().await?),
+            revalidating: Default::default(),
+        })
+    }
+
+    /// Returns the cached response of `key`, or fetches (and caches) it if missing or expired.
+    /// A stale response is returned as is, and revalidated in the background.
+    /// `endpoint` labels the cache hit/miss metrics.
+    pub async fn get_or_fetch<T, F, Fut>(
+        &self,
+        endpoint: &'static str,
+        key: &str,
+        policy: CachePolicy,
+        fetch: F,
+    ) -> eyre::Result<T>
+    where
+        T: Serialize + DeserializeOwned + Send + 'static,
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = eyre::Result<T>> + Send + 'static,
+    {
+        let cached = self
+            .get(key)
+            .await
+            .map_err(|e| tracing::warn!("failed to get cache entry {}: {}", key, e))
+            .ok()
+            .flatten()
+            .and_then(|entry| {
+                let value = serde_json::from_value::<T>(entry.value).ok()?;
+                Some((unix_now_ms().saturating_sub(entry.stored_at), value))
+            });
+
+        match cached {
+            Some((age, value)) if age < policy.ttl.as_millis() as u64 => {
+                record_request(endpoint, "hit");
+                Ok(value)
+            }
+            Some((age, value)) if age < policy.expiry().as_millis() as u64 => {
+                record_request(endpoint, "stale");
+                self.revalidate(key, policy, fetch);
+                Ok(value)
+            }
+            _ => {
+                record_request(endpoint, "miss");
+                let value = fetch().await?;
+                let entry = serde_json::to_value(&value)?;
+                if let Err(e) = self.set(key, entry, policy).await {
+                    tracing::warn!("failed to set cache entry {}: {}", key, e);
+                }
+                Ok(value)
+            }
+        }
+    }
+
+    /// Refetches `key` in the background, unless it is already being revalidated.
+    fn revalidate<T, F, Fut>(&self, key: &str, policy: CachePolicy, fetch: F)
+    where
+        T: Serialize + Send + 'static,
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = eyre::Result<T>> + Send + 'static,
+    {
+        let Ok(mut revalidating) = self.revalidating.lock() else {
+            return;
+        };
+        if !revalidating.insert(key.to_string()) {
+            return;
+        }
+        drop(revalidating);
+
+        let cache = self.clone();
+        let key = key.to_string();
+        tokio::spawn(async move {
+            match fetch()
+                .await
+                .and_then(|value| Ok(serde_json::to_value(value)?))
+            {
+                Ok(value) => {
+                    if let Err(e) = cache.set(&key, value, policy).await {
+                        tracing::warn!("failed to set cache entry {}: {}", key, e);
+                    }
+                }
+                Err(e) => tracing::warn!("failed to revalidate cache entry {}: {}", key, e),
+            }
+            if let Ok(mut revalidating) = cache.revalidating.lock() {
+                revalidating.remove(&key);
+            }
+        });
+    }
+
+    async fn get(&self, key: &str) -> eyre::Result<Option<CacheEntry>> {
+        match &self.backend {
+            Backend::Memory(entries) => {
+                let entries = entries
+                    .lock()
+                    .map_err(|_| eyre::eyre!("cache lock poisoned"))?;
+                Ok(entries
+                    .get(key)
+                    .filter(|(expires_at, _)| *expires_at > unix_now_ms())
+                    .map(|(_, entry)| entry.clone()))
+            }
+            Backend::Redis(connection) => {
+                let value: Option<String> = redis::cmd("GET")
+                    .arg(key)
+                    .query_async(&mut connection.clone())
+                    .await?;
+                Ok(value.map(|v| serde_json::from_str(&v)).transpose()?)
+            }
+        }
+    }
+
+    async fn set(
+        &self,
+        key: &str,
+        value: serde_json::Value,
+        policy: CachePolicy,
+    ) -> eyre::Result<()> {
+        let now = unix_now_ms();
+        let entry = CacheEntry {
+            stored_at: now,
+            value,
+        };
+        let expiry = policy.expiry().as_millis() as u64;
+
+        match &self.backend {
+            Backend::Memory(entries) => {
+                let mut entries = entries
+                    .lock()
+                    .map_err(|_| eyre::eyre!("cache lock poisoned"))?;
+                if entries.len() >= MAX_MEMORY_ENTRIES {
+                    entries.retain(|_, (expires_at, _)| *expires_at > now);
+                }
+                entries.insert(key.to_string(), (now + expiry, entry));
+            }
+            Backend::Redis(connection) => {
+                redis::cmd("SET")
+                    .arg(key)
+                    .arg(serde_json::to_string(&entry)?)
+                    .arg("PX")
+                    .arg(expiry)
+                    .query_async::<_, ()>(&mut connection.clone())
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn record_request(endpoint: &'static str, result: &'static str) {
+    metrics::counter!("cache_requests_total", "endpoint" => endpoint, "result" => result)
+        .increment(1);
+}
+
+fn unix_now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    fn counting_fetch(
+        calls: &Arc<AtomicUsize>,
+    ) -> impl FnOnce() -> futures::future::Ready<eyre::Result<usize>> {
+        let calls = calls.clone();
+        move || futures::future::ready(Ok(calls.fetch_add(1, Ordering::SeqCst) + 1))
+    }
+
+    #[tokio::test]
+    async fn memory_hit_and_miss() {
+        let cache = Cache::memory();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let policy = CachePolicy {
+            ttl: Duration::from_secs(60),
+            stale_ttl: Duration::ZERO,
+        };
+
+        let value = cache
+            .get_or_fetch("test", "key", policy, counting_fetch(&calls))
+            .await
+            .unwrap();
+        assert_eq!(value, 1);
+        // served from the cache
+        let value = cache
+            .get_or_fetch("test", "key", policy, counting_fetch(&calls))
+            .await
+            .unwrap();
+        assert_eq!(value, 1);
+        // other keys are fetched
+        let value = cache
+            .get_or_fetch("test", "other", policy, counting_fetch(&calls))
+            .await
+            .unwrap();
+        assert_eq!(value, 2);
+
+        // failed fetches are not cached
+        let result: eyre::Result<usize> = cache
+            .get_or_fetch("test", "failing", policy, || async {
+                Err(eyre::eyre!("upstream error"))
+            })
+            .await;
+        assert!(result.is_err());
+        assert!(cache.get("failing").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn memory_stale_while_revalidate() {
+        let cache = Cache::memory();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let policy = CachePolicy {
+            ttl: Duration::ZERO,
+            stale_ttl: Duration::from_secs(60),
+        };
+
+        let value = cache
+            .get_or_fetch("test", "key", policy, counting_fetch(&calls))
+            .await
+            .unwrap();
+        assert_eq!(value, 1);
+        // stale value is returned, while revalidated in the background
+        let value = cache
+            .get_or_fetch("test", "key", policy, counting_fetch(&calls))
+            .await
+            .unwrap();
+        assert_eq!(value, 1);
+
+        for _ in 0..100 {
+            if !cache.revalidating.lock().unwrap().contains("key") {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        let value = cache
+            .get_or_fetch("test", "key", policy, counting_fetch(&calls))
+            .await
+            .unwrap();
+        assert_eq!(value, 2);
+    }
+
+    #[tokio::test]
+    async fn memory_expired() {
+        let cache = Cache::memory();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let policy = CachePolicy {
+            ttl: Duration::ZERO,
+            stale_ttl: Duration::ZERO,
+        };
+
+        for expected in 1..=2 {
+            let value = cache
+                .get_or_fetch("test", "key", policy, counting_fetch(&calls))
+                .await
+                .unwrap();
+            assert_eq!(value, expected);
+        }
+    }
+}
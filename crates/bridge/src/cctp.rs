@@ -0,0 +1,414 @@
+use crate::U256;
+use std::{collections::HashMap, str::FromStr};
+
+use alloy::primitives::{address, Address};
+use alloy::providers::{ProviderBuilder, RootProvider};
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use eyre::Result;
+
+sol! {
+    /// Circle CCTP token messenger; burns USDC on the source chain, to be minted on the destination domain
+    /// once the burn message has been attested by Circle.
+    interface ITokenMessenger {
+        function depositForBurn(
+            uint256 amount,
+            uint32 destinationDomain,
+            bytes32 mintRecipient,
+            address burnToken
+        ) external returns (uint64 _nonce);
+
+        function localMinter() external view returns (address);
+    }
+
+    interface ITokenMinter {
+        function burnLimitsPerMessage(address token) external view returns (uint256);
+    }
+}
+
+/// Standard transfers are attested once the burn tx has reached hard finality on the source chain
+const ESTIMATED_ATTESTATION_SECS: u64 = 15 * 60;
+
+/// Validity of a quote; transfers have no fees, the quote is only bound by the burn limit
+const QUOTE_VALIDITY_SECS: u64 = 5 * 60;
+
+/// Burn limits are only updated by Circle, when raising the per message limit
+pub const BURN_LIMIT_CACHE_POLICY: crate::cache::CachePolicy = crate::cache::CachePolicy {
+    ttl: std::time::Duration::from_secs(60 * 60),
+    stale_ttl: std::time::Duration::from_secs(24 * 60 * 60),
+};
+
+/// Circle attestation api, returning the attestation of a burn message by its keccak256 hash
+const ATTESTATION_API_URL: &str = "https://iris-api.circle.com/v1/attestations";
+
+/// CCTP deployment of a chain
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CctpDomain {
+    /// Circle assigned domain id, distinct from the chain id
+    pub domain: u32,
+    pub token_messenger: Address,
+    /// Receives the attested burn messages of the other domains, minting the burnt USDC
+    pub message_transmitter: Address,
+    /// Native USDC, the only token supported by CCTP
+    pub usdc: Address,
+}
+
+/// Returns the CCTP deployment of a supported chain.
+pub fn get_cctp_domain(chain_id: u32) -> Option<CctpDomain> {
+    match utils::Chain::try_from(chain_id).ok()? {
+        utils::Chain::Ethereum => Some(CctpDomain {
+            domain: 0,
+            token_messenger: address!("Bd3fa81B58Ba92a82136038B25aDec7066af3155"),
+            message_transmitter: address!("0a992d191DEeC32aFe36203Ad87D7d289a738F81"),
+            usdc: address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"),
+        }),
+        utils::Chain::Arbitrum => Some(CctpDomain {
+            domain: 3,
+            token_messenger: address!("19330d10D9Cc8751218eaf51E8885D058642E08A"),
+            message_transmitter: address!("C30362313FBBA5cf9163F0bb16a0e01f01A896ca"),
+            usdc: address!("af88d065e77c8cC2239327C5EDb3A432268e5831"),
+        }),
+        utils::Chain::Base => Some(CctpDomain {
+            domain: 6,
+            token_messenger: address!("1682Ae6375C4E4A97e4B583BC394c861A46D8962"),
+            message_transmitter: address!("AD09780d193884d503182aD4588450C416D6F9D4"),
+            usdc: address!("833589fCD6eDb6E08f4c7C32D4f71b54bdA02913"),
+        }),
+        // solana uses the CCTP programs, rather than the evm token messenger
+        utils::Chain::Solana => None,
+    }
+}
+
+/// Resolves the source and destination domains of a USDC route; errors if the route is not supported by CCTP.
+fn get_route_domains(
+    src_chain_id: u32,
+    src_token: &str,
+    dest_chain_id: u32,
+    dest_token: &str,
+) -> Result<(CctpDomain, CctpDomain)> {
+    let src_domain =
+        get_cctp_domain(src_chain_id).ok_or(crate::BridgeError::UnsupportedChain(src_chain_id))?;
+    let dest_domain = get_cctp_domain(dest_chain_id)
+        .ok_or(crate::BridgeError::UnsupportedChain(dest_chain_id))?;
+    if src_chain_id == dest_chain_id {
+        return Err(crate::BridgeError::ProviderRejected(
+            "CCTP: source and destination chains must differ".to_string(),
+        )
+        .into());
+    }
+    if utils::is_native_token(src_token)
+        || Address::from_str(src_token)? != src_domain.usdc
+        || utils::is_native_token(dest_token)
+        || Address::from_str(dest_token)? != dest_domain.usdc
+    {
+        return Err(crate::BridgeError::ProviderRejected(
+            "CCTP: only native USDC routes are supported".to_string(),
+        )
+        .into());
+    }
+    Ok((src_domain, dest_domain))
+}
+
+fn get_deposit_for_burn_call(
+    amount: U256,
+    dest_domain: &CctpDomain,
+    recipient: &Address,
+    burn_token: &Address,
+) -> ITokenMessenger::depositForBurnCall {
+    ITokenMessenger::depositForBurnCall {
+        amount,
+        destinationDomain: dest_domain.domain,
+        // recipients are bytes32 to support non-evm domains; evm addresses are left padded
+        mintRecipient: recipient.into_word(),
+        burnToken: *burn_token,
+    }
+}
+
+/// CCTP transfers are not relayed; the burnt USDC must be minted by the caller on the destination chain,
+/// with the burn message emitted by the bridging tx and its Circle attestation.
+fn get_mint_claim(dest_chain_id: u32, dest_domain: &CctpDomain) -> crate::DestinationClaim {
+    crate::DestinationClaim {
+        chain_id: dest_chain_id,
+        contract: dest_domain.message_transmitter.to_string(),
+        function: "receiveMessage(bytes message, bytes attestation)".to_string(),
+        description: format!(
+            "mint the burnt USDC with the `MessageSent(bytes message)` event of the confirmed bridging tx, and the \
+             attestation returned by `{}/<keccak256(message)>` once the tx has reached finality",
+            ATTESTATION_API_URL
+        ),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CctpBridge {
+    providers: HashMap<u32, RootProvider<alloy::transports::http::Http<reqwest::Client>>>,
+    cache: crate::cache::Cache,
+}
+
+impl Default for CctpBridge {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CctpBridge {
+    pub fn new() -> Self {
+        let supported_providers = utils::get_supported_chains()
+            .iter()
+            .map(|(id, chain)| {
+                let rpc_url = reqwest::Url::parse(chain.rpc_url).unwrap(); // infallible
+                let provider = ProviderBuilder::new().on_http(rpc_url);
+                (*id, provider)
+            })
+            .collect::<HashMap<_, _>>();
+
+        Self {
+            providers: supported_providers,
+            cache: crate::cache::Cache::memory(),
+        }
+    }
+
+    /// Sets the cache of the slowly changing chain state (e.g. burn limits).
+    pub fn with_cache(mut self, cache: crate::cache::Cache) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Validates the amount against the (cached) max amount burnt per message by the source chain token minter.
+    async fn check_burn_limit(
+        &self,
+        chain_id: u32,
+        src_domain: &CctpDomain,
+        amount: &U256,
+    ) -> Result<()> {
+        let provider = self
+            .providers
+            .get(&chain_id)
+            .ok_or(crate::BridgeError::UnsupportedChain(chain_id))?
+            .clone();
+        let src_domain = *src_domain;
+        let burn_limit: U256 = self
+            .cache
+            .get_or_fetch(
+                "cctp_burn_limits",
+                &format!("cctp:burn_limit:{}", chain_id),
+                BURN_LIMIT_CACHE_POLICY,
+                move || async move {
+                    let minter = utils::eth_call(
+                        &provider,
+                        &src_domain.token_messenger,
+                        ITokenMessenger::localMinterCall {},
+                    )
+                    .await?
+                    ._0;
+                    let burn_limit = utils::eth_call(
+                        &provider,
+                        &minter,
+                        ITokenMinter::burnLimitsPerMessageCall {
+                            token: src_domain.usdc,
+                        },
+                    )
+                    .await?
+                    ._0;
+                    Ok(burn_limit)
+                },
+            )
+            .await?;
+        if *amount > burn_limit {
+            return Err(crate::BridgeError::AmountTooHigh.into());
+        }
+        Ok(())
+    }
+}
+
+impl crate::BridgeProvider for CctpBridge {
+    async fn get_bridging_data(
+        &self,
+        request: &crate::BridgeRequest,
+    ) -> eyre::Result<crate::BridgeResponse> {
+        let (src_domain, dest_domain) = get_route_domains(
+            request.src_chain_id,
+            &request.src_token,
+            request.dest_chain_id,
+            &request.dest_token,
+        )?;
+        if request.calldata.is_some() {
+            return Err(crate::BridgeError::ProviderRejected(
+                "CCTP: destination calldata is not supported".to_string(),
+            )
+            .into());
+        }
+        if request.src_chain_token_in_sender_permit.is_some() {
+            return Err(crate::BridgeError::ProviderRejected(
+                "CCTP: permit signatures are not supported".to_string(),
+            )
+            .into());
+        }
+        // burnt amount is minted 1:1 on the destination chain
+        if let Some(dest_amount) = request.dest_amount {
+            if dest_amount > request.src_amount {
+                return Err(eyre::eyre!(
+                    "requested dest amount is greater than output amount"
+                ));
+            }
+        }
+
+        self.check_burn_limit(request.src_chain_id, &src_domain, &request.src_amount)
+            .await?;
+
+        let provider = self
+            .providers
+            .get(&request.src_chain_id)
+            .ok_or(crate::BridgeError::UnsupportedChain(request.src_chain_id))?;
+
+        // the token messenger consumes neither EIP-2612 nor permit2 permits; an explicit approval is required
+        let allowance_action = request
+            .allowance_action(utils::get_token_allowance_action(
+                provider,
+                &src_domain.usdc,
+                &request.src_amount,
+                &Address::from_str(&request.src_caller)?,
+                &src_domain.token_messenger,
+                &utils::PermitOptions {
+                    spender: None,
+                    permit2_kind: None,
+                    deadline: utils::get_permit_deadline(request.permit_deadline),
+                },
+            ))
+            .await?;
+
+        // if there is an pre-allowance tx/sig/action required, it must be returned
+        // to be executed by the caller
+        let claim = get_mint_claim(request.dest_chain_id, &dest_domain);
+        if let Some(response) = crate::BridgeResponse::from_allowance_action(
+            crate::SupportedProviders::Cctp,
+            allowance_action,
+        ) {
+            return Ok(response
+                .with_estimated_fill_time_secs(Some(ESTIMATED_ATTESTATION_SECS))
+                .with_destination_claim(Some(claim)));
+        }
+
+        let calldata = get_deposit_for_burn_call(
+            request.src_amount,
+            &dest_domain,
+            &Address::from_str(&request.dest_recipient)?,
+            &src_domain.usdc,
+        );
+        Ok(crate::BridgeResponse::new(
+            crate::SupportedProviders::Cctp,
+            crate::BridgeAction::BridgingTx(crate::TxData {
+                data: hex::encode(calldata.abi_encode()),
+                to: src_domain.token_messenger.to_string(),
+                value: "0".to_string(),
+                ..Default::default()
+            }),
+        )
+        .with_estimated_fill_time_secs(Some(ESTIMATED_ATTESTATION_SECS))
+        .with_destination_claim(Some(claim)))
+    }
+
+    async fn get_quote(&self, request: &crate::QuoteRequest) -> eyre::Result<crate::BridgeQuote> {
+        let (src_domain, _) = get_route_domains(
+            request.src_chain_id,
+            &request.src_token,
+            request.dest_chain_id,
+            &request.dest_token,
+        )?;
+        if request.calldata.is_some() {
+            return Err(crate::BridgeError::ProviderRejected(
+                "CCTP: destination calldata is not supported".to_string(),
+            )
+            .into());
+        }
+        self.check_burn_limit(request.src_chain_id, &src_domain, &request.src_amount)
+            .await?;
+
+        Ok(crate::BridgeQuote::new(
+            crate::SupportedProviders::Cctp,
+            request.src_amount,
+            // no protocol or relayer fees; the mint tx on the destination chain is paid by the caller
+            request.src_amount,
+            vec![],
+            ESTIMATED_ATTESTATION_SECS,
+            utils::get_unix_timestamp().saturating_add(QUOTE_VALIDITY_SECS),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn route_domains() {
+        let (src_domain, dest_domain) = get_route_domains(
+            utils::Chain::Base as u32,
+            "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913",
+            utils::Chain::Arbitrum as u32,
+            "0xaf88d065e77c8cC2239327C5EDb3A432268e5831",
+        )
+        .unwrap();
+        assert_eq!(src_domain.domain, 6);
+        assert_eq!(dest_domain.domain, 3);
+
+        // bridged USDC (USDbC) is not burnable through CCTP
+        let err = get_route_domains(
+            utils::Chain::Base as u32,
+            "0xd9aAEc86B65D86f6A7B5B1b0c42FFA531710b6CA",
+            utils::Chain::Arbitrum as u32,
+            "0xaf88d065e77c8cC2239327C5EDb3A432268e5831",
+        )
+        .unwrap_err();
+        assert!(matches!(
+            crate::BridgeError::from_report(&err),
+            crate::BridgeError::ProviderRejected(_)
+        ));
+
+        let err = get_route_domains(
+            utils::Chain::Solana as u32,
+            "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+            utils::Chain::Arbitrum as u32,
+            "0xaf88d065e77c8cC2239327C5EDb3A432268e5831",
+        )
+        .unwrap_err();
+        assert_eq!(
+            crate::BridgeError::from_report(&err),
+            crate::BridgeError::UnsupportedChain(utils::Chain::Solana as u32)
+        );
+    }
+
+    #[test]
+    fn mint_claim() {
+        let dest_domain = get_cctp_domain(utils::Chain::Arbitrum as u32).unwrap();
+        let claim = get_mint_claim(utils::Chain::Arbitrum as u32, &dest_domain);
+        assert_eq!(claim.chain_id, utils::Chain::Arbitrum as u32);
+        assert_eq!(
+            Address::from_str(&claim.contract).unwrap(),
+            address!("C30362313FBBA5cf9163F0bb16a0e01f01A896ca")
+        );
+        assert!(claim.description.contains(ATTESTATION_API_URL));
+    }
+
+    #[test]
+    fn deposit_for_burn_calldata() {
+        let dest_domain = get_cctp_domain(utils::Chain::Arbitrum as u32).unwrap();
+        let src_domain = get_cctp_domain(utils::Chain::Base as u32).unwrap();
+        let recipient = address!("000007357111E4789005d4eBfF401a18D99770cE");
+        let calldata = get_deposit_for_burn_call(
+            U256::from(2_000_000u32),
+            &dest_domain,
+            &recipient,
+            &src_domain.usdc,
+        )
+        .abi_encode();
+        assert_eq!(
+            hex::encode(calldata),
+            "6fd3504e\
+             00000000000000000000000000000000000000000000000000000000001e8480\
+             0000000000000000000000000000000000000000000000000000000000000003\
+             000000000000000000000000000007357111e4789005d4ebff401a18d99770ce\
+             000000000000000000000000833589fcd6edb6e08f4c7c32d4f71b54bda02913"
+        );
+    }
+}
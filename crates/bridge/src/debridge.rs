@@ -1,11 +1,64 @@
-use crate::{BridgeRequest, BridgeResponse, TxData};
+use crate::{TxData, U256};
 use std::borrow::Cow;
 use std::{collections::HashMap, str::FromStr};
 
-use alloy::providers::{Provider, ProviderBuilder, RootProvider};
-use alloy::{primitives::Address, sol};
+use alloy::primitives::Address;
+use alloy::providers::{ProviderBuilder, RootProvider};
+use alloy::sol;
 use eyre::Result;
 
+sol! {
+    /// DlnSource custom errors, decoded from order creation reverts
+    interface IDlnSourceErrors {
+        #[derive(Debug)]
+        error AdminBadRole();
+        #[derive(Debug)]
+        error CriticalMismatchChangeStorage();
+        #[derive(Debug)]
+        error EthTransferFailed();
+        #[derive(Debug)]
+        error IncorrectOrderStatus();
+        #[derive(Debug)]
+        error MismatchedOrderId();
+        #[derive(Debug)]
+        error MismatchedTransferAmount();
+        #[derive(Debug)]
+        error NotSupportedDstChain();
+        #[derive(Debug)]
+        error SignatureInvalidV();
+        #[derive(Debug)]
+        error TransferAmountNotCoverFees();
+        #[derive(Debug)]
+        error Unauthorized();
+        #[derive(Debug)]
+        error WrongAffiliateFeeLength();
+        #[derive(Debug)]
+        error WrongArgument();
+        #[derive(Debug)]
+        error WrongFixedFee(uint256 received, uint256 actual);
+        #[derive(Debug)]
+        error ZeroAddress();
+    }
+}
+
+/// Decoders of the DlnSource custom errors
+pub(crate) const DLN_SOURCE_ERRORS: &[crate::revert::ErrorDecoder] = &[
+    crate::revert::decode_error::<IDlnSourceErrors::AdminBadRole>,
+    crate::revert::decode_error::<IDlnSourceErrors::CriticalMismatchChangeStorage>,
+    crate::revert::decode_error::<IDlnSourceErrors::EthTransferFailed>,
+    crate::revert::decode_error::<IDlnSourceErrors::IncorrectOrderStatus>,
+    crate::revert::decode_error::<IDlnSourceErrors::MismatchedOrderId>,
+    crate::revert::decode_error::<IDlnSourceErrors::MismatchedTransferAmount>,
+    crate::revert::decode_error::<IDlnSourceErrors::NotSupportedDstChain>,
+    crate::revert::decode_error::<IDlnSourceErrors::SignatureInvalidV>,
+    crate::revert::decode_error::<IDlnSourceErrors::TransferAmountNotCoverFees>,
+    crate::revert::decode_error::<IDlnSourceErrors::Unauthorized>,
+    crate::revert::decode_error::<IDlnSourceErrors::WrongAffiliateFeeLength>,
+    crate::revert::decode_error::<IDlnSourceErrors::WrongArgument>,
+    crate::revert::decode_error::<IDlnSourceErrors::WrongFixedFee>,
+    crate::revert::decode_error::<IDlnSourceErrors::ZeroAddress>,
+];
+
 #[derive(Debug, serde::Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateTxQueryParams<'a> {
@@ -15,35 +68,69 @@ pub struct CreateTxQueryParams<'a> {
     pub src_chain_token_in_sender_permit: Option<&'a str>,
     pub dst_chain_id: u32,
     pub dst_chain_token_out: &'a str,
-    pub dst_chain_token_out_recipient: &'a str,
+    /// Omitting the recipient and authority addresses only returns the order estimation, without the tx
+    pub dst_chain_token_out_recipient: Option<&'a str>,
     pub dst_chain_token_out_amount: Option<Cow<'a, str>>,
-    pub src_chain_order_authority_address: &'a str,
-    pub dst_chain_order_authority_address: &'a str,
+    pub src_chain_order_authority_address: Option<&'a str>,
+    pub dst_chain_order_authority_address: Option<&'a str>,
     pub external_call: Option<&'a String>,
 }
 
+/// DLN represents the native currency of EVM chains as the zero address
+const DLN_NATIVE_TOKEN: &str = "0x0000000000000000000000000000000000000000";
+
+/// Validity of an order estimation; DLN recommends refreshing estimations every 30s
+const QUOTE_VALIDITY_SECS: u64 = 30;
+
+fn get_order_token(token: &str) -> &str {
+    if utils::is_native_token(token) {
+        DLN_NATIVE_TOKEN
+    } else {
+        token
+    }
+}
+
 impl<'a> From<&'a crate::BridgeRequest> for CreateTxQueryParams<'a> {
     fn from(request: &'a crate::BridgeRequest) -> Self {
         let src_chain_token_in_amount = Cow::Owned(request.src_amount.to_string());
         Self {
             src_chain_id: request.src_chain_id,
-            src_chain_token_in: request.src_token.as_str(),
+            src_chain_token_in: get_order_token(&request.src_token),
             src_chain_token_in_amount,
+            // DLN only consumes EIP-2612 permit envelopes
             src_chain_token_in_sender_permit: request
                 .src_chain_token_in_sender_permit
                 .as_ref()
-                .map(|permit| match permit {
-                    crate::PermitSignature::EIP2612(data) => data.as_str(),
-                    crate::PermitSignature::Permit2(data) => data.as_str(),
+                .and_then(|permit| match permit {
+                    crate::PermitSignature::EIP2612(data) => Some(data.as_str()),
+                    crate::PermitSignature::Permit2(_) => None,
                 }),
             dst_chain_id: request.dest_chain_id,
-            dst_chain_token_out: request.dest_token.as_str(),
-            dst_chain_token_out_recipient: request.dest_recipient.as_str(),
+            dst_chain_token_out: get_order_token(&request.dest_token),
+            dst_chain_token_out_recipient: Some(request.dest_recipient.as_str()),
             dst_chain_token_out_amount: request
                 .dest_amount
                 .map(|amount| Cow::Owned(amount.to_string())),
-            src_chain_order_authority_address: request.src_caller.as_str(),
-            dst_chain_order_authority_address: request.dest_recipient.as_str(),
+            src_chain_order_authority_address: Some(request.src_caller.as_str()),
+            dst_chain_order_authority_address: Some(request.dest_recipient.as_str()),
+            external_call: request.calldata.as_ref(),
+        }
+    }
+}
+
+impl<'a> From<&'a crate::QuoteRequest> for CreateTxQueryParams<'a> {
+    fn from(request: &'a crate::QuoteRequest) -> Self {
+        Self {
+            src_chain_id: request.src_chain_id,
+            src_chain_token_in: get_order_token(&request.src_token),
+            src_chain_token_in_amount: Cow::Owned(request.src_amount.to_string()),
+            src_chain_token_in_sender_permit: None,
+            dst_chain_id: request.dest_chain_id,
+            dst_chain_token_out: get_order_token(&request.dest_token),
+            dst_chain_token_out_recipient: None,
+            dst_chain_token_out_amount: None,
+            src_chain_order_authority_address: None,
+            dst_chain_order_authority_address: None,
             external_call: request.calldata.as_ref(),
         }
     }
@@ -51,60 +138,127 @@ impl<'a> From<&'a crate::BridgeRequest> for CreateTxQueryParams<'a> {
 
 #[derive(serde::Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
-struct TokenInfo {
-    address: String,
-    chain_id: u32,
-    decimals: u8,
-    name: String,
-    symbol: String,
-    amount: String,
-    recommended_amount: Option<String>,
-    max_theoretical_amount: Option<String>,
-    approximate_operating_expense: Option<String>,
-    mutated_with_operating_expense: Option<bool>,
-    max_refund_amount: Option<String>,
+pub struct TokenInfo {
+    pub address: String,
+    pub chain_id: u32,
+    pub decimals: u8,
+    pub name: String,
+    pub symbol: String,
+    pub amount: String,
+    pub recommended_amount: Option<String>,
+    pub max_theoretical_amount: Option<String>,
+    pub approximate_operating_expense: Option<String>,
+    pub mutated_with_operating_expense: Option<bool>,
+    pub max_refund_amount: Option<String>,
 }
 
 #[derive(serde::Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
-struct CostDetails {
-    chain: String,
-    token_in: String,
-    token_out: String,
-    amount_in: String,
-    amount_out: String,
+pub struct CostDetails {
+    pub chain: String,
+    pub token_in: String,
+    pub token_out: String,
+    pub amount_in: String,
+    pub amount_out: String,
     #[serde(rename = "type")]
-    cost_type: String,
-    payload: Option<HashMap<String, String>>,
+    pub cost_type: String,
+    pub payload: Option<HashMap<String, String>>,
 }
 
 #[derive(serde::Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
-struct Estimation {
-    src_chain_token_in: TokenInfo,
-    src_chain_token_out: Option<TokenInfo>,
-    costs_details: Vec<CostDetails>,
-    recommended_slippage: f64,
+pub struct Estimation {
+    pub src_chain_token_in: TokenInfo,
+    pub src_chain_token_out: Option<TokenInfo>,
+    pub dst_chain_token_out: TokenInfo,
+    pub costs_details: Vec<CostDetails>,
+    pub recommended_slippage: f64,
 }
 
 #[derive(serde::Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
-struct Order {
-    approximate_fulfillment_delay: u32,
-    salt: u64,
-    metadata: String,
+pub struct Order {
+    pub approximate_fulfillment_delay: u32,
+    pub salt: Option<u64>,
+    pub metadata: Option<String>,
 }
 
 #[derive(serde::Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateTxResponse {
-    estimation: Estimation,
-    tx: TxData,
-    order: Order,
-    order_id: String,
-    fix_fee: String,
-    user_points: f64,
-    integrator_points: f64,
+    pub estimation: Estimation,
+    pub tx: TxData,
+    pub order: Order,
+    pub order_id: String,
+    pub fix_fee: String,
+    pub user_points: f64,
+    pub integrator_points: f64,
+}
+
+/// Hex-encoded serialized versioned tx, returned for solana source chains
+#[derive(serde::Deserialize, Debug)]
+pub struct SolanaCreateTx {
+    pub data: String,
+}
+
+/// create-tx response for solana source chains
+#[derive(serde::Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SolanaCreateTxResponse {
+    pub tx: SolanaCreateTx,
+    pub order_id: String,
+}
+
+/// create-tx response without the recipient and authority addresses; no tx is returned
+#[derive(serde::Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct EstimationResponse {
+    pub estimation: Estimation,
+    pub order: Order,
+    pub fix_fee: String,
+}
+
+impl EstimationResponse {
+    fn into_quote(self, now: u64) -> Result<crate::BridgeQuote> {
+        let mut fees = vec![];
+        for cost in &self.estimation.costs_details {
+            // swap steps convert between tokens, their fee is only reported in the payload
+            let amount = if cost.token_in.eq_ignore_ascii_case(&cost.token_out) {
+                U256::from_str(&cost.amount_in)?.saturating_sub(U256::from_str(&cost.amount_out)?)
+            } else {
+                match cost
+                    .payload
+                    .as_ref()
+                    .and_then(|payload| payload.get("feeAmount"))
+                {
+                    Some(amount) => U256::from_str(amount)?,
+                    None => continue,
+                }
+            };
+            fees.push(crate::QuoteFee {
+                name: cost.cost_type.clone(),
+                token: cost.token_in.clone(),
+                amount,
+            });
+        }
+        // flat fee paid in the source chain native currency, as the tx value
+        let fix_fee = U256::from_str(&self.fix_fee)?;
+        fees.push(crate::QuoteFee {
+            name: "fixFee".to_string(),
+            token: DLN_NATIVE_TOKEN.to_string(),
+            amount: fix_fee,
+        });
+
+        Ok(crate::BridgeQuote::new(
+            crate::SupportedProviders::DeBridge,
+            U256::from_str(&self.estimation.src_chain_token_in.amount)?,
+            U256::from_str(&self.estimation.dst_chain_token_out.amount)?,
+            fees,
+            self.order.approximate_fulfillment_delay.into(),
+            now.saturating_add(QUOTE_VALIDITY_SECS),
+        )
+        .with_native_fee(fix_fee))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -113,6 +267,12 @@ pub struct DeBridge {
     providers: HashMap<u32, RootProvider<alloy::transports::http::Http<reqwest::Client>>>,
 }
 
+impl Default for DeBridge {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl DeBridge {
     pub fn new() -> Self {
         let supported_providers = utils::get_supported_chains()
@@ -130,10 +290,10 @@ impl DeBridge {
         }
     }
 
-    pub async fn get_create_tx(
+    pub async fn get_create_tx<T: serde::de::DeserializeOwned>(
         &self,
         params: &CreateTxQueryParams<'_>,
-    ) -> Result<CreateTxResponse> {
+    ) -> Result<T> {
         let response = self
             .client
             .get("https://api.dln.trade/v1.0/dln/order/create-tx")
@@ -141,58 +301,216 @@ impl DeBridge {
             .send()
             .await?;
         if !response.status().is_success() {
-            return Err(eyre::eyre!(
+            return Err(crate::BridgeError::ProviderRejected(format!(
                 "DeBridge: failed to get create-tx: {}",
                 response.text().await?
-            ));
+            ))
+            .into());
         }
         Ok(response.json().await?)
     }
 }
 
+impl DeBridge {
+    pub async fn get_order_status(&self, order_id: &str) -> Result<crate::status::OrderStatus> {
+        let response = self
+            .client
+            .get(format!(
+                "https://stats-api.dln.trade/api/Orders/{}",
+                order_id
+            ))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(crate::BridgeError::ProviderRejected(format!(
+                "DeBridge: failed to get order: {}",
+                response.text().await?
+            ))
+            .into());
+        }
+        response.json::<OrderResponse>().await?.into_status()
+    }
+}
+
 impl crate::BridgeProvider for DeBridge {
     async fn get_bridging_data(
         &self,
         request: &crate::BridgeRequest,
     ) -> eyre::Result<crate::BridgeResponse> {
+        // the envelope is forwarded to DLN as is; parse it before creating the order
+        let permit = match &request.src_chain_token_in_sender_permit {
+            Some(crate::PermitSignature::Permit2(_)) => {
+                return Err(eyre::eyre!(
+                    "DeBridge: permit2 signatures are not supported"
+                ))
+            }
+            Some(crate::PermitSignature::EIP2612(envelope)) => {
+                Some(utils::permit::PermitEnvelope::from_str(envelope)?)
+            }
+            None => None,
+        };
+
         let params = request.into();
-        let response = self.get_create_tx(&params).await?;
 
-        // TODO: validate for source chain as solana
-        // if source chain is solana, explicit approval will not be required
+        // if source chain is solana, the serialized tx is signed as is; explicit approval will not be required
         if request.src_chain_id == utils::Chain::Solana as u32 {
-            return Ok(crate::BridgeResponse {
-                provider: crate::SupportedProviders::DeBridge,
-                bridge_action: crate::BridgeAction::BridgingTx(response.tx),
-            });
+            let response: SolanaCreateTxResponse = self.get_create_tx(&params).await?;
+            let tx = hex::decode(response.tx.data.trim_start_matches("0x"))?;
+            let tx = crate::solana::SolanaTxData::decode(&tx, utils::get_unix_timestamp())?;
+            if tx.signer != request.src_caller {
+                return Err(eyre::eyre!(
+                    "DeBridge: solana tx signer {} does not match the caller",
+                    tx.signer
+                ));
+            }
+            return Ok(crate::BridgeResponse::new(
+                crate::SupportedProviders::DeBridge,
+                crate::BridgeAction::SolanaTx(tx),
+            )
+            .with_order_id(Some(response.order_id)));
+        }
+
+        let response: CreateTxResponse = self.get_create_tx(&params).await?;
+
+        // native currency is sent as the tx value; no approval is required
+        if utils::is_native_token(&request.src_token) {
+            return Ok(crate::BridgeResponse::new(
+                crate::SupportedProviders::DeBridge,
+                crate::BridgeAction::BridgingTx(response.tx),
+            )
+            .with_order_id(Some(response.order_id)));
         }
 
         // source chain must be evm compatible; perform approval checks...
         let provider = self
             .providers
             .get(&request.src_chain_id)
-            .ok_or_else(|| eyre::eyre!("unsupported chain id: {}", request.src_chain_id))?;
+            .ok_or(crate::BridgeError::UnsupportedChain(request.src_chain_id))?;
+
+        let spender = Address::from_str(&response.tx.to)?;
+
+        // signed permit provided by the caller, consumed by the DLN tx; validate it (including the permitted amount
+        // being the src amount) before returning the tx
+        if let Some(envelope) = &permit {
+            utils::permit::verify_permit(
+                provider,
+                &Address::from_str(&request.src_token)?,
+                &Address::from_str(&request.src_caller)?,
+                &spender,
+                &request.src_amount,
+                envelope,
+            )
+            .await?;
+            return Ok(crate::BridgeResponse::new(
+                crate::SupportedProviders::DeBridge,
+                crate::BridgeAction::BridgingTx(response.tx),
+            )
+            .with_order_id(Some(response.order_id)));
+        }
 
         // TODO: this would only apply if source chain is EVM
-        let allowance_action = utils::get_token_allowance_action(
-            provider,
-            &Address::from_str(&request.src_token)?,
-            &request.src_amount,
-            &Address::from_str(&request.src_caller)?,
-            &Address::from_str(&response.tx.to)?,
-        )
-        .await?;
+        let allowance_action = request
+            .allowance_action(utils::get_token_allowance_action(
+                provider,
+                &Address::from_str(&request.src_token)?,
+                &request.src_amount,
+                &Address::from_str(&request.src_caller)?,
+                &spender,
+                &utils::PermitOptions {
+                    spender: Some(spender),
+                    // DLN only consumes EIP-2612 permits
+                    permit2_kind: None,
+                    deadline: utils::get_permit_deadline(request.permit_deadline),
+                },
+            ))
+            .await?;
 
         // if there is an pre-allowance tx/sig/action required, it must be returned
         // to be executed by the caller
-        if !matches!(allowance_action, utils::AllowanceAction::Ok) {
-            // TODO: convert allowance action as-is, into BridgeResponse
-            // TODO: impl From<AllowanceAction> for BridgeResponse
-        };
+        if let Some(response) = crate::BridgeResponse::from_allowance_action(
+            crate::SupportedProviders::DeBridge,
+            allowance_action,
+        ) {
+            return Ok(response);
+        }
+
+        Ok(crate::BridgeResponse::new(
+            crate::SupportedProviders::DeBridge,
+            crate::BridgeAction::BridgingTx(response.tx),
+        )
+        .with_order_id(Some(response.order_id)))
+    }
+
+    async fn get_quote(&self, request: &crate::QuoteRequest) -> eyre::Result<crate::BridgeQuote> {
+        let params = request.into();
+        let response: EstimationResponse = self.get_create_tx(&params).await?;
+        response.into_quote(utils::get_unix_timestamp())
+    }
+}
 
-        Ok(crate::BridgeResponse {
+#[derive(serde::Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct StringValue {
+    pub string_value: String,
+}
+
+#[derive(serde::Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct EventMetadata {
+    pub transaction_hash: StringValue,
+    pub block_time_stamp: Option<u64>,
+}
+
+/// DLN stats api order
+#[derive(serde::Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderResponse {
+    pub order_id: StringValue,
+    pub state: String,
+    pub created_src_event_metadata: Option<EventMetadata>,
+    pub fulfilled_dst_event_metadata: Option<EventMetadata>,
+}
+
+impl OrderResponse {
+    pub fn state(&self) -> Result<crate::status::OrderState> {
+        Ok(match self.state.as_str() {
+            "Created" => crate::status::OrderState::Pending,
+            // the unlock of the order funds to the taker happens after the fill
+            "Fulfilled" | "SentUnlock" | "ClaimedUnlock" => crate::status::OrderState::Filled,
+            "OrderCancelled" | "SentOrderCancel" => crate::status::OrderState::Cancelled,
+            "ClaimedOrderCancel" => crate::status::OrderState::Refunded,
+            state => return Err(eyre::eyre!("unknown order state: {}", state)),
+        })
+    }
+
+    pub fn into_status(self) -> Result<crate::status::OrderStatus> {
+        let state = self.state()?;
+        let (deposit_tx_hash, created_at) = self
+            .created_src_event_metadata
+            .map(|event| {
+                (
+                    Some(event.transaction_hash.string_value),
+                    event.block_time_stamp,
+                )
+            })
+            .unwrap_or_default();
+        let (fill_tx_hash, filled_at) = self
+            .fulfilled_dst_event_metadata
+            .map(|event| {
+                (
+                    Some(event.transaction_hash.string_value),
+                    event.block_time_stamp,
+                )
+            })
+            .unwrap_or_default();
+        Ok(crate::status::OrderStatus {
             provider: crate::SupportedProviders::DeBridge,
-            bridge_action: crate::BridgeAction::BridgingTx(response.tx),
+            order_id: self.order_id.string_value,
+            state,
+            deposit_tx_hash,
+            fill_tx_hash,
+            created_at,
+            filled_at,
         })
     }
 }
@@ -201,7 +519,6 @@ impl crate::BridgeProvider for DeBridge {
 mod tests {
     use super::*;
     use crate::BridgeProvider;
-    use crate::U256;
 
     #[tokio::test]
     async fn get_create_tx() {
@@ -213,18 +530,74 @@ mod tests {
             src_chain_token_in_sender_permit: None,
             dst_chain_id: 8453, // USDCbC Base
             dst_chain_token_out: "0xd9aAEc86B65D86f6A7B5B1b0c42FFA531710b6CA", // USDCbC Base
-            dst_chain_token_out_recipient: "0xD79842424f797feF2B713BAd555eDdD0b6c89a80",
+            dst_chain_token_out_recipient: Some("0xD79842424f797feF2B713BAd555eDdD0b6c89a80"),
             dst_chain_token_out_amount: None,
-            src_chain_order_authority_address: "0xD79842424f797feF2B713BAd555eDdD0b6c89a80",
-            dst_chain_order_authority_address: "0xD79842424f797feF2B713BAd555eDdD0b6c89a80",
+            src_chain_order_authority_address: Some("0xD79842424f797feF2B713BAd555eDdD0b6c89a80"),
+            dst_chain_order_authority_address: Some("0xD79842424f797feF2B713BAd555eDdD0b6c89a80"),
             external_call: None,
         };
-        let response = debridge.get_create_tx(&params).await;
+        let response = debridge.get_create_tx::<CreateTxResponse>(&params).await;
         assert!(response.is_ok());
         println!("{:?}", response);
         // assert!(false);
     }
 
+    #[test]
+    fn estimation_into_quote() {
+        let response: EstimationResponse = serde_json::from_value(serde_json::json!({
+            "estimation": {
+                "srcChainTokenIn": {
+                    "address": "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913", "chainId": 8453,
+                    "decimals": 6, "name": "USD Coin", "symbol": "USDC", "amount": "2000000"
+                },
+                "dstChainTokenOut": {
+                    "address": "0xaf88d065e77c8cC2239327C5EDb3A432268e5831", "chainId": 42161,
+                    "decimals": 6, "name": "USD Coin", "symbol": "USDC", "amount": "1998800"
+                },
+                "costsDetails": [{
+                    "chain": "8453", "tokenIn": "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913",
+                    "tokenOut": "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913",
+                    "amountIn": "2000000", "amountOut": "1998800", "type": "DlnProtocolFee"
+                }, {
+                    // swap fee is reported in the payload, not as the difference of distinct tokens
+                    "chain": "42161", "tokenIn": "0xaf88d065e77c8cC2239327C5EDb3A432268e5831",
+                    "tokenOut": "0x82aF49447D8a07e3bd95BD0d56f35241523fBab1",
+                    "amountIn": "1998800", "amountOut": "500000000000000", "type": "AfterSwap",
+                    "payload": { "feeAmount": "600" }
+                }, {
+                    "chain": "42161", "tokenIn": "0xaf88d065e77c8cC2239327C5EDb3A432268e5831",
+                    "tokenOut": "0x82aF49447D8a07e3bd95BD0d56f35241523fBab1",
+                    "amountIn": "1998800", "amountOut": "500000000000000", "type": "AfterSwapEstimatedSlippage"
+                }],
+                "recommendedSlippage": 0.0
+            },
+            "order": { "approximateFulfillmentDelay": 2 },
+            "fixFee": "1000000000000000"
+        }))
+        .unwrap();
+
+        let quote = response.into_quote(1_700_000_000).unwrap();
+        assert_eq!(quote.input_amount, U256::from(2_000_000u32));
+        assert_eq!(quote.output_amount, U256::from(1_998_800u32));
+        let fees: Vec<_> = quote
+            .fees
+            .iter()
+            .map(|fee| (fee.name.as_str(), fee.amount.to::<u64>()))
+            .collect();
+        assert_eq!(
+            fees,
+            [
+                ("DlnProtocolFee", 1_200),
+                ("AfterSwap", 600),
+                ("fixFee", 1_000_000_000_000_000)
+            ]
+        );
+        assert_eq!(quote.fees[2].token, DLN_NATIVE_TOKEN);
+        assert_eq!(quote.native_fee, U256::from(1_000_000_000_000_000u64));
+        assert_eq!(quote.estimated_fill_time_secs, 2);
+        assert_eq!(quote.expires_at, 1_700_000_000 + QUOTE_VALIDITY_SECS);
+    }
+
     #[tokio::test]
     #[ignore]
     async fn get_bridging_data_no_inner_calldata() {
@@ -234,13 +607,17 @@ mod tests {
             src_chain_id: utils::Chain::Base as u32, // Base
             src_token: "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913".into(), // USDC Base
             src_chain_token_in_sender_permit: None,
-            src_amount: U256::from(2000_000u32), // 4 USDC
+            src_amount: U256::from(2_000_000u32), // 4 USDC
             dest_chain_id: utils::Chain::Arbitrum as u32, // Arbitrum
             dest_token: "0xaf88d065e77c8cC2239327C5EDb3A432268e5831".into(), // USDC Arbitrum
             dest_recipient: "0x000007357111E4789005d4eBfF401a18D99770cE".into(), // recipient
             dest_amount: None,
+            permit_deadline: None,
             calldata: None,
+            strategy: None,
             simulate: false,
+            simulate_balance: false,
+            assume_approved: false,
         };
         let response = debridge.get_bridging_data(&request).await;
         assert!(response.is_ok());
@@ -248,6 +625,38 @@ mod tests {
         // assert!(false);
     }
 
+    #[test]
+    fn order_into_status() {
+        let response: OrderResponse = serde_json::from_value(serde_json::json!({
+            "orderId": { "stringValue": "0x01", "bytesArrayValue": "[1]" },
+            "state": "ClaimedUnlock",
+            "createdSrcEventMetadata": {
+                "transactionHash": { "stringValue": "0x02" },
+                "blockTimeStamp": 1700000000
+            },
+            "fulfilledDstEventMetadata": {
+                "transactionHash": { "stringValue": "0x03" },
+                "blockTimeStamp": 1700000004
+            }
+        }))
+        .unwrap();
+        let status = response.into_status().unwrap();
+        assert_eq!(status.state, crate::status::OrderState::Filled);
+        assert_eq!(status.order_id, "0x01");
+        assert_eq!(status.deposit_tx_hash.as_deref(), Some("0x02"));
+        assert_eq!(status.fill_tx_hash.as_deref(), Some("0x03"));
+        assert_eq!(status.filled_at, Some(1_700_000_004));
+
+        let response: OrderResponse = serde_json::from_value(serde_json::json!({
+            "orderId": { "stringValue": "0x01" },
+            "state": "ClaimedOrderCancel"
+        }))
+        .unwrap();
+        let status = response.into_status().unwrap();
+        assert_eq!(status.state, crate::status::OrderState::Refunded);
+        assert!(status.fill_tx_hash.is_none());
+    }
+
     #[tokio::test]
     async fn get_create_tx_not_across_supported() {
         let debridge = DeBridge::new();
@@ -255,14 +664,18 @@ mod tests {
             src_chain_id: utils::Chain::Base as u32, // Base
             src_token: "0xd9aAEc86B65D86f6A7B5B1b0c42FFA531710b6CA".into(), // USDCbC Base // NOTE: not supported by across
             src_caller: "0x000007357111E4789005d4eBfF401a18D99770cE".into(), // caller is recipient
-            src_amount: crate::U256::from(2000_000u32),                     // 2 USDC
+            src_amount: crate::U256::from(2_000_000u32),                    // 2 USDC
             src_chain_token_in_sender_permit: None,
             dest_chain_id: utils::Chain::Arbitrum as u32, // Arbitrum
             dest_token: "0xaf88d065e77c8cC2239327C5EDb3A432268e5831".into(), // USDC Arbitrum
             dest_recipient: "0x000007357111E4789005d4eBfF401a18D99770cE".into(), // recipient
             dest_amount: None,
+            permit_deadline: None,
             calldata: None,
+            strategy: None,
             simulate: false,
+            simulate_balance: false,
+            assume_approved: false,
         };
         let response = debridge.get_bridging_data(&request).await;
         assert!(response.is_ok());
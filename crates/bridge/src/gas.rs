@@ -0,0 +1,289 @@
+use std::{future::IntoFuture, str::FromStr};
+
+use alloy::{
+    consensus::{SignableTransaction, TxEip1559},
+    primitives::{address, Address, Bytes, TxKind, U256},
+    providers::{network::TransactionBuilder, Provider, RootProvider},
+    rpc::types::TransactionRequest,
+    sol,
+    sol_types::SolCall,
+};
+
+sol! {
+    // OP-stack GasPriceOracle predeploy; returns the L1 data fee of an (unsigned) rlp-encoded tx
+    function getL1Fee(bytes memory _data) external view returns (uint256);
+}
+
+/// OP-stack `GasPriceOracle` predeploy, at the same address on every OP-stack chain
+const GAS_PRICE_ORACLE: Address = address!("420000000000000000000000000000000000000F");
+
+/// Estimated cost of the source chain tx, in the native currency (in wei) of the source chain
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxCost {
+    /// Execution fee; gas limit × max fee per gas, i.e. the max fee of the execution
+    pub execution_fee: U256,
+    /// L1 data fee, charged on top of the execution fee by OP-stack chains
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub l1_data_fee: Option<U256>,
+    /// Execution fee, plus the L1 data fee if any
+    pub total_fee: U256,
+}
+
+impl TxCost {
+    pub fn new(execution_fee: U256, l1_data_fee: Option<U256>) -> Self {
+        Self {
+            execution_fee,
+            l1_data_fee,
+            total_fee: execution_fee + l1_data_fee.unwrap_or_default(),
+        }
+    }
+}
+
+/// Fills the chain id, gas limit, EIP-1559 fees and nonce of `tx`, to be signed by `from` as is.
+/// `gas_limit` (e.g. from a simulation of the same tx) is used if set, otherwise the gas is estimated;
+/// the gas limit is left unset if the estimation fails, e.g. if the tx requires an approval first.
+pub async fn fill_tx_params(
+    provider: &RootProvider<alloy::transports::http::Http<reqwest::Client>>,
+    chain_id: u32,
+    from: &Address,
+    tx: &mut crate::TxData,
+    gas_limit: Option<u64>,
+) -> eyre::Result<()> {
+    let request = crate::simulation::tx_request(from, tx)?;
+    let estimate_gas = async {
+        match gas_limit {
+            Some(gas_limit) => Some(gas_limit),
+            None => provider
+                .estimate_gas(&request)
+                .await
+                .map_err(|e| tracing::debug!("failed to estimate gas: {}", e))
+                .ok()
+                .map(crate::simulation::with_gas_buffer),
+        }
+    };
+    let (fees, nonce, gas_limit) = futures::join!(
+        provider.estimate_eip1559_fees(None),
+        provider
+            .get_transaction_count(*from)
+            .pending()
+            .into_future(),
+        estimate_gas,
+    );
+    let fees = fees?;
+
+    tx.chain_id = Some(chain_id.into());
+    tx.gas_limit = gas_limit;
+    tx.max_fee_per_gas = Some(fees.max_fee_per_gas.to_string());
+    tx.max_priority_fee_per_gas = Some(fees.max_priority_fee_per_gas.to_string());
+    tx.nonce = Some(nonce?);
+    Ok(())
+}
+
+/// Returns the cost of `tx`, whose params are filled; see `fill_tx_params`.
+/// On OP-stack chains, the L1 data fee of the tx is added to the execution fee.
+pub async fn get_tx_cost(
+    provider: &RootProvider<alloy::transports::http::Http<reqwest::Client>>,
+    chain_id: u32,
+    tx: &crate::TxData,
+) -> eyre::Result<TxCost> {
+    let (Some(gas_limit), Some(max_fee_per_gas)) = (tx.gas_limit, &tx.max_fee_per_gas) else {
+        return Err(eyre::eyre!("tx gas limit and fees are not set"));
+    };
+    let execution_fee = U256::from(gas_limit) * U256::from_str(max_fee_per_gas)?;
+
+    let is_op_stack = utils::get_supported_chains()
+        .get(&chain_id)
+        .is_some_and(|chain| chain.l1_standard_bridge.is_some());
+    if !is_op_stack {
+        return Ok(TxCost::new(execution_fee, None));
+    }
+    let call = getL1FeeCall {
+        _data: encode_unsigned_tx(tx)?.into(),
+    };
+    let request = TransactionRequest::default()
+        .with_to(GAS_PRICE_ORACLE)
+        .with_input::<Bytes>(call.abi_encode().into());
+    let response = provider.call(&request).await?;
+    let l1_data_fee = getL1FeeCall::abi_decode_returns(&response, true)
+        .map_err(|e| eyre::eyre!("failed to decode l1 fee: {e}"))?
+        ._0;
+    Ok(TxCost::new(execution_fee, Some(l1_data_fee)))
+}
+
+/// Typical gas used and calldata size (in bytes) of a provider bridging tx, rounded up from mainnet txs;
+/// the source tx cost of quotes is estimated from these, as quotes are requested without building the tx.
+fn get_typical_tx_size(provider: &crate::SupportedProviders) -> (u64, usize) {
+    match provider {
+        crate::SupportedProviders::Across => (120_000, 420),
+        crate::SupportedProviders::DeBridge => (250_000, 1_000),
+        crate::SupportedProviders::Cctp => (120_000, 132),
+        crate::SupportedProviders::OpStack => (150_000, 260),
+        crate::SupportedProviders::Arbitrum => (250_000, 420),
+        crate::SupportedProviders::Stargate => (300_000, 600),
+        crate::SupportedProviders::Relay => (70_000, 150),
+        crate::SupportedProviders::LiFi => (350_000, 1_500),
+        crate::SupportedProviders::Mayan => (150_000, 500),
+    }
+}
+
+/// Builds a tx of the typical size of the provider bridging tx, with the given fees; see `get_typical_tx_size`.
+fn get_typical_tx(
+    provider: &crate::SupportedProviders,
+    chain_id: u32,
+    max_fee_per_gas: u128,
+    max_priority_fee_per_gas: u128,
+) -> crate::TxData {
+    let (gas_limit, calldata_len) = get_typical_tx_size(provider);
+    crate::TxData {
+        // non-zero bytes, as zero bytes are charged a lower L1 data fee
+        data: Bytes::from(vec![0xff; calldata_len]).to_string(),
+        to: Address::repeat_byte(0xff).to_string(),
+        value: "0".to_string(),
+        chain_id: Some(chain_id.into()),
+        gas_limit: Some(gas_limit),
+        max_fee_per_gas: Some(max_fee_per_gas.to_string()),
+        max_priority_fee_per_gas: Some(max_priority_fee_per_gas.to_string()),
+        nonce: None,
+    }
+}
+
+/// Estimates the cost of a typical bridging tx of the provider at the current fees, including the L1 data fee
+/// on OP-stack chains; used to compare quotes, whose bridging tx is not built.
+pub async fn estimate_bridging_tx_cost(
+    provider: &RootProvider<alloy::transports::http::Http<reqwest::Client>>,
+    chain_id: u32,
+    bridge_provider: &crate::SupportedProviders,
+) -> eyre::Result<TxCost> {
+    let fees = provider.estimate_eip1559_fees(None).await?;
+    let tx = get_typical_tx(
+        bridge_provider,
+        chain_id,
+        fees.max_fee_per_gas,
+        fees.max_priority_fee_per_gas,
+    );
+    get_tx_cost(provider, chain_id, &tx).await
+}
+
+/// Rlp-encodes `tx` as an unsigned EIP-1559 tx, as posted to L1 (without the signature)
+fn encode_unsigned_tx(tx: &crate::TxData) -> eyre::Result<Vec<u8>> {
+    let fee = |fee: &Option<String>| -> eyre::Result<u128> {
+        Ok(fee
+            .as_deref()
+            .map(u128::from_str)
+            .transpose()?
+            .unwrap_or_default())
+    };
+    let tx = TxEip1559 {
+        chain_id: tx.chain_id.unwrap_or_default(),
+        nonce: tx.nonce.unwrap_or_default(),
+        gas_limit: tx.gas_limit.unwrap_or_default().into(),
+        max_fee_per_gas: fee(&tx.max_fee_per_gas)?,
+        max_priority_fee_per_gas: fee(&tx.max_priority_fee_per_gas)?,
+        to: TxKind::Call(Address::from_str(&tx.to)?),
+        value: U256::from_str(&tx.value)?,
+        input: Bytes::from_str(&tx.data)?,
+        ..Default::default()
+    };
+    Ok(tx.encoded_for_signing())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::providers::ProviderBuilder;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn fill_base_tx_params() {
+        let chain = utils::get_supported_chains()
+            .get(&(utils::Chain::Base as u32))
+            .unwrap();
+        let provider = ProviderBuilder::new().on_http(chain.rpc_url.parse().unwrap());
+        let from = Address::from_str("0x000007357111E4789005d4eBfF401a18D99770cE").unwrap();
+        let mut tx = crate::TxData {
+            data: "0x".into(),
+            to: "0x09aea4b2242abC8bb4BB78D537A67a245A7bEC64".into(),
+            value: "0".into(),
+            ..Default::default()
+        };
+
+        let response =
+            fill_tx_params(&provider, utils::Chain::Base as u32, &from, &mut tx, None).await;
+        assert!(response.is_ok());
+        assert_eq!(tx.chain_id, Some(8453));
+        // plain transfers use 21k gas, plus the buffer
+        assert_eq!(tx.gas_limit, Some(25_200));
+        assert!(tx.max_fee_per_gas.is_some() && tx.max_priority_fee_per_gas.is_some());
+        assert!(tx.nonce.is_some());
+    }
+
+    #[test]
+    fn unsigned_tx_encoding() {
+        let tx = crate::TxData {
+            data: "0x".into(),
+            to: "0x09aea4b2242abC8bb4BB78D537A67a245A7bEC64".into(),
+            value: "0".into(),
+            chain_id: Some(8453),
+            gas_limit: Some(25_200),
+            max_fee_per_gas: Some("1000000".into()),
+            max_priority_fee_per_gas: Some("1000".into()),
+            nonce: Some(7),
+        };
+        let encoded = encode_unsigned_tx(&tx).unwrap();
+        // EIP-2718 type, followed by the rlp list of the tx fields
+        assert_eq!(encoded[0], 0x02);
+        assert_eq!(encoded[1] as usize, 0xc0 + encoded.len() - 2);
+        assert_eq!(encoded[2..5], [0x82, 0x21, 0x05]); // chain id 8453
+
+        assert!(encode_unsigned_tx(&crate::TxData {
+            max_fee_per_gas: Some("0x01".into()),
+            ..tx
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn typical_tx() {
+        let tx = get_typical_tx(&crate::SupportedProviders::Across, 8453, 1_000_000, 1_000);
+        assert_eq!(tx.gas_limit, Some(120_000));
+        assert_eq!(Bytes::from_str(&tx.data).unwrap().len(), 420);
+        assert_eq!(tx.max_fee_per_gas.as_deref(), Some("1000000"));
+        // the typical tx is encodable, for its L1 data fee to be estimated
+        assert!(encode_unsigned_tx(&tx).is_ok());
+    }
+
+    #[test]
+    fn tx_cost_total() {
+        let cost = TxCost::new(U256::from(21_000), Some(U256::from(1_000)));
+        assert_eq!(cost.total_fee, U256::from(22_000));
+        assert_eq!(
+            TxCost::new(U256::from(21_000), None).total_fee,
+            U256::from(21_000)
+        );
+    }
+
+    #[tokio::test]
+    async fn get_base_tx_cost() {
+        let chain = utils::get_supported_chains()
+            .get(&(utils::Chain::Base as u32))
+            .unwrap();
+        let provider = ProviderBuilder::new().on_http(chain.rpc_url.parse().unwrap());
+        let tx = crate::TxData {
+            data: "0x".into(),
+            to: "0x09aea4b2242abC8bb4BB78D537A67a245A7bEC64".into(),
+            value: "0".into(),
+            chain_id: Some(8453),
+            gas_limit: Some(25_200),
+            max_fee_per_gas: Some("1000000".into()),
+            max_priority_fee_per_gas: Some("1000".into()),
+            nonce: Some(7),
+        };
+
+        let response = get_tx_cost(&provider, utils::Chain::Base as u32, &tx).await;
+        assert!(response.is_ok());
+        let cost = response.unwrap();
+        assert_eq!(cost.execution_fee, U256::from(25_200_000_000u64));
+        assert!(cost.l1_data_fee.is_some_and(|fee| fee > U256::ZERO));
+    }
+}
@@ -1,23 +1,130 @@
 use core::future::Future;
+use std::str::FromStr;
 
-use alloy::primitives::U256;
+use alloy::{
+    dyn_abi::TypedData,
+    primitives::{Address, U256},
+    sol_types::SolCall,
+};
 
 pub mod across;
+pub mod arbitrum;
+pub mod cache;
+pub mod cctp;
 pub mod debridge;
+pub mod gas;
+pub mod lifi;
+pub mod mayan;
+pub mod op_stack;
+pub mod orders;
+pub mod relay;
+pub mod revert;
+pub mod routing;
 pub mod service;
+pub mod simulation;
+pub mod solana;
+pub mod stargate;
+pub mod status;
 
-#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum SupportedProviders {
     Across,
     DeBridge,
+    Cctp,
+    /// OP-stack canonical bridge (L1StandardBridge)
+    OpStack,
+    /// Arbitrum canonical bridge (L1GatewayRouter)
+    Arbitrum,
+    /// Stargate v2 pools (LayerZero)
+    Stargate,
+    /// Relay.link solver network
+    Relay,
+    /// LI.FI aggregator; routes through the underlying bridges, as a second-tier provider
+    LiFi,
+    /// Mayan Swift auctions, settled through Wormhole
+    Mayan,
 }
 
+impl SupportedProviders {
+    /// Aggregators are only attempted after the first-tier providers, whose routes are built directly;
+    /// unless explicitly preferred.
+    pub fn is_second_tier(&self) -> bool {
+        matches!(self, Self::LiFi)
+    }
+}
+
+impl std::fmt::Display for SupportedProviders {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Across => write!(f, "across"),
+            Self::DeBridge => write!(f, "debridge"),
+            Self::Cctp => write!(f, "cctp"),
+            Self::OpStack => write!(f, "opstack"),
+            Self::Arbitrum => write!(f, "arbitrum"),
+            Self::Stargate => write!(f, "stargate"),
+            Self::Relay => write!(f, "relay"),
+            Self::LiFi => write!(f, "lifi"),
+            Self::Mayan => write!(f, "mayan"),
+        }
+    }
+}
+
+impl FromStr for SupportedProviders {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "across" => Ok(Self::Across),
+            "debridge" => Ok(Self::DeBridge),
+            "cctp" => Ok(Self::Cctp),
+            "opstack" => Ok(Self::OpStack),
+            "arbitrum" => Ok(Self::Arbitrum),
+            "stargate" => Ok(Self::Stargate),
+            "relay" => Ok(Self::Relay),
+            "lifi" => Ok(Self::LiFi),
+            "mayan" => Ok(Self::Mayan),
+            _ => Err(format!("unsupported provider: {}", value)),
+        }
+    }
+}
+
+/// Signed permit envelope provided by the caller, to be consumed by the bridging tx.
+/// Serialized as `<kind>:<hex envelope>` (e.g. `eip2612:0x...`), so that it can be provided as a query param.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "String", into = "String")]
 pub enum PermitSignature {
+    /// `abi.encodePacked(uint256 amount, uint256 deadline, bytes signature)`; the amount must be `src_amount`
     EIP2612(String),
+    /// Permit2 signature-transfer; `abi.encodePacked(uint256 nonce, uint256 deadline, bytes signature)`.
+    /// Across periphery deposits append the quote of the signed deposit witness;
+    /// `abi.encodePacked(uint256 outputAmount, uint32 quoteTimestamp)`
     Permit2(String),
 }
 
+impl TryFrom<String> for PermitSignature {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.split_once(':') {
+            Some(("eip2612", envelope)) => Ok(Self::EIP2612(envelope.into())),
+            Some(("permit2", envelope)) => Ok(Self::Permit2(envelope.into())),
+            _ => Err(format!(
+                "invalid permit signature, expected `eip2612:<envelope>` or `permit2:<envelope>`: {}",
+                value
+            )),
+        }
+    }
+}
+
+impl From<PermitSignature> for String {
+    fn from(permit: PermitSignature) -> Self {
+        match permit {
+            PermitSignature::EIP2612(envelope) => format!("eip2612:{}", envelope),
+            PermitSignature::Permit2(envelope) => format!("permit2:{}", envelope),
+        }
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BridgeRequest {
@@ -30,46 +137,129 @@ pub struct BridgeRequest {
     dest_token: String,
     dest_recipient: String,
     dest_amount: Option<U256>,
+    /// Unix timestamp (in seconds) until which a requested permit signature is valid.
+    /// Defaults to `utils::DEFAULT_PERMIT_DEADLINE_SECS` from the time of the request.
+    permit_deadline: Option<u64>,
     // TODO - create/use custom struct based on debridge; to support solana + evm
+    /// Hex-encoded calldata to be executed on the destination chain.
+    /// Across: forwarded as the deposit `message` to the recipient (e.g. multicall handler); DLN: external call.
     calldata: Option<String>,
-    // TODO: potentially support simulation - via local anvil fork
+    /// Routing strategy overriding the service default, e.g. `cheapest` or `preferred:debridge`
+    strategy: Option<routing::RoutingStrategy>,
     /// simulate flag forces bridge tx to validate the resulting transaction and estimate its gas consumption.
-    /// You will find the estimation at tx.gasLimit field of the resulting object.
-    /// Important: to estimate a transaction, you are required to provide sender address that will be used to execute this transaction.
+    /// The returned tx is executed from src_caller with `eth_call` against the latest source chain block;
+    /// you will find the outcome, gasLimit estimation and any decoded revert reason at the `simulation` field
+    /// of the resulting object. The simulation is best-effort; if the tx can not be simulated (e.g. rpc failures), it
+    /// is returned with an unsuccessful simulation stating the `unavailableReason`.
     /// It should have enough assets on its balance to cover the amount specified in the src_amount property, and enough native gas token to cover the protocol global fixed fee. (DeBridge)
     /// Caution: if the input token (src_token) is not a native blockchain currency but an ERC-20 token, it is necessary to provide an approve to spend this token by the tx.allowanceTarget contract prior to such estimation
     /// This can be done either by executing increaseAllowance() on-chain or by providing the permit envelope via the srcChainTokenInSenderPermit property. Failing to provide a correct approve to spend will result an error during transaction.
+    /// If an approval or signature is required first, the bridging tx is built as if approved, and simulated with the
+    /// allowance injected into the token storage; i.e. it can be simulated before approval.
     simulate: bool,
+    /// Injects the src_amount balance (and the native tx value) of src_caller into the simulation; requires `simulate`
+    #[serde(default)]
+    simulate_balance: bool,
+    /// Builds the bridging tx as if the spender was approved, skipping any approval/signature action;
+    /// set internally to simulate the bridging tx before approval
+    #[serde(skip)]
+    assume_approved: bool,
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-#[cfg_attr(test, derive(PartialEq))]
-pub struct TxData {
-    data: String,
-    to: String,
-    value: String,
+impl BridgeRequest {
+    /// Returns the allowance action of the request, unless the spender is assumed to be approved.
+    pub(crate) async fn allowance_action(
+        &self,
+        allowance_action: impl Future<Output = eyre::Result<utils::AllowanceAction>>,
+    ) -> eyre::Result<utils::AllowanceAction> {
+        if self.assume_approved {
+            return Ok(utils::AllowanceAction::Ok);
+        }
+        allowance_action.await
+    }
 }
 
+pub use utils::TxData;
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum BridgeAction {
     /// If no approvals are required, the bridging tx can be called directly
     BridgingTx(TxData),
-    /// EIP-2612 payload, to be signed for BridgingTx.
+    /// EIP-2612 payload (EIP-712 typed data), to be signed for BridgingTx.
     /// If the source token supports EIP-2612, the caller must sign this data, for a bridging tx to be returned
-    PermitSignature(String),
-    /// Permit2 payload, to be signed for BridgingTx
+    PermitSignature(TypedData),
+    /// Permit2 payload (EIP-712 typed data), to be signed for BridgingTx
     /// If source token doesnt support eip-2612 and the bridging contract is not approved,
     /// but the caller has approved the canonical permit2 contract, the caller must sign this data
-    Permit2Signature(String),
+    Permit2Signature(TypedData),
     /// Permit2 tx, to be called before BridgingTx
     /// If source token doesnt support eip-2612 and the bridging contract is not approved,
     /// and the caller has not approved the canonical permit2 contract, the caller must call this tx
-    /// before the bridging tx
-    Permit2Tx(TxData),
+    /// before the bridging tx; the permit2 payload is to be signed once the tx has been executed
+    Permit2Tx(TxData, TypedData),
     /// Bridge approval tx, to be called before BridgingTx
     /// If the bridging contract requires an explicit approval tx, the caller must call this tx
     /// before the bridging tx
     BridgeApprovalTx(SupportedProviders, TxData),
+    /// Solana tx, to be signed by the caller; returned instead of BridgingTx for solana source chains
+    SolanaTx(solana::SolanaTxData),
+}
+
+impl BridgeAction {
+    /// Action name, as serialized; e.g. `BridgingTx`
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::BridgingTx(_) => "BridgingTx",
+            Self::PermitSignature(_) => "PermitSignature",
+            Self::Permit2Signature(_) => "Permit2Signature",
+            Self::Permit2Tx(_, _) => "Permit2Tx",
+            Self::BridgeApprovalTx(_, _) => "BridgeApprovalTx",
+            Self::SolanaTx(_) => "SolanaTx",
+        }
+    }
+
+    /// EVM tx to be executed by the caller; `None` if a signature is required instead, or for solana txs
+    pub fn tx(&self) -> Option<&TxData> {
+        match self {
+            Self::BridgingTx(tx) | Self::Permit2Tx(tx, _) | Self::BridgeApprovalTx(_, tx) => {
+                Some(tx)
+            }
+            Self::PermitSignature(_) | Self::Permit2Signature(_) | Self::SolanaTx(_) => None,
+        }
+    }
+
+    /// Mutable reference to the tx to be executed by the caller; see `tx`
+    pub fn tx_mut(&mut self) -> Option<&mut TxData> {
+        match self {
+            Self::BridgingTx(tx) | Self::Permit2Tx(tx, _) | Self::BridgeApprovalTx(_, tx) => {
+                Some(tx)
+            }
+            Self::PermitSignature(_) | Self::Permit2Signature(_) | Self::SolanaTx(_) => None,
+        }
+    }
+
+    /// Spender to be approved by the action; `None` for bridging txs, which require no approval
+    pub fn spender(&self) -> eyre::Result<Option<Address>> {
+        match self {
+            Self::BridgingTx(_) | Self::SolanaTx(_) => Ok(None),
+            Self::BridgeApprovalTx(_, tx) => {
+                let data = alloy::primitives::Bytes::from_str(&tx.data)?;
+                let call = utils::approveCall::abi_decode(&data, true)
+                    .map_err(|e| eyre::eyre!("failed to decode approval tx: {e}"))?;
+                Ok(Some(call.spender))
+            }
+            Self::PermitSignature(data)
+            | Self::Permit2Signature(data)
+            | Self::Permit2Tx(_, data) => {
+                let spender = data
+                    .message
+                    .get("spender")
+                    .and_then(|spender| spender.as_str())
+                    .ok_or_else(|| eyre::eyre!("permit payload has no spender"))?;
+                Ok(Some(Address::from_str(spender)?))
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -79,6 +269,354 @@ pub struct BridgeResponse {
     /// Optional approval_tx (either permit2 data to be signed, or explicit approval tx to be called before bridge_tx)
     /// Cross-chain source bridging tx
     bridge_action: BridgeAction,
+    /// Estimated time (in seconds) for the bridged funds to be received on the destination chain, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    estimated_fill_time_secs: Option<u64>,
+    /// Delay (in seconds) of the reverse route through the same provider, if it differs significantly;
+    /// e.g. canonical rollup withdrawals are subject to a challenge period
+    #[serde(skip_serializing_if = "Option::is_none")]
+    withdrawal_delay_secs: Option<u64>,
+    /// Bridge the funds are routed through, if the provider is an aggregator; e.g. `stargate` through `lifi`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    underlying_bridge: Option<String>,
+    /// Provider order id, to track the order status with; returned with the bridging tx, if known before
+    /// the tx is executed (e.g. DLN order ids)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    order_id: Option<String>,
+    /// Simulation of the returned tx from the source chain caller; set if requested with `simulate`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    simulation: Option<simulation::Simulation>,
+    /// Estimated cost of the returned tx on the source chain, including the L1 data fee on OP-stack chains
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tx_cost: Option<gas::TxCost>,
+    /// Claim to be executed by the caller on the destination chain once the bridging tx is confirmed, if the
+    /// provider does not deliver the funds itself; e.g. minting a CCTP burn, or executing an Arbitrum withdrawal
+    #[serde(skip_serializing_if = "Option::is_none")]
+    destination_claim: Option<DestinationClaim>,
+}
+
+/// Tx to be called by the caller on the destination chain to receive the bridged funds; its calldata depends
+/// on the confirmed bridging tx, and can only be built once the source chain message has been attested (or, for
+/// rollup withdrawals, its challenge period has elapsed).
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DestinationClaim {
+    pub chain_id: u32,
+    /// Contract to be called; e.g. the CCTP message transmitter
+    pub contract: String,
+    /// Function to be called, e.g. `receiveMessage(bytes message, bytes attestation)`
+    pub function: String,
+    /// How the call arguments are retrieved from the bridging tx
+    pub description: String,
+}
+
+impl BridgeResponse {
+    /// Response with the given action and no optional details; see the `with_*` setters.
+    pub(crate) fn new(provider: SupportedProviders, bridge_action: BridgeAction) -> Self {
+        Self {
+            provider,
+            bridge_action,
+            estimated_fill_time_secs: None,
+            withdrawal_delay_secs: None,
+            underlying_bridge: None,
+            order_id: None,
+            simulation: None,
+            tx_cost: None,
+            destination_claim: None,
+        }
+    }
+
+    pub(crate) fn with_estimated_fill_time_secs(mut self, secs: Option<u64>) -> Self {
+        self.estimated_fill_time_secs = secs;
+        self
+    }
+
+    pub(crate) fn with_withdrawal_delay_secs(mut self, secs: Option<u64>) -> Self {
+        self.withdrawal_delay_secs = secs;
+        self
+    }
+
+    pub(crate) fn with_underlying_bridge(mut self, underlying_bridge: Option<String>) -> Self {
+        self.underlying_bridge = underlying_bridge;
+        self
+    }
+
+    pub(crate) fn with_order_id(mut self, order_id: Option<String>) -> Self {
+        self.order_id = order_id;
+        self
+    }
+
+    pub(crate) fn with_destination_claim(mut self, claim: Option<DestinationClaim>) -> Self {
+        self.destination_claim = claim;
+        self
+    }
+
+    /// Converts an allowance action into the response to be returned to the caller.
+    /// Returns `None` if no pre-approval/signature action is required, i.e. the bridging tx can be returned.
+    pub(crate) fn from_allowance_action(
+        provider: SupportedProviders,
+        allowance_action: utils::AllowanceAction,
+    ) -> Option<Self> {
+        let bridge_action = match allowance_action {
+            utils::AllowanceAction::Ok => return None,
+            utils::AllowanceAction::PermitSignature(data) => BridgeAction::PermitSignature(data),
+            utils::AllowanceAction::Permit2Signature(data) => BridgeAction::Permit2Signature(data),
+            utils::AllowanceAction::Permit2Tx(tx, data) => BridgeAction::Permit2Tx(tx, data),
+            utils::AllowanceAction::ApprovalTx(tx) => {
+                BridgeAction::BridgeApprovalTx(provider.clone(), tx)
+            }
+        };
+        Some(Self::new(provider, bridge_action))
+    }
+}
+
+/// Quote request; does not require a connected caller, as no bridging tx is built.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteRequest {
+    src_chain_id: u32,
+    src_token: String,
+    src_amount: U256,
+    dest_chain_id: u32,
+    dest_token: String,
+    dest_recipient: Option<String>,
+    calldata: Option<String>,
+    /// Routing strategy overriding the service default, used to pick the returned quote
+    strategy: Option<routing::RoutingStrategy>,
+}
+
+impl From<&BridgeRequest> for QuoteRequest {
+    fn from(request: &BridgeRequest) -> Self {
+        Self {
+            src_chain_id: request.src_chain_id,
+            src_token: request.src_token.clone(),
+            src_amount: request.src_amount,
+            dest_chain_id: request.dest_chain_id,
+            dest_token: request.dest_token.clone(),
+            dest_recipient: Some(request.dest_recipient.clone()),
+            calldata: request.calldata.clone(),
+            strategy: request.strategy.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteFee {
+    /// Provider specific fee name, e.g. `lpFee`
+    name: String,
+    /// Token the fee is charged in
+    token: String,
+    amount: U256,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BridgeQuote {
+    provider: SupportedProviders,
+    /// Amount of `src_token` to be sent on the source chain
+    input_amount: U256,
+    /// Expected amount of `dest_token` to be received on the destination chain
+    output_amount: U256,
+    fees: Vec<QuoteFee>,
+    /// Sum of the `fees` paid in the source chain native currency on top of the input amount, e.g. as the
+    /// bridging tx value; these are not deducted from the output amount
+    native_fee: U256,
+    /// Estimated time (in seconds) for the order to be filled on the destination chain
+    estimated_fill_time_secs: u64,
+    /// Unix timestamp (in seconds) after which the quote is stale, and should be requested again
+    expires_at: u64,
+    /// Estimated cost of a typical bridging tx of the provider on the source chain, including the L1 data fee on
+    /// OP-stack chains; not set if the source chain fees could not be estimated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tx_cost: Option<gas::TxCost>,
+    /// Delay (in seconds) of the reverse route through the same provider; see `BridgeResponse`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    withdrawal_delay_secs: Option<u64>,
+    /// Bridge the funds are routed through, if the provider is an aggregator; see `BridgeResponse`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    underlying_bridge: Option<String>,
+}
+
+impl BridgeQuote {
+    /// Quote without the optional route details; see the `with_*` setters.
+    pub(crate) fn new(
+        provider: SupportedProviders,
+        input_amount: U256,
+        output_amount: U256,
+        fees: Vec<QuoteFee>,
+        estimated_fill_time_secs: u64,
+        expires_at: u64,
+    ) -> Self {
+        Self {
+            provider,
+            input_amount,
+            output_amount,
+            fees,
+            native_fee: U256::ZERO,
+            estimated_fill_time_secs,
+            expires_at,
+            tx_cost: None,
+            withdrawal_delay_secs: None,
+            underlying_bridge: None,
+        }
+    }
+
+    pub(crate) fn with_native_fee(mut self, native_fee: U256) -> Self {
+        self.native_fee = native_fee;
+        self
+    }
+
+    pub(crate) fn with_withdrawal_delay_secs(mut self, secs: Option<u64>) -> Self {
+        self.withdrawal_delay_secs = secs;
+        self
+    }
+
+    pub(crate) fn with_underlying_bridge(mut self, underlying_bridge: Option<String>) -> Self {
+        self.underlying_bridge = underlying_bridge;
+        self
+    }
+
+    pub(crate) fn with_tx_cost(mut self, tx_cost: Option<gas::TxCost>) -> Self {
+        self.tx_cost = tx_cost;
+        self
+    }
+
+    /// Native currency paid on top of the input amount; the native fee, plus the estimated source tx cost
+    pub fn native_cost(&self) -> U256 {
+        let tx_cost = self.tx_cost.as_ref().map(|cost| cost.total_fee);
+        self.native_fee.saturating_add(tx_cost.unwrap_or_default())
+    }
+
+    /// Output amount net of the native cost, converted into the output token at the quote rate if the input token is
+    /// the native currency. Otherwise, the source tx cost is not deducted, as it is paid with every provider, and
+    /// `None` is returned if a native fee can not be converted.
+    pub fn net_output_amount(&self, native_input: bool) -> Option<U256> {
+        if native_input && !self.input_amount.is_zero() {
+            let native_cost =
+                self.native_cost().saturating_mul(self.output_amount) / self.input_amount;
+            return Some(self.output_amount.saturating_sub(native_cost));
+        }
+        self.native_fee.is_zero().then_some(self.output_amount)
+    }
+}
+
+/// Typed provider errors, surfaced to the caller to explain why a provider could not bridge the request.
+/// Provider implementations return these as `eyre::Report`s; use `BridgeError::from_report` to recover them.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase", tag = "code", content = "reason")]
+pub enum BridgeError {
+    /// Source or destination chain is not supported by the provider
+    UnsupportedChain(u32),
+    /// Requested amount is below the provider minimum
+    AmountTooLow,
+    /// Requested amount exceeds the provider maximum
+    AmountTooHigh,
+    /// Provider api rejected the request, e.g. unsupported token route
+    ProviderRejected(String),
+    /// Contract call reverted, e.g. while estimating the bridging tx gas
+    Reverted(revert::RevertError),
+    /// Any other failure, e.g. network or rpc errors
+    Other(String),
+}
+
+impl std::fmt::Display for BridgeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsupportedChain(chain_id) => write!(f, "unsupported chain id: {}", chain_id),
+            Self::AmountTooLow => write!(f, "requested amount is below min deposit limit"),
+            Self::AmountTooHigh => write!(f, "requested amount exceeds max deposit limit"),
+            Self::ProviderRejected(reason) => write!(f, "provider rejected request: {}", reason),
+            Self::Reverted(error) => write!(f, "execution reverted: {}", error),
+            Self::Other(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl std::error::Error for BridgeError {}
+
+impl BridgeError {
+    /// Recovers the typed error of a report; rpc reverts are decoded, other errors are returned as `Other`.
+    pub fn from_report(report: &eyre::Report) -> Self {
+        if let Some(error) = report.downcast_ref::<Self>() {
+            return error.clone();
+        }
+        report
+            .downcast_ref::<alloy::transports::TransportError>()
+            .and_then(revert::RevertError::from_transport_error)
+            .map(Self::Reverted)
+            .unwrap_or_else(|| Self::Other(report.to_string()))
+    }
+
+    /// Stable error code; the revert code for reverts, e.g. `SpokePool.InvalidFillDeadline`
+    pub fn code(&self) -> String {
+        match self {
+            Self::UnsupportedChain(_) => "unsupportedChain".into(),
+            Self::AmountTooLow => "amountTooLow".into(),
+            Self::AmountTooHigh => "amountTooHigh".into(),
+            Self::ProviderRejected(_) => "providerRejected".into(),
+            Self::Reverted(error) => error.code.clone(),
+            Self::Other(_) => "other".into(),
+        }
+    }
+}
+
+/// Outcome of quoting a request with a single provider
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase", tag = "status")]
+pub enum ProviderQuote {
+    Quoted(Box<BridgeQuote>),
+    Failed {
+        provider: SupportedProviders,
+        error: BridgeError,
+    },
+}
+
+impl ProviderQuote {
+    pub fn new(provider: SupportedProviders, result: eyre::Result<BridgeQuote>) -> Self {
+        match result {
+            Ok(quote) => Self::Quoted(Box::new(quote)),
+            Err(report) => Self::Failed {
+                provider,
+                error: BridgeError::from_report(&report),
+            },
+        }
+    }
+}
+
+/// Orders quotes by net output amount (highest first), then lowest native cost. Quotes with a native fee that can
+/// not be converted into the output token are ordered after the others, by output amount and then native cost.
+pub(crate) fn cmp_net_output(
+    a: &BridgeQuote,
+    b: &BridgeQuote,
+    native_input: bool,
+) -> std::cmp::Ordering {
+    match (
+        a.net_output_amount(native_input),
+        b.net_output_amount(native_input),
+    ) {
+        (Some(net_a), Some(net_b)) => net_b
+            .cmp(&net_a)
+            .then(a.native_cost().cmp(&b.native_cost())),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => b
+            .output_amount
+            .cmp(&a.output_amount)
+            .then(a.native_cost().cmp(&b.native_cost())),
+    }
+}
+
+/// Ranks provider quotes by net output amount (see `cmp_net_output`), ties broken by the fastest estimated fill;
+/// failed quotes are ranked last. `native_input` is whether the input token is the source chain native currency.
+pub fn rank_quotes(quotes: &mut [ProviderQuote], native_input: bool) {
+    quotes.sort_by(|a, b| match (a, b) {
+        (ProviderQuote::Quoted(a), ProviderQuote::Quoted(b)) => cmp_net_output(a, b, native_input)
+            .then(a.estimated_fill_time_secs.cmp(&b.estimated_fill_time_secs)),
+        (ProviderQuote::Quoted(_), ProviderQuote::Failed { .. }) => std::cmp::Ordering::Less,
+        (ProviderQuote::Failed { .. }, ProviderQuote::Quoted(_)) => std::cmp::Ordering::Greater,
+        (ProviderQuote::Failed { .. }, ProviderQuote::Failed { .. }) => std::cmp::Ordering::Equal,
+    });
 }
 
 pub trait BridgeProvider {
@@ -86,4 +624,300 @@ pub trait BridgeProvider {
         &self,
         request: &BridgeRequest,
     ) -> impl Future<Output = eyre::Result<BridgeResponse>> + Send;
+
+    /// Returns the expected output and fees for the request, without building a bridging tx.
+    fn get_quote(
+        &self,
+        request: &QuoteRequest,
+    ) -> impl Future<Output = eyre::Result<BridgeQuote>> + Send;
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::sol_types::SolError;
+
+    use super::*;
+
+    #[test]
+    fn from_allowance_action() {
+        let tx = TxData {
+            data: "0x095ea7b3".into(),
+            to: "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913".into(),
+            value: "0".into(),
+            ..Default::default()
+        };
+
+        // no action required, bridging tx can be returned
+        assert!(BridgeResponse::from_allowance_action(
+            SupportedProviders::Across,
+            utils::AllowanceAction::Ok
+        )
+        .is_none());
+
+        // approval tx is returned as-is for the provider
+        let response = BridgeResponse::from_allowance_action(
+            SupportedProviders::DeBridge,
+            utils::AllowanceAction::ApprovalTx(tx.clone()),
+        )
+        .unwrap();
+        assert_eq!(response.provider, SupportedProviders::DeBridge);
+        assert!(matches!(
+            response.bridge_action,
+            BridgeAction::BridgeApprovalTx(SupportedProviders::DeBridge, ref approval_tx) if *approval_tx == tx
+        ));
+
+        // permit2 tx retains both the approval tx and the payload to be signed
+        let typed_data = TypedData::from_struct(
+            &utils::permit::Permit {
+                owner: Default::default(),
+                spender: Default::default(),
+                value: U256::from(1),
+                nonce: U256::ZERO,
+                deadline: U256::ZERO,
+            },
+            None,
+        );
+        let response = BridgeResponse::from_allowance_action(
+            SupportedProviders::Across,
+            utils::AllowanceAction::Permit2Tx(tx.clone(), typed_data.clone()),
+        )
+        .unwrap();
+        assert!(matches!(
+            response.bridge_action,
+            BridgeAction::Permit2Tx(ref permit2_tx, ref data) if *permit2_tx == tx && *data == typed_data
+        ));
+    }
+
+    #[test]
+    fn bridge_action_spender() {
+        let spender = alloy::primitives::address!("09aea4b2242abC8bb4BB78D537A67a245A7bEC64");
+        let tx = TxData {
+            data: alloy::primitives::Bytes::from(
+                utils::approveCall {
+                    spender,
+                    amount: U256::MAX,
+                }
+                .abi_encode(),
+            )
+            .to_string(),
+            to: "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913".into(),
+            value: "0".into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            BridgeAction::BridgeApprovalTx(SupportedProviders::Across, tx.clone())
+                .spender()
+                .unwrap(),
+            Some(spender)
+        );
+        // bridging txs require no approval
+        assert_eq!(BridgeAction::BridgingTx(tx).spender().unwrap(), None);
+
+        let typed_data = TypedData::from_struct(
+            &utils::permit::Permit {
+                owner: Default::default(),
+                spender,
+                value: U256::from(1),
+                nonce: U256::ZERO,
+                deadline: U256::ZERO,
+            },
+            None,
+        );
+        assert_eq!(
+            BridgeAction::PermitSignature(typed_data).spender().unwrap(),
+            Some(spender)
+        );
+    }
+
+    #[test]
+    fn rank_provider_quotes() {
+        let quote = |provider, output_amount: u32, estimated_fill_time_secs| {
+            BridgeQuote::new(
+                provider,
+                U256::from(2_000_000u32),
+                U256::from(output_amount),
+                vec![],
+                estimated_fill_time_secs,
+                0,
+            )
+        };
+        let mut quotes = vec![
+            ProviderQuote::new(
+                SupportedProviders::Across,
+                Err(BridgeError::AmountTooLow.into()),
+            ),
+            ProviderQuote::new(
+                SupportedProviders::DeBridge,
+                Ok(quote(SupportedProviders::DeBridge, 1_998_000, 2)),
+            ),
+            ProviderQuote::new(
+                SupportedProviders::Across,
+                Ok(quote(SupportedProviders::Across, 1_998_000, 1)),
+            ),
+            ProviderQuote::new(
+                SupportedProviders::DeBridge,
+                Ok(quote(SupportedProviders::DeBridge, 1_999_000, 60)),
+            ),
+        ];
+        rank_quotes(&mut quotes, false);
+
+        let ranked: Vec<_> = quotes
+            .iter()
+            .map(|quote| match quote {
+                ProviderQuote::Quoted(quote) => (
+                    quote.output_amount.to::<u32>(),
+                    quote.estimated_fill_time_secs,
+                ),
+                ProviderQuote::Failed { .. } => (0, 0),
+            })
+            .collect();
+        assert_eq!(
+            ranked,
+            vec![(1_999_000, 60), (1_998_000, 1), (1_998_000, 2), (0, 0)]
+        );
+        assert!(matches!(
+            quotes[3],
+            ProviderQuote::Failed {
+                error: BridgeError::AmountTooLow,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn rank_native_fee_quotes() {
+        let quote = |provider, output_amount: u32, native_fee: u32| {
+            ProviderQuote::Quoted(Box::new(
+                BridgeQuote::new(
+                    provider,
+                    U256::from(2_000_000u32),
+                    U256::from(output_amount),
+                    vec![],
+                    1,
+                    0,
+                )
+                .with_native_fee(U256::from(native_fee)),
+            ))
+        };
+        let ranked = |native_input| {
+            let mut quotes = vec![
+                quote(SupportedProviders::DeBridge, 1_999_000, 2_000),
+                quote(SupportedProviders::Across, 1_998_000, 0),
+                quote(SupportedProviders::Stargate, 1_999_000, 1_000),
+            ];
+            rank_quotes(&mut quotes, native_input);
+            quotes
+                .into_iter()
+                .map(|quote| match quote {
+                    ProviderQuote::Quoted(quote) => quote.provider,
+                    ProviderQuote::Failed { provider, .. } => provider,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // native fees are converted at the quote rate, if paid in the input token
+        assert_eq!(
+            ranked(true),
+            [
+                SupportedProviders::Stargate,
+                SupportedProviders::Across,
+                SupportedProviders::DeBridge
+            ]
+        );
+        // otherwise, quotes with native fees are ranked after the others, by lowest native fee
+        assert_eq!(
+            ranked(false),
+            [
+                SupportedProviders::Across,
+                SupportedProviders::Stargate,
+                SupportedProviders::DeBridge
+            ]
+        );
+    }
+
+    #[test]
+    fn rank_tx_cost_quotes() {
+        let quote = |provider, output_amount: u32, tx_cost: u32| {
+            BridgeQuote::new(
+                provider,
+                U256::from(2_000_000u32),
+                U256::from(output_amount),
+                vec![],
+                1,
+                0,
+            )
+            .with_tx_cost(Some(gas::TxCost::new(U256::from(tx_cost), None)))
+        };
+        let across = quote(SupportedProviders::Across, 1_998_000, 5_000);
+        let relay = quote(SupportedProviders::Relay, 1_998_500, 2_000);
+        let cctp = quote(SupportedProviders::Cctp, 1_998_500, 1_000);
+
+        // tx costs only break ties, if not convertible into the output token
+        assert_eq!(
+            cmp_net_output(&cctp, &relay, false),
+            std::cmp::Ordering::Less
+        );
+        assert_eq!(
+            cmp_net_output(&relay, &across, false),
+            std::cmp::Ordering::Less
+        );
+        // otherwise, tx costs are deducted from the output amount
+        assert_eq!(
+            across.net_output_amount(true),
+            Some(U256::from(1_993_005u32))
+        );
+        assert_eq!(
+            cmp_net_output(&across, &relay, true),
+            std::cmp::Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn bridge_error_from_report() {
+        let report: eyre::Report = BridgeError::UnsupportedChain(1).into();
+        assert_eq!(
+            BridgeError::from_report(&report),
+            BridgeError::UnsupportedChain(1)
+        );
+        assert_eq!(
+            BridgeError::from_report(&eyre::eyre!("rpc error")),
+            BridgeError::Other("rpc error".into())
+        );
+        assert_eq!(
+            serde_json::to_value(BridgeError::AmountTooHigh).unwrap(),
+            serde_json::json!({ "code": "amountTooHigh" })
+        );
+        assert_eq!(BridgeError::AmountTooHigh.code(), "amountTooHigh");
+
+        // rpc reverts are decoded, with the contract error as code
+        let payload = serde_json::from_value(serde_json::json!({
+            "code": 3,
+            "message": "execution reverted",
+            "data": format!(
+                "0x{}",
+                hex::encode(across::ISpokePoolErrors::InvalidFillDeadline {}.abi_encode())
+            ),
+        }))
+        .unwrap();
+        let error: alloy::transports::TransportError =
+            alloy::transports::RpcError::ErrorResp(payload);
+        let report: eyre::Report = error.into();
+        let error = BridgeError::from_report(&report);
+        assert_eq!(error.code(), "SpokePool.InvalidFillDeadline");
+        assert_eq!(
+            error.to_string(),
+            "execution reverted: SpokePool: InvalidFillDeadline"
+        );
+    }
+
+    #[test]
+    fn permit_signature_serde() {
+        let permit: PermitSignature = serde_json::from_str("\"eip2612:0x1234\"").unwrap();
+        assert!(matches!(permit, PermitSignature::EIP2612(ref envelope) if envelope == "0x1234"));
+        assert_eq!(
+            serde_json::to_string(&PermitSignature::Permit2("0x1234".into())).unwrap(),
+            "\"permit2:0x1234\""
+        );
+        assert!(serde_json::from_str::<PermitSignature>("\"0x1234\"").is_err());
+    }
 }
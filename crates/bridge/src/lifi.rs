@@ -0,0 +1,525 @@
+use crate::{TxData, U256};
+use std::{collections::HashMap, str::FromStr};
+
+use alloy::primitives::Address;
+use alloy::providers::{ProviderBuilder, RootProvider};
+use base64::Engine;
+use eyre::Result;
+
+/// LI.FI represents the native currency of EVM chains as the zero address
+const LIFI_NATIVE_TOKEN: &str = "0x0000000000000000000000000000000000000000";
+
+/// LI.FI chain id of solana, which has no evm chain id
+const LIFI_SOLANA_CHAIN_ID: u64 = 1151111081099710;
+
+/// LI.FI requires a sender to quote; placeholders used for quotes without a recipient
+const QUOTE_PLACEHOLDER_ADDRESS: &str = "0x000000000000000000000000000000000000dEaD";
+const SOLANA_QUOTE_PLACEHOLDER_ADDRESS: &str = "11111111111111111111111111111111";
+
+/// Validity of a quote; routes are priced at the current liquidity of the underlying bridges and dexes
+const QUOTE_VALIDITY_SECS: u64 = 30;
+
+fn get_lifi_token(token: &str) -> &str {
+    if utils::is_native_token(token) {
+        LIFI_NATIVE_TOKEN
+    } else {
+        token
+    }
+}
+
+/// LI.FI uses its own chain id for solana; evm chains are identified by their chain id.
+fn get_lifi_chain_id(chain_id: u32) -> Result<u64> {
+    match utils::Chain::try_from(chain_id) {
+        Ok(utils::Chain::Solana) => Ok(LIFI_SOLANA_CHAIN_ID),
+        Ok(_) => Ok(u64::from(chain_id)),
+        Err(_) => Err(crate::BridgeError::UnsupportedChain(chain_id).into()),
+    }
+}
+
+fn get_quote_placeholder_address(chain_id: u32) -> &'static str {
+    if chain_id == utils::Chain::Solana as u32 {
+        SOLANA_QUOTE_PLACEHOLDER_ADDRESS
+    } else {
+        QUOTE_PLACEHOLDER_ADDRESS
+    }
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteQueryParams<'a> {
+    pub from_chain: u64,
+    pub to_chain: u64,
+    pub from_token: &'a str,
+    pub to_token: &'a str,
+    pub from_amount: String,
+    pub from_address: &'a str,
+    pub to_address: &'a str,
+}
+
+impl<'a> TryFrom<&'a crate::BridgeRequest> for QuoteQueryParams<'a> {
+    type Error = eyre::Report;
+
+    fn try_from(request: &'a crate::BridgeRequest) -> Result<Self> {
+        Ok(Self {
+            from_chain: get_lifi_chain_id(request.src_chain_id)?,
+            to_chain: get_lifi_chain_id(request.dest_chain_id)?,
+            from_token: get_lifi_token(&request.src_token),
+            to_token: get_lifi_token(&request.dest_token),
+            from_amount: request.src_amount.to_string(),
+            from_address: &request.src_caller,
+            to_address: &request.dest_recipient,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a crate::QuoteRequest> for QuoteQueryParams<'a> {
+    type Error = eyre::Report;
+
+    /// The recipient is used as the sender if on the same kind of chain, as the quote does not depend on it.
+    fn try_from(request: &'a crate::QuoteRequest) -> Result<Self> {
+        let recipient = request.dest_recipient.as_deref();
+        let is_solana = |chain_id| chain_id == utils::Chain::Solana as u32;
+        let sender = recipient
+            .filter(|_| is_solana(request.src_chain_id) == is_solana(request.dest_chain_id))
+            .unwrap_or(get_quote_placeholder_address(request.src_chain_id));
+        Ok(Self {
+            from_chain: get_lifi_chain_id(request.src_chain_id)?,
+            to_chain: get_lifi_chain_id(request.dest_chain_id)?,
+            from_token: get_lifi_token(&request.src_token),
+            to_token: get_lifi_token(&request.dest_token),
+            from_amount: request.src_amount.to_string(),
+            from_address: sender,
+            to_address: recipient.unwrap_or(get_quote_placeholder_address(request.dest_chain_id)),
+        })
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Token {
+    pub address: String,
+    pub chain_id: u32,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeCost {
+    pub name: String,
+    pub token: Token,
+    pub amount: String,
+    /// Whether the fee is deducted from the output amount; otherwise it is paid on top, as the tx value
+    #[serde(default = "default_included")]
+    pub included: bool,
+}
+
+fn default_included() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Estimate {
+    /// Contract to be approved for the source token
+    pub approval_address: String,
+    pub from_amount: String,
+    pub to_amount: String,
+    /// Output amount after the max slippage; the tx reverts below it
+    pub to_amount_min: String,
+    #[serde(default)]
+    pub fee_costs: Vec<FeeCost>,
+    /// Estimated duration in seconds
+    pub execution_duration: f64,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IncludedStep {
+    /// Step type, e.g. `swap` or `cross`
+    #[serde(rename = "type")]
+    pub step_type: String,
+    /// Dex or bridge executing the step
+    pub tool: String,
+}
+
+/// Route tx; an evm tx, or a solana tx only consisting of the base64-encoded serialized tx
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionRequest {
+    pub to: Option<String>,
+    pub data: String,
+    /// Hex-encoded native value
+    pub value: Option<String>,
+    pub chain_id: Option<u64>,
+}
+
+/// Route step returned by the quote endpoint; either a single bridge step, or a `lifi` step combining
+/// swaps and a bridge into a single tx.
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteResponse {
+    #[serde(rename = "type")]
+    pub step_type: String,
+    pub tool: String,
+    pub estimate: Estimate,
+    #[serde(default)]
+    pub included_steps: Vec<IncludedStep>,
+    pub transaction_request: Option<TransactionRequest>,
+}
+
+impl QuoteResponse {
+    /// Bridge executing the cross-chain step of the route.
+    pub fn underlying_bridge(&self) -> String {
+        self.included_steps
+            .iter()
+            .find(|step| step.step_type == "cross")
+            .map(|step| step.tool.clone())
+            .unwrap_or_else(|| self.tool.clone())
+    }
+
+    pub fn min_output_amount(&self) -> Result<U256> {
+        Ok(U256::from_str(&self.estimate.to_amount_min)?)
+    }
+
+    fn transaction_request(&self) -> Result<&TransactionRequest> {
+        self.transaction_request
+            .as_ref()
+            .ok_or_else(|| eyre::eyre!("LI.FI: quote is missing the transaction request"))
+    }
+
+    /// Converts the route evm tx; the native value is returned as decimal, as for the other providers.
+    pub fn get_tx(&self, src_chain_id: u32) -> Result<TxData> {
+        let tx = self.transaction_request()?;
+        if tx.chain_id != Some(u64::from(src_chain_id)) {
+            return Err(eyre::eyre!(
+                "LI.FI: transaction is not executed on the source chain"
+            ));
+        }
+        let (Some(to), Some(value)) = (&tx.to, &tx.value) else {
+            return Err(eyre::eyre!("LI.FI: transaction is not an evm tx"));
+        };
+        Ok(TxData {
+            data: tx.data.clone(),
+            to: to.clone(),
+            value: U256::from_str(value)?.to_string(),
+            ..Default::default()
+        })
+    }
+
+    /// Decodes the route solana tx; `now` is the unix timestamp (in seconds) at which the tx was built.
+    pub fn get_solana_tx(&self, now: u64) -> Result<crate::solana::SolanaTxData> {
+        let tx = base64::engine::general_purpose::STANDARD
+            .decode(&self.transaction_request()?.data)
+            .map_err(|e| eyre::eyre!("LI.FI: invalid solana tx: {}", e))?;
+        crate::solana::SolanaTxData::decode(&tx, now)
+    }
+
+    pub fn into_quote(self, now: u64) -> Result<crate::BridgeQuote> {
+        let underlying_bridge = self.underlying_bridge();
+        let mut fees = vec![];
+        let mut native_fee = U256::ZERO;
+        for fee in self.estimate.fee_costs {
+            let amount = U256::from_str(&fee.amount)?;
+            if !fee.included && utils::is_native_token(&fee.token.address) {
+                native_fee = native_fee.saturating_add(amount);
+            }
+            fees.push(crate::QuoteFee {
+                name: fee.name,
+                token: fee.token.address,
+                amount,
+            });
+        }
+
+        Ok(crate::BridgeQuote::new(
+            crate::SupportedProviders::LiFi,
+            U256::from_str(&self.estimate.from_amount)?,
+            U256::from_str(&self.estimate.to_amount)?,
+            fees,
+            self.estimate.execution_duration.ceil() as u64,
+            now.saturating_add(QUOTE_VALIDITY_SECS),
+        )
+        .with_native_fee(native_fee)
+        .with_underlying_bridge(Some(underlying_bridge)))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LiFi {
+    client: reqwest::Client,
+    providers: HashMap<u32, RootProvider<alloy::transports::http::Http<reqwest::Client>>>,
+}
+
+impl Default for LiFi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LiFi {
+    pub fn new() -> Self {
+        let supported_providers = utils::get_supported_chains()
+            .iter()
+            .map(|(id, chain)| {
+                let rpc_url = reqwest::Url::parse(chain.rpc_url).unwrap(); // infallible
+                let provider = ProviderBuilder::new().on_http(rpc_url);
+                (*id, provider)
+            })
+            .collect::<HashMap<_, _>>();
+
+        Self {
+            client: reqwest::Client::new(),
+            providers: supported_providers,
+        }
+    }
+
+    pub async fn get_lifi_quote(&self, params: &QuoteQueryParams<'_>) -> Result<QuoteResponse> {
+        let response = self
+            .client
+            .get("https://li.quest/v1/quote")
+            .query(&params)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(crate::BridgeError::ProviderRejected(format!(
+                "LI.FI: failed to get quote: {}",
+                response.text().await?
+            ))
+            .into());
+        }
+        Ok(response.json().await?)
+    }
+}
+
+impl crate::BridgeProvider for LiFi {
+    async fn get_bridging_data(
+        &self,
+        request: &crate::BridgeRequest,
+    ) -> eyre::Result<crate::BridgeResponse> {
+        let params = request.try_into()?;
+        if request.calldata.is_some() {
+            return Err(crate::BridgeError::ProviderRejected(
+                "LI.FI: destination calldata is not supported".to_string(),
+            )
+            .into());
+        }
+        if request.src_chain_token_in_sender_permit.is_some() {
+            return Err(crate::BridgeError::ProviderRejected(
+                "LI.FI: permit signatures are not supported".to_string(),
+            )
+            .into());
+        }
+        let is_solana_source = request.src_chain_id == utils::Chain::Solana as u32;
+        if request.dest_chain_id != utils::Chain::Solana as u32 {
+            Address::from_str(&request.dest_recipient)?;
+        }
+
+        let response = self.get_lifi_quote(&params).await?;
+        if let Some(dest_amount) = request.dest_amount {
+            if dest_amount > response.min_output_amount()? {
+                return Err(eyre::eyre!(
+                    "requested dest amount is greater than output amount"
+                ));
+            }
+        }
+        let underlying_bridge = Some(response.underlying_bridge());
+        let estimated_fill_time_secs = Some(response.estimate.execution_duration.ceil() as u64);
+
+        // if source chain is solana, the serialized tx is signed as is; explicit approval will not be required
+        if is_solana_source {
+            let tx = response.get_solana_tx(utils::get_unix_timestamp())?;
+            if tx.signer != request.src_caller {
+                return Err(eyre::eyre!(
+                    "LI.FI: solana tx signer {} does not match the caller",
+                    tx.signer
+                ));
+            }
+            return Ok(crate::BridgeResponse::new(
+                crate::SupportedProviders::LiFi,
+                crate::BridgeAction::SolanaTx(tx),
+            )
+            .with_estimated_fill_time_secs(estimated_fill_time_secs)
+            .with_underlying_bridge(underlying_bridge));
+        }
+
+        let caller = Address::from_str(&request.src_caller)?;
+        let tx = response.get_tx(request.src_chain_id)?;
+
+        // native currency is sent as the tx value; no approval is required
+        if !utils::is_native_token(&request.src_token) {
+            let provider = self
+                .providers
+                .get(&request.src_chain_id)
+                .ok_or(crate::BridgeError::UnsupportedChain(request.src_chain_id))?;
+
+            // the route approval step; the LI.FI diamond requires an explicit approval
+            let allowance_action = request
+                .allowance_action(utils::get_token_allowance_action(
+                    provider,
+                    &Address::from_str(&request.src_token)?,
+                    &request.src_amount,
+                    &caller,
+                    &Address::from_str(&response.estimate.approval_address)?,
+                    &utils::PermitOptions {
+                        spender: None,
+                        permit2_kind: None,
+                        deadline: utils::get_permit_deadline(request.permit_deadline),
+                    },
+                ))
+                .await?;
+
+            // if there is an pre-allowance tx/sig/action required, it must be returned
+            // to be executed by the caller
+            if let Some(mut response) = crate::BridgeResponse::from_allowance_action(
+                crate::SupportedProviders::LiFi,
+                allowance_action,
+            ) {
+                response.underlying_bridge = underlying_bridge;
+                return Ok(response);
+            }
+        }
+
+        Ok(crate::BridgeResponse::new(
+            crate::SupportedProviders::LiFi,
+            crate::BridgeAction::BridgingTx(tx),
+        )
+        .with_estimated_fill_time_secs(estimated_fill_time_secs)
+        .with_underlying_bridge(underlying_bridge))
+    }
+
+    async fn get_quote(&self, request: &crate::QuoteRequest) -> eyre::Result<crate::BridgeQuote> {
+        let params = request.try_into()?;
+        if request.calldata.is_some() {
+            return Err(crate::BridgeError::ProviderRejected(
+                "LI.FI: destination calldata is not supported".to_string(),
+            )
+            .into());
+        }
+        let response = self.get_lifi_quote(&params).await?;
+        response.into_quote(utils::get_unix_timestamp())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote_response() -> QuoteResponse {
+        let usdc_base = serde_json::json!({
+            "address": "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913", "chainId": 8453,
+            "symbol": "USDC", "decimals": 6, "name": "USD Coin"
+        });
+        serde_json::from_value(serde_json::json!({
+            "type": "lifi",
+            "id": "0x01",
+            "tool": "stargateV2",
+            "action": { "fromChainId": 8453, "toChainId": 42161 },
+            "estimate": {
+                "tool": "stargateV2",
+                "approvalAddress": "0x1231DEB6f5749EF6cE6943a275A1D3E7486F4EaE",
+                "fromAmount": "2000000",
+                "toAmount": "1998400",
+                "toAmountMin": "1993404",
+                "feeCosts": [{
+                    "name": "LIFI Fixed Fee", "token": usdc_base, "amount": "5000",
+                    "amountUSD": "0.005", "included": true
+                }, {
+                    "name": "LayerZero fee", "amount": "30000000000000", "amountUSD": "0.09", "included": false,
+                    "token": {
+                        "address": "0x0000000000000000000000000000000000000000", "chainId": 8453,
+                        "symbol": "ETH", "decimals": 18, "name": "ETH"
+                    }
+                }],
+                "gasCosts": [],
+                "executionDuration": 62.5
+            },
+            "includedSteps": [
+                { "type": "protocol", "tool": "feeCollection" },
+                { "type": "cross", "tool": "stargateV2" }
+            ],
+            "transactionRequest": {
+                "from": "0x000007357111E4789005d4eBfF401a18D99770cE",
+                "to": "0x1231DEB6f5749EF6cE6943a275A1D3E7486F4EaE",
+                "data": "0xabcd",
+                "value": "0x5af3107a4000",
+                "chainId": 8453
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn route_tx() {
+        let response = quote_response();
+        assert_eq!(response.underlying_bridge(), "stargateV2");
+        assert_eq!(
+            response.min_output_amount().unwrap(),
+            U256::from(1_993_404u32)
+        );
+
+        let tx = response.get_tx(8453).unwrap();
+        assert_eq!(tx.to, "0x1231DEB6f5749EF6cE6943a275A1D3E7486F4EaE");
+        assert_eq!(tx.value, "100000000000000");
+        assert!(response.get_tx(42161).is_err());
+    }
+
+    #[test]
+    fn solana_route() {
+        assert_eq!(
+            get_lifi_chain_id(utils::Chain::Solana as u32).unwrap(),
+            LIFI_SOLANA_CHAIN_ID
+        );
+        assert_eq!(get_lifi_chain_id(utils::Chain::Base as u32).unwrap(), 8453);
+        assert!(get_lifi_chain_id(10).is_err());
+
+        // evm recipients are not used as the solana sender
+        let request = crate::QuoteRequest {
+            src_chain_id: utils::Chain::Solana as u32,
+            src_token: "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".into(),
+            src_amount: U256::from(2_000_000u32),
+            dest_chain_id: utils::Chain::Base as u32,
+            dest_token: "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913".into(),
+            dest_recipient: Some("0x000007357111E4789005d4eBfF401a18D99770cE".into()),
+            calldata: None,
+            strategy: None,
+        };
+        let params = QuoteQueryParams::try_from(&request).unwrap();
+        assert_eq!(params.from_chain, LIFI_SOLANA_CHAIN_ID);
+        assert_eq!(params.from_address, SOLANA_QUOTE_PLACEHOLDER_ADDRESS);
+        assert_eq!(
+            params.to_address,
+            "0x000007357111E4789005d4eBfF401a18D99770cE"
+        );
+
+        // solana txs are returned serialized, as base64
+        let mut response = quote_response();
+        let mut tx = vec![1];
+        tx.extend([0; 64]);
+        tx.extend([0x80, 1, 0, 0, 1]);
+        tx.extend([1; 32]);
+        tx.extend([7; 32]);
+        tx.extend([0, 0]);
+        response.transaction_request = Some(TransactionRequest {
+            to: None,
+            data: base64::engine::general_purpose::STANDARD.encode(&tx),
+            value: None,
+            chain_id: None,
+        });
+        let solana_tx = response.get_solana_tx(1_700_000_000).unwrap();
+        assert_eq!(solana_tx.signer, bs58::encode([1; 32]).into_string());
+        assert!(response.get_tx(8453).is_err());
+    }
+
+    #[test]
+    fn response_into_quote() {
+        let quote = quote_response().into_quote(1_700_000_000).unwrap();
+        assert_eq!(quote.provider, crate::SupportedProviders::LiFi);
+        assert_eq!(quote.output_amount, U256::from(1_998_400u32));
+        assert_eq!(quote.estimated_fill_time_secs, 63);
+        assert_eq!(quote.underlying_bridge.as_deref(), Some("stargateV2"));
+        assert_eq!(quote.fees.len(), 2);
+        assert_eq!(quote.fees[0].amount, U256::from(5_000u32));
+        // fees paid on top of the input amount, as the tx value
+        assert_eq!(quote.native_fee, U256::from(30_000_000_000_000u64));
+    }
+}
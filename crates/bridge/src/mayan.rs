@@ -0,0 +1,587 @@
+use crate::U256;
+use std::{collections::HashMap, str::FromStr};
+
+use alloy::primitives::{keccak256, Address, B256};
+use alloy::providers::{ProviderBuilder, RootProvider};
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use eyre::Result;
+
+sol! {
+    /// Mayan Swift; locks the input on the source chain for an auction between solvers, who fill the order
+    /// on the destination chain. Settlement is verified through Wormhole.
+    interface IMayanSwift {
+        struct OrderParams {
+            bytes32 trader;
+            bytes32 tokenOut;
+            uint64 minAmountOut;
+            uint64 gasDrop;
+            uint64 cancelFee;
+            uint64 refundFee;
+            uint64 deadline;
+            bytes32 destAddr;
+            uint16 destChainId;
+            bytes32 referrerAddr;
+            uint8 referrerBps;
+            uint8 auctionMode;
+            bytes32 random;
+        }
+
+        function createOrderWithEth(OrderParams memory params) external payable returns (bytes32 orderHash);
+
+        function createOrderWithToken(address tokenIn, uint256 amountIn, OrderParams memory params)
+            external
+            returns (bytes32 orderHash);
+    }
+}
+
+/// Mayan represents the native currency (including SOL) as the zero address
+const MAYAN_NATIVE_TOKEN: &str = "0x0000000000000000000000000000000000000000";
+
+/// Price api version the quotes are requested for
+const MAYAN_SDK_VERSION: &str = "9_7_0";
+
+/// Swift order amounts are normalized to at most 8 decimals, as Wormhole token amounts
+const SWIFT_MAX_DECIMALS: u8 = 8;
+
+/// Validity of a quote; auction prices follow the solvers' inventory
+const QUOTE_VALIDITY_SECS: u64 = 30;
+
+/// Mayan chain name and Wormhole chain id of a supported chain.
+fn get_mayan_chain(chain_id: u32) -> Option<(&'static str, u16)> {
+    match utils::Chain::try_from(chain_id).ok()? {
+        utils::Chain::Solana => Some(("solana", 1)),
+        utils::Chain::Ethereum => Some(("ethereum", 2)),
+        utils::Chain::Arbitrum => Some(("arbitrum", 23)),
+        utils::Chain::Base => Some(("base", 30)),
+    }
+}
+
+fn get_mayan_token(token: &str) -> &str {
+    if utils::is_native_token(token) {
+        MAYAN_NATIVE_TOKEN
+    } else {
+        token
+    }
+}
+
+/// Encodes an address of the chain as bytes32; solana addresses are base58, evm addresses are left padded.
+fn get_address_bytes32(chain_id: u32, address: &str) -> Result<B256> {
+    if chain_id != utils::Chain::Solana as u32 {
+        return Ok(Address::from_str(address)?.into_word());
+    }
+    if address == MAYAN_NATIVE_TOKEN {
+        return Ok(B256::ZERO);
+    }
+    let bytes = bs58::decode(address)
+        .into_vec()
+        .map_err(|e| eyre::eyre!("invalid solana address {}: {}", address, e))?;
+    if bytes.len() != 32 {
+        return Err(eyre::eyre!("invalid solana address: {}", address));
+    }
+    Ok(B256::from_slice(&bytes))
+}
+
+/// Converts a decimal amount, as returned by the price api, into base units.
+fn to_base_units(amount: f64, decimals: u8) -> Result<U256> {
+    let amount = format!("{:.*}", usize::from(decimals), amount).replace('.', "");
+    Ok(U256::from_str(&amount)?)
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteQueryParams<'a> {
+    /// Input amount in base units
+    pub amount_in64: String,
+    pub from_token: &'a str,
+    pub from_chain: &'a str,
+    pub to_token: &'a str,
+    pub to_chain: &'a str,
+    pub slippage_bps: &'a str,
+    pub swift: bool,
+    pub mctp: bool,
+    pub wormhole: bool,
+    pub sdk_version: &'a str,
+}
+
+impl<'a> QuoteQueryParams<'a> {
+    /// Swift only quote; the Wormhole token bridge and MCTP routes are slower than the existing providers.
+    fn new(
+        src_chain_id: u32,
+        src_token: &'a str,
+        src_amount: U256,
+        dest_chain_id: u32,
+        dest_token: &'a str,
+    ) -> Result<Self> {
+        let (from_chain, _) = get_mayan_chain(src_chain_id)
+            .ok_or(crate::BridgeError::UnsupportedChain(src_chain_id))?;
+        let (to_chain, _) = get_mayan_chain(dest_chain_id)
+            .ok_or(crate::BridgeError::UnsupportedChain(dest_chain_id))?;
+        Ok(Self {
+            amount_in64: src_amount.to_string(),
+            from_token: get_mayan_token(src_token),
+            from_chain,
+            to_token: get_mayan_token(dest_token),
+            to_chain,
+            slippage_bps: "auto",
+            swift: true,
+            mctp: false,
+            wormhole: false,
+            sdk_version: MAYAN_SDK_VERSION,
+        })
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Token {
+    pub contract: String,
+    /// Solana mint address, if a solana token
+    pub mint: Option<String>,
+    pub decimals: u8,
+    pub symbol: String,
+}
+
+/// Swift route quote; amounts are decimals rather than base units
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Quote {
+    #[serde(rename = "type")]
+    pub quote_type: String,
+    pub effective_amount_in64: String,
+    pub expected_amount_out: f64,
+    /// Output amount after the max slippage; the order is refunded below it
+    pub min_amount_out: f64,
+    pub eta_seconds: u64,
+    pub from_token: Token,
+    pub to_token: Token,
+    /// Relayer fee, in the input token
+    #[serde(default)]
+    pub client_relayer_fee_success: Option<f64>,
+    pub swift_mayan_contract: Option<String>,
+    pub swift_input_contract: Option<String>,
+    pub swift_auction_mode: Option<u8>,
+    pub cancel_relayer_fee64: Option<String>,
+    pub refund_relayer_fee64: Option<String>,
+    pub deadline64: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct QuoteResponse {
+    pub quotes: Vec<Quote>,
+}
+
+impl Quote {
+    pub fn output_amount(&self) -> Result<U256> {
+        to_base_units(self.expected_amount_out, self.to_token.decimals)
+    }
+
+    pub fn min_output_amount(&self) -> Result<U256> {
+        to_base_units(self.min_amount_out, self.to_token.decimals)
+    }
+
+    /// Builds the order params of the quote; `random` makes the order hash unique.
+    fn get_order_params(
+        &self,
+        dest_chain_id: u32,
+        trader: &Address,
+        recipient: &str,
+        min_amount_out: U256,
+        random: B256,
+    ) -> Result<IMayanSwift::OrderParams> {
+        let (_, dest_wormhole_chain_id) = get_mayan_chain(dest_chain_id)
+            .ok_or(crate::BridgeError::UnsupportedChain(dest_chain_id))?;
+        let parse_u64 = |value: &Option<String>, name: &str| -> Result<u64> {
+            value
+                .as_deref()
+                .ok_or_else(|| eyre::eyre!("Mayan: quote is missing {}", name))?
+                .parse()
+                .map_err(|e| eyre::eyre!("Mayan: invalid {}: {}", name, e))
+        };
+        let min_amount_out = match self.to_token.decimals.checked_sub(SWIFT_MAX_DECIMALS) {
+            Some(excess_decimals) => {
+                min_amount_out / U256::from(10u64).pow(U256::from(excess_decimals))
+            }
+            None => min_amount_out,
+        };
+        let token_out = self
+            .to_token
+            .mint
+            .as_deref()
+            .unwrap_or(&self.to_token.contract);
+
+        Ok(IMayanSwift::OrderParams {
+            trader: trader.into_word(),
+            tokenOut: get_address_bytes32(dest_chain_id, token_out)?,
+            minAmountOut: min_amount_out.try_into()?,
+            gasDrop: 0,
+            cancelFee: parse_u64(&self.cancel_relayer_fee64, "cancelRelayerFee64")?,
+            refundFee: parse_u64(&self.refund_relayer_fee64, "refundRelayerFee64")?,
+            deadline: parse_u64(&self.deadline64, "deadline64")?,
+            destAddr: get_address_bytes32(dest_chain_id, recipient)?,
+            destChainId: dest_wormhole_chain_id,
+            referrerAddr: B256::ZERO,
+            referrerBps: 0,
+            auctionMode: self
+                .swift_auction_mode
+                .ok_or_else(|| eyre::eyre!("Mayan: quote is missing swiftAuctionMode"))?,
+            random,
+        })
+    }
+
+    pub fn into_quote(self, now: u64) -> Result<crate::BridgeQuote> {
+        let mut fees = vec![];
+        if let Some(relayer_fee) = self.client_relayer_fee_success {
+            fees.push(crate::QuoteFee {
+                name: "relayerFee".to_string(),
+                token: self.from_token.contract.clone(),
+                amount: to_base_units(relayer_fee, self.from_token.decimals)?,
+            });
+        }
+
+        Ok(crate::BridgeQuote::new(
+            crate::SupportedProviders::Mayan,
+            U256::from_str(&self.effective_amount_in64)?,
+            self.output_amount()?,
+            fees,
+            self.eta_seconds,
+            now.saturating_add(QUOTE_VALIDITY_SECS),
+        ))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MayanBridge {
+    client: reqwest::Client,
+    providers: HashMap<u32, RootProvider<alloy::transports::http::Http<reqwest::Client>>>,
+}
+
+impl Default for MayanBridge {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MayanBridge {
+    pub fn new() -> Self {
+        let supported_providers = utils::get_supported_chains()
+            .iter()
+            .filter(|(id, _)| **id != utils::Chain::Solana as u32)
+            .map(|(id, chain)| {
+                let rpc_url = reqwest::Url::parse(chain.rpc_url).unwrap(); // infallible
+                let provider = ProviderBuilder::new().on_http(rpc_url);
+                (*id, provider)
+            })
+            .collect::<HashMap<_, _>>();
+
+        Self {
+            client: reqwest::Client::new(),
+            providers: supported_providers,
+        }
+    }
+
+    /// Returns the swift quote of the route.
+    pub async fn get_swift_quote(&self, params: &QuoteQueryParams<'_>) -> Result<Quote> {
+        let response = self
+            .client
+            .get("https://price-api.mayan.finance/v3/quote")
+            .query(&params)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(crate::BridgeError::ProviderRejected(format!(
+                "Mayan: failed to get quote: {}",
+                response.text().await?
+            ))
+            .into());
+        }
+        let response: QuoteResponse = response.json().await?;
+        response
+            .quotes
+            .into_iter()
+            .find(|quote| quote.quote_type == "SWIFT")
+            .ok_or_else(|| {
+                crate::BridgeError::ProviderRejected("Mayan: no swift route".to_string()).into()
+            })
+    }
+}
+
+/// Only evm source chains are supported; solana source orders are built by the Mayan sdk from solana
+/// instructions, rather than a tx. Solana routes are rejected when quoting too, so that a quote is only
+/// returned for routes the bridging tx can be built for.
+fn check_source_chain(src_chain_id: u32) -> Result<()> {
+    if src_chain_id == utils::Chain::Solana as u32 {
+        return Err(crate::BridgeError::ProviderRejected(
+            "Mayan: solana source orders are not supported".to_string(),
+        )
+        .into());
+    }
+    Ok(())
+}
+
+impl crate::BridgeProvider for MayanBridge {
+    async fn get_bridging_data(
+        &self,
+        request: &crate::BridgeRequest,
+    ) -> eyre::Result<crate::BridgeResponse> {
+        check_source_chain(request.src_chain_id)?;
+        if request.calldata.is_some() {
+            return Err(crate::BridgeError::ProviderRejected(
+                "Mayan: destination calldata is not supported".to_string(),
+            )
+            .into());
+        }
+        if request.src_chain_token_in_sender_permit.is_some() {
+            return Err(crate::BridgeError::ProviderRejected(
+                "Mayan: permit signatures are not supported".to_string(),
+            )
+            .into());
+        }
+
+        let params = QuoteQueryParams::new(
+            request.src_chain_id,
+            &request.src_token,
+            request.src_amount,
+            request.dest_chain_id,
+            &request.dest_token,
+        )?;
+        let quote = self.get_swift_quote(&params).await?;
+        let swift_contract = Address::from_str(
+            quote
+                .swift_mayan_contract
+                .as_deref()
+                .ok_or_else(|| eyre::eyre!("Mayan: quote is missing swiftMayanContract"))?,
+        )?;
+        // the input token is swapped into the swift input token by the Mayan forwarder; not supported
+        if quote.swift_input_contract.as_deref().map(get_mayan_token)
+            != Some(get_mayan_token(&request.src_token))
+        {
+            return Err(crate::BridgeError::ProviderRejected(
+                "Mayan: source swaps are not supported".to_string(),
+            )
+            .into());
+        }
+
+        if let Some(dest_amount) = request.dest_amount {
+            if dest_amount > quote.output_amount()? {
+                return Err(eyre::eyre!(
+                    "requested dest amount is greater than output amount"
+                ));
+            }
+        }
+        let min_amount_out = match request.dest_amount {
+            Some(dest_amount) => dest_amount,
+            None => quote.min_output_amount()?,
+        };
+
+        let caller = Address::from_str(&request.src_caller)?;
+        let random = keccak256(
+            [
+                caller.as_slice(),
+                &request.src_amount.to_be_bytes::<32>(),
+                &utils::get_unix_timestamp().to_be_bytes(),
+            ]
+            .concat(),
+        );
+        let order_params = quote.get_order_params(
+            request.dest_chain_id,
+            &caller,
+            &request.dest_recipient,
+            min_amount_out,
+            random,
+        )?;
+
+        let response = |bridge_action| {
+            crate::BridgeResponse::new(crate::SupportedProviders::Mayan, bridge_action)
+                .with_estimated_fill_time_secs(Some(quote.eta_seconds))
+        };
+
+        // native currency is sent as the tx value; no approval is required
+        if utils::is_native_token(&request.src_token) {
+            let calldata = IMayanSwift::createOrderWithEthCall {
+                params: order_params,
+            };
+            return Ok(response(crate::BridgeAction::BridgingTx(crate::TxData {
+                data: hex::encode(calldata.abi_encode()),
+                to: swift_contract.to_string(),
+                value: request.src_amount.to_string(),
+                ..Default::default()
+            })));
+        }
+
+        let token = Address::from_str(&request.src_token)?;
+        let provider = self
+            .providers
+            .get(&request.src_chain_id)
+            .ok_or(crate::BridgeError::UnsupportedChain(request.src_chain_id))?;
+
+        // permits are only consumed through the Mayan forwarder; an explicit approval is required
+        let allowance_action = request
+            .allowance_action(utils::get_token_allowance_action(
+                provider,
+                &token,
+                &request.src_amount,
+                &caller,
+                &swift_contract,
+                &utils::PermitOptions {
+                    spender: None,
+                    permit2_kind: None,
+                    deadline: utils::get_permit_deadline(request.permit_deadline),
+                },
+            ))
+            .await?;
+
+        // if there is an pre-allowance tx/sig/action required, it must be returned
+        // to be executed by the caller
+        if let Some(response) = crate::BridgeResponse::from_allowance_action(
+            crate::SupportedProviders::Mayan,
+            allowance_action,
+        ) {
+            return Ok(response);
+        }
+
+        let calldata = IMayanSwift::createOrderWithTokenCall {
+            tokenIn: token,
+            amountIn: request.src_amount,
+            params: order_params,
+        };
+        Ok(response(crate::BridgeAction::BridgingTx(crate::TxData {
+            data: hex::encode(calldata.abi_encode()),
+            to: swift_contract.to_string(),
+            value: "0".to_string(),
+            ..Default::default()
+        })))
+    }
+
+    async fn get_quote(&self, request: &crate::QuoteRequest) -> eyre::Result<crate::BridgeQuote> {
+        check_source_chain(request.src_chain_id)?;
+        if request.calldata.is_some() {
+            return Err(crate::BridgeError::ProviderRejected(
+                "Mayan: destination calldata is not supported".to_string(),
+            )
+            .into());
+        }
+        let params = QuoteQueryParams::new(
+            request.src_chain_id,
+            &request.src_token,
+            request.src_amount,
+            request.dest_chain_id,
+            &request.dest_token,
+        )?;
+        self.get_swift_quote(&params)
+            .await?
+            .into_quote(utils::get_unix_timestamp())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::address;
+
+    fn swift_quote() -> Quote {
+        serde_json::from_value(serde_json::json!({
+            "type": "SWIFT",
+            "effectiveAmountIn64": "2000000",
+            "expectedAmountOut": 1.996512,
+            "minAmountOut": 1.986529,
+            "etaSeconds": 12,
+            "fromToken": {
+                "contract": "0xaf88d065e77c8cC2239327C5EDb3A432268e5831", "decimals": 6, "symbol": "USDC"
+            },
+            "toToken": {
+                "contract": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+                "mint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", "decimals": 6, "symbol": "USDC"
+            },
+            "clientRelayerFeeSuccess": 0.003488,
+            "swiftMayanContract": "0xC38e4e6A15593f908255214653d3D947CA1c2338",
+            "swiftInputContract": "0xaf88d065e77c8cC2239327C5EDb3A432268e5831",
+            "swiftAuctionMode": 2,
+            "cancelRelayerFee64": "1200",
+            "refundRelayerFee64": "340",
+            "deadline64": "1700000600"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn address_bytes32() {
+        let usdc = get_address_bytes32(
+            utils::Chain::Solana as u32,
+            "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+        )
+        .unwrap();
+        assert_eq!(
+            hex::encode(usdc),
+            "c6fa7af3bedbad3a3d65f36aabc97431b1bbe4c2d2f6e0e47ca60203452f5d61"
+        );
+        let evm = get_address_bytes32(
+            utils::Chain::Base as u32,
+            "0x000007357111E4789005d4eBfF401a18D99770cE",
+        )
+        .unwrap();
+        assert_eq!(
+            evm,
+            address!("000007357111E4789005d4eBfF401a18D99770cE").into_word()
+        );
+        assert!(get_address_bytes32(utils::Chain::Solana as u32, "invalid0OIl").is_err());
+    }
+
+    #[test]
+    fn swift_order_params() {
+        let quote = swift_quote();
+        assert_eq!(quote.output_amount().unwrap(), U256::from(1_996_512u32));
+        assert_eq!(quote.min_output_amount().unwrap(), U256::from(1_986_529u32));
+
+        let trader = address!("000007357111E4789005d4eBfF401a18D99770cE");
+        let params = quote
+            .get_order_params(
+                utils::Chain::Solana as u32,
+                &trader,
+                "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+                quote.min_output_amount().unwrap(),
+                B256::ZERO,
+            )
+            .unwrap();
+        assert_eq!(params.trader, trader.into_word());
+        assert_eq!(params.minAmountOut, 1_986_529);
+        assert_eq!(params.destChainId, 1);
+        assert_eq!(params.cancelFee, 1_200);
+        assert_eq!(params.deadline, 1_700_000_600);
+        assert_eq!(params.auctionMode, 2);
+
+        // 18 decimals amounts are normalized to 8 decimals
+        let mut quote = quote;
+        quote.to_token.decimals = 18;
+        let params = quote
+            .get_order_params(
+                utils::Chain::Solana as u32,
+                &trader,
+                "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+                U256::from(1_500_000_000_000_000_000u64),
+                B256::ZERO,
+            )
+            .unwrap();
+        assert_eq!(params.minAmountOut, 150_000_000);
+    }
+
+    #[test]
+    fn swift_quote_into_quote() {
+        let quote = swift_quote().into_quote(1_700_000_000).unwrap();
+        assert_eq!(quote.provider, crate::SupportedProviders::Mayan);
+        assert_eq!(quote.input_amount, U256::from(2_000_000u32));
+        assert_eq!(quote.output_amount, U256::from(1_996_512u32));
+        assert_eq!(quote.estimated_fill_time_secs, 12);
+        assert_eq!(quote.fees[0].amount, U256::from(3_488u32));
+    }
+
+    #[test]
+    fn solana_source_rejected() {
+        assert!(check_source_chain(utils::Chain::Base as u32).is_ok());
+        let err = check_source_chain(utils::Chain::Solana as u32).unwrap_err();
+        assert!(matches!(
+            crate::BridgeError::from_report(&err),
+            crate::BridgeError::ProviderRejected(_)
+        ));
+    }
+}
@@ -0,0 +1,363 @@
+use crate::U256;
+use std::{collections::HashMap, str::FromStr};
+
+use alloy::primitives::Address;
+use alloy::providers::{ProviderBuilder, RootProvider};
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use eyre::Result;
+
+sol! {
+    /// OP-stack canonical bridge on L1; deposits are relayed to the L2 by the rollup derivation itself
+    interface IL1StandardBridge {
+        function depositETHTo(address _to, uint32 _minGasLimit, bytes calldata _extraData) external payable;
+
+        function depositERC20To(
+            address _l1Token,
+            address _l2Token,
+            address _to,
+            uint256 _amount,
+            uint32 _minGasLimit,
+            bytes calldata _extraData
+        ) external;
+    }
+
+    /// L2 representation of an L1 token, minted by the L2 standard bridge
+    interface IOptimismMintableERC20 {
+        function remoteToken() external view returns (address);
+        // legacy getter, prior to bedrock
+        function l1Token() external view returns (address);
+    }
+}
+
+/// Gas limit of the deposit finalization on L2; as used by the superchain bridges ui
+const DEPOSIT_MIN_GAS_LIMIT: u32 = 200_000;
+
+/// Deposits are included on L2 once the L1 deposit tx is picked up by the sequencer, typically within a few minutes
+const ESTIMATED_DEPOSIT_SECS: u64 = 3 * 60;
+
+/// Withdrawals (L2 -> L1) can only be finalized after the 7 day fault proof challenge period
+const WITHDRAWAL_DELAY_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Validity of a quote; canonical deposits have no fees, the quote only depends on the route
+const QUOTE_VALIDITY_SECS: u64 = 5 * 60;
+
+/// Resolves the L1 standard bridge of the OP-stack destination chain; only Ethereum -> L2 deposits are supported.
+fn get_l1_standard_bridge(src_chain_id: u32, dest_chain_id: u32) -> Result<Address> {
+    if src_chain_id != utils::Chain::Ethereum as u32 {
+        return Err(crate::BridgeError::UnsupportedChain(src_chain_id).into());
+    }
+    let bridge = utils::get_supported_chains()
+        .get(&dest_chain_id)
+        .and_then(|chain| chain.l1_standard_bridge)
+        .ok_or(crate::BridgeError::UnsupportedChain(dest_chain_id))?;
+    Ok(Address::from_str(bridge)?)
+}
+
+/// Builds the deposit tx; `None` tokens represent the native currency (ETH), sent as the tx value.
+fn get_deposit_tx(
+    bridge: &Address,
+    l1_token: Option<Address>,
+    l2_token: Option<Address>,
+    recipient: &Address,
+    amount: U256,
+) -> Result<crate::TxData> {
+    let (data, value) = match (l1_token, l2_token) {
+        (None, None) => (
+            IL1StandardBridge::depositETHToCall {
+                _to: *recipient,
+                _minGasLimit: DEPOSIT_MIN_GAS_LIMIT,
+                _extraData: Default::default(),
+            }
+            .abi_encode(),
+            amount,
+        ),
+        (Some(l1_token), Some(l2_token)) => (
+            IL1StandardBridge::depositERC20ToCall {
+                _l1Token: l1_token,
+                _l2Token: l2_token,
+                _to: *recipient,
+                _amount: amount,
+                _minGasLimit: DEPOSIT_MIN_GAS_LIMIT,
+                _extraData: Default::default(),
+            }
+            .abi_encode(),
+            U256::ZERO,
+        ),
+        _ => {
+            return Err(crate::BridgeError::ProviderRejected(
+                "OP-stack: native currency can only be bridged to the native currency".to_string(),
+            )
+            .into())
+        }
+    };
+    Ok(crate::TxData {
+        data: hex::encode(data),
+        to: bridge.to_string(),
+        value: value.to_string(),
+        ..Default::default()
+    })
+}
+
+fn parse_token(token: &str) -> Result<Option<Address>> {
+    if utils::is_native_token(token) {
+        return Ok(None);
+    }
+    Ok(Some(Address::from_str(token)?))
+}
+
+#[derive(Debug, Clone)]
+pub struct OpStackBridge {
+    providers: HashMap<u32, RootProvider<alloy::transports::http::Http<reqwest::Client>>>,
+}
+
+impl Default for OpStackBridge {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OpStackBridge {
+    pub fn new() -> Self {
+        let supported_providers = utils::get_supported_chains()
+            .iter()
+            .map(|(id, chain)| {
+                let rpc_url = reqwest::Url::parse(chain.rpc_url).unwrap(); // infallible
+                let provider = ProviderBuilder::new().on_http(rpc_url);
+                (*id, provider)
+            })
+            .collect::<HashMap<_, _>>();
+
+        Self {
+            providers: supported_providers,
+        }
+    }
+
+    /// Validates that the L2 token is the canonical representation of the L1 token; tokens bridged to a
+    /// non-matching L2 token would be refunded on L1 only after the withdrawal delay.
+    async fn check_l2_token(
+        &self,
+        dest_chain_id: u32,
+        l1_token: Option<Address>,
+        l2_token: Option<Address>,
+    ) -> Result<()> {
+        let (Some(l1_token), Some(l2_token)) = (l1_token, l2_token) else {
+            return Ok(());
+        };
+        let provider = self
+            .providers
+            .get(&dest_chain_id)
+            .ok_or(crate::BridgeError::UnsupportedChain(dest_chain_id))?;
+        let remote_token = match utils::eth_call(
+            provider,
+            &l2_token,
+            IOptimismMintableERC20::remoteTokenCall {},
+        )
+        .await
+        {
+            Ok(remote_token) => remote_token._0,
+            Err(_) => {
+                utils::eth_call(provider, &l2_token, IOptimismMintableERC20::l1TokenCall {})
+                    .await
+                    .map_err(|_| {
+                        crate::BridgeError::ProviderRejected(format!(
+                            "OP-stack: {} is not a canonically bridged token",
+                            l2_token
+                        ))
+                    })?
+                    ._0
+            }
+        };
+        if remote_token != l1_token {
+            return Err(crate::BridgeError::ProviderRejected(format!(
+                "OP-stack: {} is not the canonical representation of {}",
+                l2_token, l1_token
+            ))
+            .into());
+        }
+        Ok(())
+    }
+}
+
+impl crate::BridgeProvider for OpStackBridge {
+    async fn get_bridging_data(
+        &self,
+        request: &crate::BridgeRequest,
+    ) -> eyre::Result<crate::BridgeResponse> {
+        let bridge = get_l1_standard_bridge(request.src_chain_id, request.dest_chain_id)?;
+        if request.calldata.is_some() {
+            return Err(crate::BridgeError::ProviderRejected(
+                "OP-stack: destination calldata is not supported".to_string(),
+            )
+            .into());
+        }
+        if request.src_chain_token_in_sender_permit.is_some() {
+            return Err(crate::BridgeError::ProviderRejected(
+                "OP-stack: permit signatures are not supported".to_string(),
+            )
+            .into());
+        }
+        // deposited amount is minted 1:1 on the destination chain
+        if let Some(dest_amount) = request.dest_amount {
+            if dest_amount > request.src_amount {
+                return Err(eyre::eyre!(
+                    "requested dest amount is greater than output amount"
+                ));
+            }
+        }
+
+        let l1_token = parse_token(&request.src_token)?;
+        let l2_token = parse_token(&request.dest_token)?;
+        let deposit_tx = get_deposit_tx(
+            &bridge,
+            l1_token,
+            l2_token,
+            &Address::from_str(&request.dest_recipient)?,
+            request.src_amount,
+        )?;
+        self.check_l2_token(request.dest_chain_id, l1_token, l2_token)
+            .await?;
+
+        // approval responses carry the deposit latencies too
+        let with_deposit_details = |response: crate::BridgeResponse| {
+            response
+                .with_estimated_fill_time_secs(Some(ESTIMATED_DEPOSIT_SECS))
+                .with_withdrawal_delay_secs(Some(WITHDRAWAL_DELAY_SECS))
+        };
+        let response = |bridge_action| {
+            with_deposit_details(crate::BridgeResponse::new(
+                crate::SupportedProviders::OpStack,
+                bridge_action,
+            ))
+        };
+
+        // native currency deposit; no allowance is required, the amount is sent as value
+        let Some(l1_token) = l1_token else {
+            return Ok(response(crate::BridgeAction::BridgingTx(deposit_tx)));
+        };
+
+        let provider = self
+            .providers
+            .get(&request.src_chain_id)
+            .ok_or(crate::BridgeError::UnsupportedChain(request.src_chain_id))?;
+
+        // the standard bridge consumes neither EIP-2612 nor permit2 permits; an explicit approval is required
+        let allowance_action = request
+            .allowance_action(utils::get_token_allowance_action(
+                provider,
+                &l1_token,
+                &request.src_amount,
+                &Address::from_str(&request.src_caller)?,
+                &bridge,
+                &utils::PermitOptions {
+                    spender: None,
+                    permit2_kind: None,
+                    deadline: utils::get_permit_deadline(request.permit_deadline),
+                },
+            ))
+            .await?;
+
+        // if there is an pre-allowance tx/sig/action required, it must be returned
+        // to be executed by the caller
+        if let Some(response) = crate::BridgeResponse::from_allowance_action(
+            crate::SupportedProviders::OpStack,
+            allowance_action,
+        ) {
+            return Ok(with_deposit_details(response));
+        }
+
+        Ok(response(crate::BridgeAction::BridgingTx(deposit_tx)))
+    }
+
+    async fn get_quote(&self, request: &crate::QuoteRequest) -> eyre::Result<crate::BridgeQuote> {
+        get_l1_standard_bridge(request.src_chain_id, request.dest_chain_id)?;
+        if request.calldata.is_some() {
+            return Err(crate::BridgeError::ProviderRejected(
+                "OP-stack: destination calldata is not supported".to_string(),
+            )
+            .into());
+        }
+        let l1_token = parse_token(&request.src_token)?;
+        let l2_token = parse_token(&request.dest_token)?;
+        if l1_token.is_some() != l2_token.is_some() {
+            return Err(crate::BridgeError::ProviderRejected(
+                "OP-stack: native currency can only be bridged to the native currency".to_string(),
+            )
+            .into());
+        }
+        self.check_l2_token(request.dest_chain_id, l1_token, l2_token)
+            .await?;
+
+        Ok(crate::BridgeQuote::new(
+            crate::SupportedProviders::OpStack,
+            request.src_amount,
+            // no bridge fees; the L2 deposit gas is paid through the L1 tx
+            request.src_amount,
+            vec![],
+            ESTIMATED_DEPOSIT_SECS,
+            utils::get_unix_timestamp().saturating_add(QUOTE_VALIDITY_SECS),
+        )
+        .with_withdrawal_delay_secs(Some(WITHDRAWAL_DELAY_SECS)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::address;
+
+    #[test]
+    fn l1_standard_bridge() {
+        assert_eq!(
+            get_l1_standard_bridge(utils::Chain::Ethereum as u32, utils::Chain::Base as u32)
+                .unwrap(),
+            address!("3154Cf16ccdb4C6d922629664174b904d80F2C35")
+        );
+        // withdrawals are not supported
+        assert!(
+            get_l1_standard_bridge(utils::Chain::Base as u32, utils::Chain::Ethereum as u32)
+                .is_err()
+        );
+        // arbitrum is not an OP-stack rollup
+        assert!(get_l1_standard_bridge(
+            utils::Chain::Ethereum as u32,
+            utils::Chain::Arbitrum as u32
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn deposit_tx() {
+        let bridge = address!("3154Cf16ccdb4C6d922629664174b904d80F2C35");
+        let recipient = address!("000007357111E4789005d4eBfF401a18D99770cE");
+
+        let tx = get_deposit_tx(&bridge, None, None, &recipient, U256::from(1_000u32)).unwrap();
+        assert_eq!(tx.value, "1000");
+        assert!(tx
+            .data
+            .starts_with(&hex::encode(IL1StandardBridge::depositETHToCall::SELECTOR)));
+
+        let usdc = address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+        let usdbc = address!("d9aAEc86B65D86f6A7B5B1b0c42FFA531710b6CA");
+        let tx = get_deposit_tx(
+            &bridge,
+            Some(usdc),
+            Some(usdbc),
+            &recipient,
+            U256::from(2_000_000u32),
+        )
+        .unwrap();
+        assert_eq!(tx.value, "0");
+        let call = IL1StandardBridge::depositERC20ToCall::abi_decode(
+            &hex::decode(&tx.data).unwrap(),
+            true,
+        )
+        .unwrap();
+        assert_eq!(call._l2Token, usdbc);
+        assert_eq!(call._amount, U256::from(2_000_000u32));
+        assert_eq!(call._minGasLimit, DEPOSIT_MIN_GAS_LIMIT);
+
+        // native currency can not be bridged to a token
+        assert!(get_deposit_tx(&bridge, None, Some(usdbc), &recipient, U256::from(1u32)).is_err());
+    }
+}
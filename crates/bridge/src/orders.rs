@@ -0,0 +1,744 @@
+use std::{future::Future, str::FromStr};
+
+use sqlx::{
+    any::{AnyPoolOptions, AnyRow},
+    AnyPool, Row,
+};
+
+use crate::{
+    status::{OrderState, OrderStatus},
+    BridgeRequest, BridgeResponse, SupportedProviders,
+};
+
+/// Default page size of the account orders
+pub const DEFAULT_ORDERS_LIMIT: u32 = 20;
+/// Maximum page size of the account orders
+pub const MAX_ORDERS_LIMIT: u32 = 100;
+
+/// Typed order store errors, surfaced to the caller; returned as `eyre::Report`s, to be recovered by downcasting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrderError {
+    /// Malformed request, e.g. an invalid tx hash or status filter
+    InvalidRequest(String),
+    /// No order of the account with the id, awaiting its tx hash
+    NotFound(i64),
+    /// The order is not a bridging tx (e.g. an approval), its tx hash can not be attached
+    NotBridging(i64),
+    /// The attached tx was not sent by the account of the order
+    NotOwner,
+}
+
+impl std::fmt::Display for OrderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidRequest(reason) => write!(f, "invalid request: {}", reason),
+            Self::NotFound(id) => write!(
+                f,
+                "order {} not found for account, or its tx hash is already attached",
+                id
+            ),
+            Self::NotBridging(id) => write!(
+                f,
+                "order {} is not a bridging tx, its tx hash can not be attached",
+                id
+            ),
+            Self::NotOwner => write!(f, "tx was not sent by the order account"),
+        }
+    }
+}
+
+impl std::error::Error for OrderError {}
+
+/// Lifecycle of a recorded bridging response; starts as `created`, and is `submitted` once the caller
+/// attaches the source chain tx hash. Terminal states are resolved through the provider order status, once the
+/// tx hash is attached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OrderRecordStatus {
+    Created,
+    Submitted,
+    Filled,
+    Expired,
+    Refunded,
+    Cancelled,
+}
+
+impl std::fmt::Display for OrderRecordStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Created => write!(f, "created"),
+            Self::Submitted => write!(f, "submitted"),
+            Self::Filled => write!(f, "filled"),
+            Self::Expired => write!(f, "expired"),
+            Self::Refunded => write!(f, "refunded"),
+            Self::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+impl FromStr for OrderRecordStatus {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "created" => Ok(Self::Created),
+            "submitted" => Ok(Self::Submitted),
+            "filled" => Ok(Self::Filled),
+            "expired" => Ok(Self::Expired),
+            "refunded" => Ok(Self::Refunded),
+            "cancelled" => Ok(Self::Cancelled),
+            _ => Err(format!("unsupported order status: {}", value)),
+        }
+    }
+}
+
+impl OrderRecordStatus {
+    /// Whether the order has been resolved, and its status is no longer updated
+    pub fn is_terminal(&self) -> bool {
+        !matches!(self, Self::Created | Self::Submitted)
+    }
+}
+
+impl From<OrderState> for OrderRecordStatus {
+    fn from(state: OrderState) -> Self {
+        match state {
+            // deposited on the source chain, i.e. the tx has been submitted
+            OrderState::Pending => Self::Submitted,
+            OrderState::Filled => Self::Filled,
+            OrderState::Expired => Self::Expired,
+            OrderState::Refunded => Self::Refunded,
+            OrderState::Cancelled => Self::Cancelled,
+        }
+    }
+}
+
+/// Bridging response returned to an account, as recorded by the store
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderRecord {
+    pub id: i64,
+    /// Source chain caller
+    pub account: String,
+    pub provider: SupportedProviders,
+    /// Returned bridge action; e.g. `BridgingTx`, or `BridgeApprovalTx` if an approval was required
+    pub action: String,
+    pub src_chain_id: u32,
+    pub src_token: String,
+    /// Source amount, in the token base units
+    pub src_amount: String,
+    pub dest_chain_id: u32,
+    pub dest_token: String,
+    pub dest_recipient: String,
+    /// Requested destination amount, in the token base units
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dest_amount: Option<String>,
+    /// Provider order id, if known before the tx is executed (e.g. DLN order ids)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_id: Option<String>,
+    /// Source chain tx hash, attached by the caller once submitted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<String>,
+    pub status: OrderRecordStatus,
+    /// Unix timestamp (in seconds) of the bridging response
+    pub created_at: u64,
+    /// Unix timestamp (in seconds) of the last status update
+    pub updated_at: u64,
+}
+
+impl TryFrom<AnyRow> for OrderRecord {
+    type Error = eyre::Report;
+
+    fn try_from(row: AnyRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            account: row.try_get("account")?,
+            provider: row
+                .try_get::<String, _>("provider")?
+                .parse()
+                .map_err(|e: String| eyre::eyre!(e))?,
+            action: row.try_get("action")?,
+            src_chain_id: row.try_get::<i64, _>("src_chain_id")?.try_into()?,
+            src_token: row.try_get("src_token")?,
+            src_amount: row.try_get("src_amount")?,
+            dest_chain_id: row.try_get::<i64, _>("dest_chain_id")?.try_into()?,
+            dest_token: row.try_get("dest_token")?,
+            dest_recipient: row.try_get("dest_recipient")?,
+            dest_amount: row.try_get("dest_amount")?,
+            order_id: row.try_get("order_id")?,
+            tx_hash: row.try_get("tx_hash")?,
+            status: row
+                .try_get::<String, _>("status")?
+                .parse()
+                .map_err(|e: String| eyre::eyre!(e))?,
+            created_at: row.try_get::<i64, _>("created_at")?.try_into()?,
+            updated_at: row.try_get::<i64, _>("updated_at")?.try_into()?,
+        })
+    }
+}
+
+/// Account orders query; paginated from the most recent order, by the `before` record id cursor
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrdersQuery {
+    /// Comma-separated statuses to filter by; e.g. `created,submitted`
+    pub status: Option<String>,
+    /// Page size; defaults to `DEFAULT_ORDERS_LIMIT`, capped at `MAX_ORDERS_LIMIT`
+    pub limit: Option<u32>,
+    /// Record id cursor; only orders recorded before it are returned
+    pub before: Option<i64>,
+}
+
+impl OrdersQuery {
+    fn statuses(&self) -> eyre::Result<Vec<OrderRecordStatus>> {
+        self.status
+            .as_deref()
+            .map(|status| {
+                status
+                    .split(',')
+                    .map(|s| {
+                        s.trim()
+                            .parse()
+                            .map_err(|e: String| OrderError::InvalidRequest(e).into())
+                    })
+                    .collect()
+            })
+            .unwrap_or(Ok(vec![]))
+    }
+
+    fn limit(&self) -> u32 {
+        self.limit
+            .unwrap_or(DEFAULT_ORDERS_LIMIT)
+            .clamp(1, MAX_ORDERS_LIMIT)
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrdersPage {
+    pub orders: Vec<OrderRecord>,
+    /// Cursor of the next page, if there are more orders
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<i64>,
+}
+
+/// Persists the bridging responses returned to accounts.
+/// Backed by SQLite (e.g. `sqlite://tsumori.db?mode=rwc`, or `sqlite::memory:` for tests) for single-node
+/// deployments, or Postgres (`postgres://...`) in production.
+#[derive(Debug, Clone)]
+pub struct OrderStore {
+    pool: AnyPool,
+}
+
+impl OrderStore {
+    /// Connects to the database at `url`, creating the orders table if it does not exist.
+    pub async fn connect(url: &str) -> eyre::Result<Self> {
+        sqlx::any::install_default_drivers();
+
+        let is_postgres = url.starts_with("postgres:") || url.starts_with("postgresql:");
+        // each in-memory sqlite connection has its own database
+        let max_connections = if url.contains(":memory:") { 1 } else { 10 };
+        let pool = AnyPoolOptions::new()
+            .max_connections(max_connections)
+            .connect(url)
+            .await?;
+
+        let id_column = if is_postgres {
+            "id BIGSERIAL PRIMARY KEY"
+        } else {
+            "id INTEGER PRIMARY KEY AUTOINCREMENT"
+        };
+        sqlx::query(&format!(
+            "CREATE TABLE IF NOT EXISTS bridge_orders (
+                {id_column},
+                account TEXT NOT NULL,
+                provider TEXT NOT NULL,
+                action TEXT NOT NULL,
+                src_chain_id BIGINT NOT NULL,
+                src_token TEXT NOT NULL,
+                src_amount TEXT NOT NULL,
+                dest_chain_id BIGINT NOT NULL,
+                dest_token TEXT NOT NULL,
+                dest_recipient TEXT NOT NULL,
+                dest_amount TEXT,
+                order_id TEXT,
+                tx_hash TEXT,
+                status TEXT NOT NULL,
+                created_at BIGINT NOT NULL,
+                updated_at BIGINT NOT NULL
+            )"
+        ))
+        .execute(&pool)
+        .await?;
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS bridge_orders_account_idx ON bridge_orders (account, id)",
+        )
+        .execute(&pool)
+        .await?;
+
+        Ok(Self { pool })
+    }
+
+    /// Records a bridging response returned for `request`; returns the record id, with which the caller
+    /// attaches the submitted tx hash.
+    pub async fn record(
+        &self,
+        request: &BridgeRequest,
+        response: &BridgeResponse,
+    ) -> eyre::Result<i64> {
+        let now = unix_now();
+        let id = sqlx::query(
+            "INSERT INTO bridge_orders (account, provider, action, src_chain_id, src_token, src_amount,
+                dest_chain_id, dest_token, dest_recipient, dest_amount, order_id, status, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+            RETURNING id",
+        )
+        .bind(normalize_account(&request.src_caller))
+        .bind(response.provider.to_string())
+        .bind(response.bridge_action.kind())
+        .bind(i64::from(request.src_chain_id))
+        .bind(&request.src_token)
+        .bind(request.src_amount.to_string())
+        .bind(i64::from(request.dest_chain_id))
+        .bind(&request.dest_token)
+        .bind(&request.dest_recipient)
+        .bind(request.dest_amount.map(|amount| amount.to_string()))
+        .bind(response.order_id.clone())
+        .bind(OrderRecordStatus::Created.to_string())
+        .bind(now)
+        .bind(now)
+        .fetch_one(&self.pool)
+        .await?
+        .try_get("id")?;
+        Ok(id)
+    }
+
+    /// Attaches the submitted source chain tx hash to the account's order record, if it is a bridging tx, not yet
+    /// resolved, and has no tx hash attached. The tx must have been sent by the account, as proven by `get_sender`
+    /// (e.g. `BridgeService::get_tx_sender`), returning the sender of a tx hash on the order source chain; `None`
+    /// if the tx is not found.
+    pub async fn attach_tx_hash<F, Fut>(
+        &self,
+        account: &str,
+        id: i64,
+        tx_hash: &str,
+        get_sender: F,
+    ) -> eyre::Result<()>
+    where
+        F: FnOnce(u32, String) -> Fut,
+        Fut: Future<Output = eyre::Result<Option<String>>>,
+    {
+        let tx_hash = tx_hash.trim();
+        if tx_hash.is_empty() {
+            return Err(OrderError::InvalidRequest("tx hash is required".to_string()).into());
+        }
+        if tx_hash.starts_with("0x") {
+            tx_hash
+                .parse::<alloy::primitives::B256>()
+                .map_err(|e| OrderError::InvalidRequest(format!("invalid tx hash: {}", e)))?;
+        }
+
+        let order = sqlx::query(
+            "SELECT * FROM bridge_orders WHERE id = $1 AND account = $2 AND tx_hash IS NULL",
+        )
+        .bind(id)
+        .bind(normalize_account(account))
+        .fetch_optional(&self.pool)
+        .await?
+        .map(OrderRecord::try_from)
+        .transpose()?
+        .filter(|order| !order.status.is_terminal())
+        .ok_or(OrderError::NotFound(id))?;
+        // approval and signature responses are followed by a bridging tx response, recorded separately
+        if !matches!(order.action.as_str(), "BridgingTx" | "SolanaTx") {
+            return Err(OrderError::NotBridging(id).into());
+        }
+        let src_chain_id = order.src_chain_id;
+        let sender = get_sender(src_chain_id, tx_hash.to_string())
+            .await?
+            .ok_or_else(|| {
+                OrderError::InvalidRequest("tx not found on the source chain".to_string())
+            })?;
+        if normalize_account(&sender) != normalize_account(account) {
+            return Err(OrderError::NotOwner.into());
+        }
+
+        // the status is not downgraded, if resolved meanwhile
+        let result = sqlx::query(
+            "UPDATE bridge_orders SET tx_hash = $1, status = CASE WHEN status = $2 THEN $3 ELSE status END,
+                updated_at = $4
+            WHERE id = $5 AND account = $6 AND tx_hash IS NULL",
+        )
+        .bind(tx_hash)
+        .bind(OrderRecordStatus::Created.to_string())
+        .bind(OrderRecordStatus::Submitted.to_string())
+        .bind(unix_now())
+        .bind(id)
+        .bind(normalize_account(account))
+        .execute(&self.pool)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(OrderError::NotFound(id).into());
+        }
+        Ok(())
+    }
+
+    /// Updates the records of a resolved provider order, matched by its order id or deposit tx hash. Records
+    /// awaiting their tx hash are left `created`, for the caller to attach it; e.g. if the DLN order id returned
+    /// with the bridging tx is polled before.
+    pub async fn update_status(&self, status: &OrderStatus) -> eyre::Result<()> {
+        sqlx::query(
+            "UPDATE bridge_orders SET status = $1, updated_at = $2
+            WHERE provider = $3 AND (order_id = $4 OR tx_hash = $5) AND (tx_hash IS NOT NULL OR status <> $6)",
+        )
+        .bind(OrderRecordStatus::from(status.state).to_string())
+        .bind(unix_now())
+        .bind(status.provider.to_string())
+        .bind(&status.order_id)
+        .bind(status.deposit_tx_hash.clone())
+        .bind(OrderRecordStatus::Created.to_string())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns a page of the account's orders, most recent first.
+    pub async fn get_account_orders(
+        &self,
+        account: &str,
+        query: &OrdersQuery,
+    ) -> eyre::Result<OrdersPage> {
+        let statuses = query.statuses()?;
+        let limit = query.limit();
+
+        let mut sql = "SELECT * FROM bridge_orders WHERE account = $1".to_string();
+        let mut param = 1;
+        if query.before.is_some() {
+            param += 1;
+            sql.push_str(&format!(" AND id < ${}", param));
+        }
+        if !statuses.is_empty() {
+            let placeholders = (0..statuses.len())
+                .map(|i| format!("${}", param + 1 + i))
+                .collect::<Vec<_>>()
+                .join(", ");
+            param += statuses.len();
+            sql.push_str(&format!(" AND status IN ({})", placeholders));
+        }
+        // an extra row is fetched, to determine whether there is a next page
+        sql.push_str(&format!(" ORDER BY id DESC LIMIT ${}", param + 1));
+
+        let mut statement = sqlx::query(&sql).bind(normalize_account(account));
+        if let Some(before) = query.before {
+            statement = statement.bind(before);
+        }
+        for status in statuses {
+            statement = statement.bind(status.to_string());
+        }
+        let rows = statement
+            .bind(i64::from(limit) + 1)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut orders = rows
+            .into_iter()
+            .map(OrderRecord::try_from)
+            .collect::<eyre::Result<Vec<_>>>()?;
+        let next = if orders.len() > limit as usize {
+            orders.truncate(limit as usize);
+            orders.last().map(|order| order.id)
+        } else {
+            None
+        };
+        Ok(OrdersPage { orders, next })
+    }
+}
+
+/// EVM addresses are case-insensitive; other (e.g. Solana base58) addresses are kept as is.
+fn normalize_account(account: &str) -> String {
+    if account.starts_with("0x") {
+        account.to_lowercase()
+    } else {
+        account.to_string()
+    }
+}
+
+fn unix_now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACCOUNT: &str = "0x000007357111E4789005d4eBfF401a18D99770cE";
+    const OTHER_ACCOUNT: &str = "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913";
+    const TX_HASH: &str = "0x8f1c7e7f0f0a1f9b1f3c5b0c9c1e1d2a6b8b5c4d3e2f1a0b9c8d7e6f5a4b3c2d";
+
+    fn request() -> BridgeRequest {
+        BridgeRequest {
+            src_chain_id: utils::Chain::Base as u32,
+            src_token: "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913".into(), // USDC Base
+            src_caller: ACCOUNT.into(),
+            src_amount: crate::U256::from(2_000_000u32), // 2 USDC
+            src_chain_token_in_sender_permit: None,
+            dest_chain_id: utils::Chain::Arbitrum as u32,
+            dest_token: "0xaf88d065e77c8cC2239327C5EDb3A432268e5831".into(), // USDC Arbitrum
+            dest_recipient: ACCOUNT.into(),
+            dest_amount: None,
+            permit_deadline: None,
+            calldata: None,
+            strategy: None,
+            simulate: false,
+            simulate_balance: false,
+            assume_approved: false,
+        }
+    }
+
+    fn order_status(state: OrderState) -> OrderStatus {
+        OrderStatus {
+            provider: SupportedProviders::DeBridge,
+            order_id: "0x01".into(),
+            state,
+            deposit_tx_hash: None,
+            fill_tx_hash: None,
+            created_at: None,
+            filled_at: None,
+        }
+    }
+
+    fn order_error(result: eyre::Result<()>) -> OrderError {
+        result
+            .unwrap_err()
+            .downcast_ref::<OrderError>()
+            .cloned()
+            .unwrap()
+    }
+
+    fn response(order_id: Option<&str>) -> BridgeResponse {
+        BridgeResponse::new(
+            SupportedProviders::DeBridge,
+            crate::BridgeAction::BridgingTx(crate::TxData {
+                data: "0x".into(),
+                to: "0xeF4fB24aD0916217251F553c0596F8Edc630EB66".into(),
+                value: "0".into(),
+                ..Default::default()
+            }),
+        )
+        .with_order_id(order_id.map(Into::into))
+    }
+
+    /// Returns `sender` as the sender of any tx on the source chain
+    fn sent_by(
+        sender: Option<&'static str>,
+    ) -> impl FnOnce(u32, String) -> std::future::Ready<eyre::Result<Option<String>>> {
+        move |chain_id, _| {
+            assert_eq!(chain_id, utils::Chain::Base as u32);
+            std::future::ready(Ok(sender.map(Into::into)))
+        }
+    }
+
+    #[tokio::test]
+    async fn record_and_attach_tx_hash() {
+        let store = OrderStore::connect("sqlite::memory:").await.unwrap();
+        let id = store
+            .record(&request(), &response(Some("0x01")))
+            .await
+            .unwrap();
+
+        let tx_hash = TX_HASH;
+        // the tx must be found, and sent by the account
+        assert!(matches!(
+            order_error(
+                store
+                    .attach_tx_hash(ACCOUNT, id, tx_hash, sent_by(None))
+                    .await
+            ),
+            OrderError::InvalidRequest(_)
+        ));
+        assert_eq!(
+            order_error(
+                store
+                    .attach_tx_hash(ACCOUNT, id, tx_hash, sent_by(Some(OTHER_ACCOUNT)))
+                    .await
+            ),
+            OrderError::NotOwner
+        );
+        assert!(matches!(
+            order_error(
+                store
+                    .attach_tx_hash(ACCOUNT, id, "0x01", sent_by(Some(ACCOUNT)))
+                    .await
+            ),
+            OrderError::InvalidRequest(_)
+        ));
+        // the account is matched case-insensitively
+        store
+            .attach_tx_hash(&ACCOUNT.to_lowercase(), id, tx_hash, sent_by(Some(ACCOUNT)))
+            .await
+            .unwrap();
+        // tx hash can only be attached once, by the account
+        assert_eq!(
+            order_error(
+                store
+                    .attach_tx_hash(ACCOUNT, id, tx_hash, sent_by(Some(ACCOUNT)))
+                    .await
+            ),
+            OrderError::NotFound(id)
+        );
+        assert_eq!(
+            order_error(
+                store
+                    .attach_tx_hash(OTHER_ACCOUNT, id, tx_hash, sent_by(Some(OTHER_ACCOUNT)))
+                    .await
+            ),
+            OrderError::NotFound(id)
+        );
+
+        let page = store
+            .get_account_orders(ACCOUNT, &OrdersQuery::default())
+            .await
+            .unwrap();
+        assert_eq!(page.orders.len(), 1);
+        assert_eq!(page.next, None);
+        let order = &page.orders[0];
+        assert_eq!(order.id, id);
+        assert_eq!(order.account, ACCOUNT.to_lowercase());
+        assert_eq!(order.provider, SupportedProviders::DeBridge);
+        assert_eq!(order.action, "BridgingTx");
+        assert_eq!(order.src_amount, "2000000");
+        assert_eq!(order.order_id.as_deref(), Some("0x01"));
+        assert_eq!(order.tx_hash.as_deref(), Some(tx_hash));
+        assert_eq!(order.status, OrderRecordStatus::Submitted);
+
+        store
+            .update_status(&order_status(OrderState::Filled))
+            .await
+            .unwrap();
+        let page = store
+            .get_account_orders(ACCOUNT, &OrdersQuery::default())
+            .await
+            .unwrap();
+        assert_eq!(page.orders[0].status, OrderRecordStatus::Filled);
+    }
+
+    #[tokio::test]
+    async fn status_polled_before_attach() {
+        let store = OrderStore::connect("sqlite::memory:").await.unwrap();
+        let id = store
+            .record(&request(), &response(Some("0x01")))
+            .await
+            .unwrap();
+        let status = |store: OrderStore| async move {
+            store
+                .get_account_orders(ACCOUNT, &OrdersQuery::default())
+                .await
+                .unwrap()
+                .orders[0]
+                .status
+        };
+
+        // the order is left created until its tx hash is attached
+        store
+            .update_status(&order_status(OrderState::Pending))
+            .await
+            .unwrap();
+        assert_eq!(status(store.clone()).await, OrderRecordStatus::Created);
+        store
+            .attach_tx_hash(ACCOUNT, id, TX_HASH, sent_by(Some(ACCOUNT)))
+            .await
+            .unwrap();
+        assert_eq!(status(store.clone()).await, OrderRecordStatus::Submitted);
+        store
+            .update_status(&order_status(OrderState::Filled))
+            .await
+            .unwrap();
+        assert_eq!(status(store.clone()).await, OrderRecordStatus::Filled);
+    }
+
+    #[tokio::test]
+    async fn attach_to_non_bridging_order_rejected() {
+        let store = OrderStore::connect("sqlite::memory:").await.unwrap();
+        let approval = BridgeResponse::new(
+            SupportedProviders::DeBridge,
+            crate::BridgeAction::BridgeApprovalTx(
+                SupportedProviders::DeBridge,
+                crate::TxData::default(),
+            ),
+        );
+        let id = store.record(&request(), &approval).await.unwrap();
+        assert_eq!(
+            order_error(
+                store
+                    .attach_tx_hash(ACCOUNT, id, TX_HASH, sent_by(Some(ACCOUNT)))
+                    .await
+            ),
+            OrderError::NotBridging(id)
+        );
+    }
+
+    #[tokio::test]
+    async fn account_orders_pagination_and_status() {
+        let store = OrderStore::connect("sqlite::memory:").await.unwrap();
+        let mut ids = vec![];
+        for _ in 0..5 {
+            ids.push(store.record(&request(), &response(None)).await.unwrap());
+        }
+        store
+            .attach_tx_hash(ACCOUNT, ids[1], TX_HASH, sent_by(Some(ACCOUNT)))
+            .await
+            .unwrap();
+
+        let query = OrdersQuery {
+            status: None,
+            limit: Some(2),
+            before: None,
+        };
+        let page = store.get_account_orders(ACCOUNT, &query).await.unwrap();
+        assert_eq!(
+            page.orders.iter().map(|o| o.id).collect::<Vec<_>>(),
+            vec![ids[4], ids[3]]
+        );
+        assert_eq!(page.next, Some(ids[3]));
+
+        let query = OrdersQuery {
+            before: page.next,
+            ..query
+        };
+        let page = store.get_account_orders(ACCOUNT, &query).await.unwrap();
+        assert_eq!(
+            page.orders.iter().map(|o| o.id).collect::<Vec<_>>(),
+            vec![ids[2], ids[1]]
+        );
+
+        let query = OrdersQuery {
+            status: Some("submitted,filled".into()),
+            limit: None,
+            before: None,
+        };
+        let page = store.get_account_orders(ACCOUNT, &query).await.unwrap();
+        assert_eq!(page.orders.len(), 1);
+        assert_eq!(page.orders[0].id, ids[1]);
+        assert_eq!(page.next, None);
+
+        let query = OrdersQuery {
+            status: Some("unknown".into()),
+            limit: None,
+            before: None,
+        };
+        let err = store.get_account_orders(ACCOUNT, &query).await.unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<OrderError>(),
+            Some(OrderError::InvalidRequest(_))
+        ));
+
+        // other accounts' orders are not returned
+        let page = store
+            .get_account_orders(OTHER_ACCOUNT, &OrdersQuery::default())
+            .await
+            .unwrap();
+        assert!(page.orders.is_empty());
+    }
+}
@@ -0,0 +1,452 @@
+use crate::{TxData, U256};
+use std::str::FromStr;
+
+use alloy::primitives::Address;
+use eyre::Result;
+
+/// Relay represents the native currency of EVM chains as the zero address
+const RELAY_NATIVE_TOKEN: &str = "0x0000000000000000000000000000000000000000";
+
+/// Relay requires a user to quote; placeholder used for quotes without a recipient
+const QUOTE_PLACEHOLDER_USER: &str = "0x000000000000000000000000000000000000dEaD";
+
+/// Validity of a quote; Relay quotes are priced at the current relayer gas costs
+const QUOTE_VALIDITY_SECS: u64 = 30;
+
+fn get_relay_token(token: &str) -> &str {
+    if utils::is_native_token(token) {
+        RELAY_NATIVE_TOKEN
+    } else {
+        token
+    }
+}
+
+/// Relay uses its own chain ids for non-evm chains; only evm routes are supported.
+fn check_route(src_chain_id: u32, dest_chain_id: u32) -> Result<()> {
+    for chain_id in [src_chain_id, dest_chain_id] {
+        match utils::Chain::try_from(chain_id) {
+            Ok(utils::Chain::Solana) | Err(_) => {
+                return Err(crate::BridgeError::UnsupportedChain(chain_id).into())
+            }
+            Ok(_) => {}
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, serde::Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteParams<'a> {
+    pub user: &'a str,
+    pub recipient: &'a str,
+    pub origin_chain_id: u32,
+    pub destination_chain_id: u32,
+    pub origin_currency: &'a str,
+    pub destination_currency: &'a str,
+    /// Input amount for `EXACT_INPUT` trades
+    pub amount: String,
+    pub trade_type: &'a str,
+}
+
+impl<'a> From<&'a crate::BridgeRequest> for QuoteParams<'a> {
+    fn from(request: &'a crate::BridgeRequest) -> Self {
+        Self {
+            user: &request.src_caller,
+            recipient: &request.dest_recipient,
+            origin_chain_id: request.src_chain_id,
+            destination_chain_id: request.dest_chain_id,
+            origin_currency: get_relay_token(&request.src_token),
+            destination_currency: get_relay_token(&request.dest_token),
+            amount: request.src_amount.to_string(),
+            trade_type: "EXACT_INPUT",
+        }
+    }
+}
+
+impl<'a> From<&'a crate::QuoteRequest> for QuoteParams<'a> {
+    fn from(request: &'a crate::QuoteRequest) -> Self {
+        let recipient = request
+            .dest_recipient
+            .as_deref()
+            .unwrap_or(QUOTE_PLACEHOLDER_USER);
+        Self {
+            user: recipient,
+            recipient,
+            origin_chain_id: request.src_chain_id,
+            destination_chain_id: request.dest_chain_id,
+            origin_currency: get_relay_token(&request.src_token),
+            destination_currency: get_relay_token(&request.dest_token),
+            amount: request.src_amount.to_string(),
+            trade_type: "EXACT_INPUT",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StepKind {
+    Transaction,
+    Signature,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Step {
+    /// Step id, e.g. `approve`, `deposit` or `authorize`
+    pub id: String,
+    pub kind: StepKind,
+    pub items: Vec<StepItem>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepItem {
+    pub status: String,
+    /// Tx data for transaction steps; signature payload for signature steps
+    pub data: serde_json::Value,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepTxData {
+    pub to: String,
+    pub data: String,
+    pub value: String,
+    pub chain_id: u32,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Currency {
+    pub address: String,
+    pub chain_id: u32,
+    pub decimals: u8,
+    pub symbol: String,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurrencyAmount {
+    pub currency: Currency,
+    pub amount: String,
+}
+
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Fees {
+    /// Destination chain gas paid to the relayer
+    pub relayer_gas: Option<CurrencyAmount>,
+    /// Relayer margin for the capital provided
+    pub relayer_service: Option<CurrencyAmount>,
+    /// Integrator fees
+    pub app: Option<CurrencyAmount>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Details {
+    pub currency_in: CurrencyAmount,
+    pub currency_out: CurrencyAmount,
+    /// Estimated fill time in seconds
+    pub time_estimate: Option<u64>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteResponse {
+    pub steps: Vec<Step>,
+    #[serde(default)]
+    pub fees: Fees,
+    pub details: Details,
+}
+
+impl QuoteResponse {
+    pub fn output_amount(&self) -> Result<U256> {
+        Ok(U256::from_str(&self.details.currency_out.amount)?)
+    }
+
+    /// Maps the first incomplete step onto the bridge action to be executed by the caller;
+    /// once an approval has been executed, a new quote returns the deposit step.
+    /// If `assume_approved`, the approval step is skipped for the deposit step following it.
+    /// Signature steps are posted back to Relay by its sdk, which the bridging actions do not support.
+    pub fn into_action(
+        self,
+        src_chain_id: u32,
+        assume_approved: bool,
+    ) -> Result<crate::BridgeAction> {
+        let step = self
+            .steps
+            .into_iter()
+            .filter(|step| !(assume_approved && step.id == "approve"))
+            .find(|step| step.items.iter().any(|item| item.status == "incomplete"))
+            .ok_or_else(|| eyre::eyre!("Relay: no steps to execute"))?;
+        if step.kind == StepKind::Signature {
+            return Err(crate::BridgeError::ProviderRejected(format!(
+                "Relay: signature step `{}` is not supported",
+                step.id
+            ))
+            .into());
+        }
+        let item = step
+            .items
+            .into_iter()
+            .find(|item| item.status == "incomplete")
+            .ok_or_else(|| eyre::eyre!("Relay: no steps to execute"))?;
+        let tx: StepTxData = serde_json::from_value(item.data)?;
+        if tx.chain_id != src_chain_id {
+            return Err(eyre::eyre!(
+                "Relay: step `{}` is not executed on the source chain",
+                step.id
+            ));
+        }
+        let tx = TxData {
+            data: tx.data,
+            to: tx.to,
+            value: tx.value,
+            ..Default::default()
+        };
+        Ok(match step.id.as_str() {
+            "approve" => {
+                crate::BridgeAction::BridgeApprovalTx(crate::SupportedProviders::Relay, tx)
+            }
+            _ => crate::BridgeAction::BridgingTx(tx),
+        })
+    }
+
+    pub fn into_quote(self, now: u64) -> Result<crate::BridgeQuote> {
+        let input_amount = U256::from_str(&self.details.currency_in.amount)?;
+        let output_amount = self.output_amount()?;
+        let fees = [
+            ("relayerGasFee", self.fees.relayer_gas),
+            ("relayerServiceFee", self.fees.relayer_service),
+            ("appFee", self.fees.app),
+        ]
+        .into_iter()
+        .filter_map(|(name, fee)| {
+            let fee = fee?;
+            // negative fees (relayer rewards) are not reported
+            let amount = U256::from_str(&fee.amount).ok()?;
+            Some(crate::QuoteFee {
+                name: name.to_string(),
+                token: fee.currency.address,
+                amount,
+            })
+        })
+        .collect();
+
+        Ok(crate::BridgeQuote::new(
+            crate::SupportedProviders::Relay,
+            input_amount,
+            output_amount,
+            fees,
+            self.details.time_estimate.unwrap_or_default(),
+            now.saturating_add(QUOTE_VALIDITY_SECS),
+        ))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RelayBridge {
+    client: reqwest::Client,
+}
+
+impl Default for RelayBridge {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RelayBridge {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+        }
+    }
+
+    pub async fn get_relay_quote(&self, params: &QuoteParams<'_>) -> Result<QuoteResponse> {
+        let response = self
+            .client
+            .post("https://api.relay.link/quote")
+            .json(&params)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(crate::BridgeError::ProviderRejected(format!(
+                "Relay: failed to get quote: {}",
+                response.text().await?
+            ))
+            .into());
+        }
+        Ok(response.json().await?)
+    }
+}
+
+impl crate::BridgeProvider for RelayBridge {
+    async fn get_bridging_data(
+        &self,
+        request: &crate::BridgeRequest,
+    ) -> eyre::Result<crate::BridgeResponse> {
+        check_route(request.src_chain_id, request.dest_chain_id)?;
+        if request.calldata.is_some() {
+            return Err(crate::BridgeError::ProviderRejected(
+                "Relay: destination calldata is not supported".to_string(),
+            )
+            .into());
+        }
+        if request.src_chain_token_in_sender_permit.is_some() {
+            return Err(crate::BridgeError::ProviderRejected(
+                "Relay: permit signatures are not supported".to_string(),
+            )
+            .into());
+        }
+        // validate addresses before requesting a quote
+        Address::from_str(&request.src_caller)?;
+        Address::from_str(&request.dest_recipient)?;
+
+        let response = self.get_relay_quote(&request.into()).await?;
+        if let Some(dest_amount) = request.dest_amount {
+            if dest_amount > response.output_amount()? {
+                return Err(eyre::eyre!(
+                    "requested dest amount is greater than output amount"
+                ));
+            }
+        }
+
+        // the approval step is only returned by Relay if the caller's allowance is insufficient
+        let estimated_fill_time_secs = response.details.time_estimate;
+        Ok(crate::BridgeResponse::new(
+            crate::SupportedProviders::Relay,
+            response.into_action(request.src_chain_id, request.assume_approved)?,
+        )
+        .with_estimated_fill_time_secs(estimated_fill_time_secs))
+    }
+
+    async fn get_quote(&self, request: &crate::QuoteRequest) -> eyre::Result<crate::BridgeQuote> {
+        check_route(request.src_chain_id, request.dest_chain_id)?;
+        if request.calldata.is_some() {
+            return Err(crate::BridgeError::ProviderRejected(
+                "Relay: destination calldata is not supported".to_string(),
+            )
+            .into());
+        }
+        let response = self.get_relay_quote(&request.into()).await?;
+        response.into_quote(utils::get_unix_timestamp())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote_response(steps: serde_json::Value) -> QuoteResponse {
+        let usdc_base = serde_json::json!({
+            "address": "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913", "chainId": 8453,
+            "decimals": 6, "symbol": "USDC", "name": "USD Coin"
+        });
+        let usdc_arbitrum = serde_json::json!({
+            "address": "0xaf88d065e77c8cc2239327c5edb3a432268e5831", "chainId": 42161,
+            "decimals": 6, "symbol": "USDC", "name": "USD Coin"
+        });
+        serde_json::from_value(serde_json::json!({
+            "steps": steps,
+            "fees": {
+                "gas": { "currency": { "address": RELAY_NATIVE_TOKEN, "chainId": 8453, "decimals": 18, "symbol": "ETH" }, "amount": "3000000000000" },
+                "relayerGas": { "currency": usdc_base, "amount": "1200" },
+                "relayerService": { "currency": usdc_base, "amount": "-5" },
+                "app": { "currency": usdc_base, "amount": "0" }
+            },
+            "details": {
+                "operation": "bridge",
+                "timeEstimate": 4,
+                "currencyIn": { "currency": usdc_base, "amount": "2000000" },
+                "currencyOut": { "currency": usdc_arbitrum, "amount": "1998805" }
+            }
+        }))
+        .unwrap()
+    }
+
+    fn tx_step(id: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "action": "Confirm transaction in your wallet",
+            "kind": "transaction",
+            "requestId": "0x01",
+            "items": [{
+                "status": "incomplete",
+                "data": {
+                    "from": "0x000007357111E4789005d4eBfF401a18D99770cE",
+                    "to": "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913",
+                    "data": "0x095ea7b3",
+                    "value": "0",
+                    "chainId": 8453
+                }
+            }]
+        })
+    }
+
+    #[test]
+    fn steps_into_action() {
+        let response = quote_response(serde_json::json!([tx_step("approve"), tx_step("deposit")]));
+        assert!(matches!(
+            response.into_action(8453, false).unwrap(),
+            crate::BridgeAction::BridgeApprovalTx(crate::SupportedProviders::Relay, _)
+        ));
+        // the deposit step is returned as is, if the approval is assumed
+        let response = quote_response(serde_json::json!([tx_step("approve"), tx_step("deposit")]));
+        assert!(matches!(
+            response.into_action(8453, true).unwrap(),
+            crate::BridgeAction::BridgingTx(_)
+        ));
+
+        let response = quote_response(serde_json::json!([tx_step("deposit")]));
+        match response.into_action(8453, false).unwrap() {
+            crate::BridgeAction::BridgingTx(tx) => {
+                assert_eq!(tx.to, "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913");
+                assert_eq!(tx.value, "0");
+            }
+            action => panic!("unexpected action: {:?}", action),
+        }
+
+        // steps on another chain than the source chain can not be executed
+        let response = quote_response(serde_json::json!([tx_step("deposit")]));
+        assert!(response.into_action(42161, false).is_err());
+
+        let response = quote_response(serde_json::json!([{
+            "id": "authorize",
+            "kind": "signature",
+            "items": [{ "status": "incomplete", "data": { "sign": {}, "post": {} } }]
+        }]));
+        let err = response.into_action(8453, false).unwrap_err();
+        assert!(matches!(
+            crate::BridgeError::from_report(&err),
+            crate::BridgeError::ProviderRejected(_)
+        ));
+    }
+
+    #[test]
+    fn response_into_quote() {
+        let quote = quote_response(serde_json::json!([tx_step("deposit")]))
+            .into_quote(1_700_000_000)
+            .unwrap();
+        assert_eq!(quote.provider, crate::SupportedProviders::Relay);
+        assert_eq!(quote.input_amount, U256::from(2_000_000u32));
+        assert_eq!(quote.output_amount, U256::from(1_998_805u32));
+        assert_eq!(quote.estimated_fill_time_secs, 4);
+        assert_eq!(quote.expires_at, 1_700_000_000 + QUOTE_VALIDITY_SECS);
+        // origin gas is paid by the caller, rewards are not reported
+        let fees: Vec<_> = quote.fees.iter().map(|fee| fee.name.as_str()).collect();
+        assert_eq!(fees, ["relayerGasFee", "appFee"]);
+        assert_eq!(quote.fees[0].amount, U256::from(1_200u32));
+    }
+
+    #[test]
+    fn route() {
+        assert!(check_route(utils::Chain::Base as u32, utils::Chain::Arbitrum as u32).is_ok());
+        let err =
+            check_route(utils::Chain::Solana as u32, utils::Chain::Arbitrum as u32).unwrap_err();
+        assert_eq!(
+            crate::BridgeError::from_report(&err),
+            crate::BridgeError::UnsupportedChain(utils::Chain::Solana as u32)
+        );
+    }
+}
@@ -0,0 +1,207 @@
+use std::str::FromStr;
+
+use alloy::{
+    primitives::Bytes,
+    sol_types::{Panic, SolError},
+    transports::TransportError,
+};
+
+/// Decodes revert data as a custom error; returns the error name and its decoded arguments
+pub(crate) type ErrorDecoder = fn(&[u8]) -> Option<(&'static str, String)>;
+
+pub(crate) fn decode_error<E: SolError + std::fmt::Debug>(
+    data: &[u8],
+) -> Option<(&'static str, String)> {
+    let error = E::abi_decode(data, true).ok()?;
+    let name = E::SIGNATURE.split('(').next().unwrap_or(E::SIGNATURE);
+    Some((name, format!("{:?}", error)))
+}
+
+/// Contracts whose custom errors are decoded, with their decoders
+const CONTRACT_ERRORS: &[(&str, &[ErrorDecoder])] = &[
+    ("SpokePool", crate::across::SPOKE_POOL_ERRORS),
+    ("DlnSource", crate::debridge::DLN_SOURCE_ERRORS),
+];
+
+/// Decoded execution revert of a tx
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevertError {
+    /// Stable error code; `<Contract>.<Error>` for the known custom errors (e.g. `SpokePool.InvalidFillDeadline`),
+    /// otherwise `revert` (`Error(string)`), `panic`, `customError`, `invalidRevertData`, or `executionError`
+    /// if the node returned no revert data
+    pub code: String,
+    /// Decoded reason, including the error arguments
+    pub reason: String,
+}
+
+impl std::fmt::Display for RevertError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.reason)
+    }
+}
+
+impl RevertError {
+    fn new(code: &str, reason: String) -> Self {
+        Self {
+            code: code.to_string(),
+            reason,
+        }
+    }
+
+    /// Decodes `Error(string)`, `Panic(uint256)` and the known contract errors; other custom errors are
+    /// returned by their selector.
+    pub fn decode(data: &[u8]) -> Self {
+        let Some(selector) = data.get(..4) else {
+            return Self::new(
+                "invalidRevertData",
+                format!("invalid revert data 0x{}", hex::encode(data)),
+            );
+        };
+        for (contract, decoders) in CONTRACT_ERRORS {
+            if let Some((name, error)) = decoders.iter().find_map(|decode| decode(data)) {
+                return Self {
+                    code: format!("{}.{}", contract, name),
+                    reason: format!("{}: {}", contract, error),
+                };
+            }
+        }
+        match alloy::sol_types::decode_revert_reason(data) {
+            Some(reason) if selector == Panic::SELECTOR => Self::new("panic", reason),
+            Some(reason) => Self::new("revert", reason),
+            None => Self::new(
+                "customError",
+                format!("custom error 0x{}", hex::encode(selector)),
+            ),
+        }
+    }
+
+    /// Returns the revert of an rpc error response, if it carries revert data or reports a revert.
+    pub fn from_transport_error(error: &TransportError) -> Option<Self> {
+        let payload = error.as_error_resp()?;
+        let revert_data = payload
+            .try_data_as::<String>()
+            .and_then(|data| data.ok())
+            .and_then(|data| Bytes::from_str(&data).ok());
+        match revert_data {
+            Some(data) if !data.is_empty() => Some(Self::decode(&data)),
+            _ if payload.message.contains("revert") => {
+                Some(Self::new("executionError", payload.message.clone()))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{primitives::U256, sol_types::Revert, transports::RpcError};
+
+    use super::*;
+
+    #[test]
+    fn revert_reasons() {
+        let data = Revert::from("ERC20: transfer amount exceeds balance").abi_encode();
+        assert_eq!(
+            RevertError::decode(&data),
+            RevertError::new(
+                "revert",
+                "revert: ERC20: transfer amount exceeds balance".into()
+            )
+        );
+
+        let data = Panic::from(0x11).abi_encode();
+        assert_eq!(
+            RevertError::decode(&data),
+            RevertError::new(
+                "panic",
+                "panic: arithmetic underflow or overflow (0x11)".into()
+            )
+        );
+
+        // unknown custom error, with arguments
+        let data =
+            hex::decode("f722177f0000000000000000000000000000000000000000000000000000000000000001")
+                .unwrap();
+        assert_eq!(
+            RevertError::decode(&data),
+            RevertError::new("customError", "custom error 0xf722177f".into())
+        );
+
+        assert_eq!(
+            RevertError::decode(&[0x01]),
+            RevertError::new("invalidRevertData", "invalid revert data 0x01".into())
+        );
+    }
+
+    #[test]
+    fn contract_errors() {
+        let data = crate::across::ISpokePoolErrors::InvalidFillDeadline {}.abi_encode();
+        assert_eq!(
+            RevertError::decode(&data),
+            RevertError::new(
+                "SpokePool.InvalidFillDeadline",
+                "SpokePool: InvalidFillDeadline".into()
+            )
+        );
+
+        let data = crate::across::ISpokePoolErrors::MsgValueDoesNotMatchInputAmount {}.abi_encode();
+        assert_eq!(
+            RevertError::decode(&data).code,
+            "SpokePool.MsgValueDoesNotMatchInputAmount"
+        );
+
+        let data = crate::debridge::IDlnSourceErrors::WrongFixedFee {
+            received: U256::from(1),
+            actual: U256::from(2),
+        }
+        .abi_encode();
+        assert_eq!(
+            RevertError::decode(&data),
+            RevertError::new(
+                "DlnSource.WrongFixedFee",
+                "DlnSource: WrongFixedFee { received: 1, actual: 2 }".into()
+            )
+        );
+    }
+
+    #[test]
+    fn error_response_revert() {
+        let payload = serde_json::from_value(serde_json::json!({
+            "code": 3,
+            "message": "execution reverted",
+            "data": format!(
+                "0x{}",
+                hex::encode(crate::across::ISpokePoolErrors::InvalidQuoteTimestamp {}.abi_encode())
+            ),
+        }))
+        .unwrap();
+        assert_eq!(
+            RevertError::from_transport_error(&RpcError::ErrorResp(payload)).unwrap(),
+            RevertError::new(
+                "SpokePool.InvalidQuoteTimestamp",
+                "SpokePool: InvalidQuoteTimestamp".into()
+            )
+        );
+
+        let payload = serde_json::from_value(serde_json::json!({
+            "code": -32000,
+            "message": "execution reverted",
+        }))
+        .unwrap();
+        assert_eq!(
+            RevertError::from_transport_error(&RpcError::ErrorResp(payload))
+                .unwrap()
+                .code,
+            "executionError"
+        );
+
+        // not a revert
+        let payload = serde_json::from_value(serde_json::json!({
+            "code": -32000,
+            "message": "header not found",
+        }))
+        .unwrap();
+        assert!(RevertError::from_transport_error(&RpcError::ErrorResp(payload)).is_none());
+    }
+}
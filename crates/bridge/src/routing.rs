@@ -0,0 +1,377 @@
+use std::str::FromStr;
+
+use crate::{ProviderQuote, SupportedProviders};
+
+/// Policy deciding the order in which providers are attempted for a request; the first provider to succeed is used.
+/// Serialized as `cheapest`, `fastest`, `preferred:<provider>` or `weighted:<provider>=<weight>,...`,
+/// so that it can be provided as a query param or cli arg.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum RoutingStrategy {
+    /// Highest quoted net output amount first
+    Cheapest,
+    /// Lowest quoted estimated fill time first
+    Fastest,
+    /// Preferred provider first, falling back to the remaining providers
+    Preferred(SupportedProviders),
+    /// Weighted split of requests between providers; the provider attempted first is picked at random,
+    /// proportionally to its weight, falling back to the remaining providers. If none of the weighted
+    /// providers supports the route, the candidates are attempted in the default order.
+    Weighted(Vec<(SupportedProviders, u32)>),
+}
+
+impl Default for RoutingStrategy {
+    fn default() -> Self {
+        Self::Preferred(SupportedProviders::Across)
+    }
+}
+
+impl RoutingStrategy {
+    /// Whether provider quotes are required to order the providers.
+    pub fn requires_quotes(&self) -> bool {
+        matches!(self, Self::Cheapest | Self::Fastest)
+    }
+
+    /// Orders the candidate providers supporting the route; second-tier providers are attempted last,
+    /// unless preferred. Quote based strategies only return providers with a successful quote;
+    /// `native_input` is whether the input token is the source chain native currency, see `cmp_net_output`.
+    pub fn order(
+        &self,
+        candidates: &[SupportedProviders],
+        quotes: &[ProviderQuote],
+        native_input: bool,
+        seed: u64,
+    ) -> Vec<SupportedProviders> {
+        match self {
+            Self::Cheapest | Self::Fastest => {
+                let mut quotes: Vec<_> = quotes
+                    .iter()
+                    .filter_map(|quote| match quote {
+                        ProviderQuote::Quoted(quote) if candidates.contains(&quote.provider) => {
+                            Some(quote)
+                        }
+                        _ => None,
+                    })
+                    .collect();
+                if *self == Self::Cheapest {
+                    quotes.sort_by(|a, b| {
+                        crate::cmp_net_output(a, b, native_input)
+                            .then(a.estimated_fill_time_secs.cmp(&b.estimated_fill_time_secs))
+                    });
+                } else {
+                    quotes.sort_by(|a, b| {
+                        a.estimated_fill_time_secs
+                            .cmp(&b.estimated_fill_time_secs)
+                            .then(crate::cmp_net_output(a, b, native_input))
+                    });
+                }
+                demote_second_tier(quotes.into_iter().map(|quote| quote.provider.clone()))
+            }
+            Self::Preferred(provider) => {
+                prioritise(&demote_second_tier(candidates.to_vec()), Some(provider))
+            }
+            Self::Weighted(weights) => prioritise(
+                &demote_second_tier(candidates.to_vec()),
+                get_weighted_provider(weights, candidates, seed),
+            ),
+        }
+    }
+}
+
+/// Moves the provider (if a candidate) to the front, keeping the order of the remaining candidates.
+fn prioritise(
+    candidates: &[SupportedProviders],
+    provider: Option<&SupportedProviders>,
+) -> Vec<SupportedProviders> {
+    let mut providers = candidates.to_vec();
+    if let Some(pos) = provider.and_then(|provider| providers.iter().position(|p| p == provider)) {
+        let provider = providers.remove(pos);
+        providers.insert(0, provider);
+    }
+    providers
+}
+
+/// Moves second-tier providers after the first-tier providers, keeping the order within each tier.
+fn demote_second_tier(
+    providers: impl IntoIterator<Item = SupportedProviders>,
+) -> Vec<SupportedProviders> {
+    let (first_tier, second_tier): (Vec<_>, Vec<_>) = providers
+        .into_iter()
+        .partition(|provider| !provider.is_second_tier());
+    first_tier.into_iter().chain(second_tier).collect()
+}
+
+/// Picks a candidate provider proportionally to its weight, using the `seed` as the random value.
+fn get_weighted_provider<'a>(
+    weights: &'a [(SupportedProviders, u32)],
+    candidates: &[SupportedProviders],
+    seed: u64,
+) -> Option<&'a SupportedProviders> {
+    let weights: Vec<_> = weights
+        .iter()
+        .filter(|(provider, weight)| *weight > 0 && candidates.contains(provider))
+        .collect();
+    let total: u64 = weights.iter().map(|(_, weight)| u64::from(*weight)).sum();
+    if total == 0 {
+        return None;
+    }
+    let mut pick = seed % total;
+    weights.into_iter().find_map(|(provider, weight)| {
+        if pick < u64::from(*weight) {
+            return Some(provider);
+        }
+        pick -= u64::from(*weight);
+        None
+    })
+}
+
+impl FromStr for RoutingStrategy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.split_once(':') {
+            None if value == "cheapest" => Ok(Self::Cheapest),
+            None if value == "fastest" => Ok(Self::Fastest),
+            Some(("preferred", provider)) => Ok(Self::Preferred(provider.parse()?)),
+            Some(("weighted", weights)) => weights
+                .split(',')
+                .map(|weight| {
+                    let (provider, weight) = weight.split_once('=').ok_or_else(|| {
+                        format!("invalid provider weight, expected `<provider>=<weight>`: {}", weight)
+                    })?;
+                    let weight = weight
+                        .parse()
+                        .map_err(|e| format!("invalid provider weight {}: {}", weight, e))?;
+                    Ok((provider.parse()?, weight))
+                })
+                .collect::<Result<_, String>>()
+                .map(Self::Weighted),
+            _ => Err(format!(
+                "invalid routing strategy, expected `cheapest`, `fastest`, `preferred:<provider>` or `weighted:<provider>=<weight>,...`: {}",
+                value
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for RoutingStrategy {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<RoutingStrategy> for String {
+    fn from(strategy: RoutingStrategy) -> Self {
+        match strategy {
+            RoutingStrategy::Cheapest => "cheapest".to_string(),
+            RoutingStrategy::Fastest => "fastest".to_string(),
+            RoutingStrategy::Preferred(provider) => format!("preferred:{}", provider),
+            RoutingStrategy::Weighted(weights) => format!(
+                "weighted:{}",
+                weights
+                    .iter()
+                    .map(|(provider, weight)| format!("{}={}", provider, weight))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BridgeQuote, U256};
+
+    fn quote(
+        provider: SupportedProviders,
+        output_amount: u32,
+        estimated_fill_time_secs: u64,
+    ) -> ProviderQuote {
+        ProviderQuote::Quoted(Box::new(BridgeQuote::new(
+            provider,
+            U256::from(2_000_000u32),
+            U256::from(output_amount),
+            vec![],
+            estimated_fill_time_secs,
+            0,
+        )))
+    }
+
+    #[test]
+    fn routing_strategy_serde() {
+        let strategies = [
+            ("cheapest", RoutingStrategy::Cheapest),
+            ("fastest", RoutingStrategy::Fastest),
+            (
+                "preferred:debridge",
+                RoutingStrategy::Preferred(SupportedProviders::DeBridge),
+            ),
+            (
+                "weighted:across=70,debridge=30",
+                RoutingStrategy::Weighted(vec![
+                    (SupportedProviders::Across, 70),
+                    (SupportedProviders::DeBridge, 30),
+                ]),
+            ),
+        ];
+        for (value, strategy) in strategies {
+            assert_eq!(RoutingStrategy::from_str(value).unwrap(), strategy);
+            assert_eq!(String::from(strategy), value);
+        }
+        assert!(RoutingStrategy::from_str("preferred:unknown").is_err());
+        assert!(RoutingStrategy::from_str("weighted:across").is_err());
+        assert!(RoutingStrategy::from_str("random").is_err());
+    }
+
+    #[test]
+    fn order_by_quotes() {
+        let candidates = [SupportedProviders::Across, SupportedProviders::DeBridge];
+        let quotes = [
+            quote(SupportedProviders::Across, 1_998_000, 4),
+            quote(SupportedProviders::DeBridge, 1_999_000, 60),
+        ];
+        assert_eq!(
+            RoutingStrategy::Cheapest.order(&candidates, &quotes, false, 0),
+            vec![SupportedProviders::DeBridge, SupportedProviders::Across]
+        );
+        assert_eq!(
+            RoutingStrategy::Fastest.order(&candidates, &quotes, false, 0),
+            vec![SupportedProviders::Across, SupportedProviders::DeBridge]
+        );
+
+        // providers failing to quote are not attempted
+        let quotes = [
+            ProviderQuote::new(
+                SupportedProviders::Across,
+                Err(crate::BridgeError::AmountTooLow.into()),
+            ),
+            quote(SupportedProviders::DeBridge, 1_999_000, 60),
+        ];
+        assert_eq!(
+            RoutingStrategy::Cheapest.order(&candidates, &quotes, false, 0),
+            vec![SupportedProviders::DeBridge]
+        );
+    }
+
+    #[test]
+    fn order_preferred() {
+        let candidates = [SupportedProviders::Across, SupportedProviders::DeBridge];
+        assert_eq!(
+            RoutingStrategy::Preferred(SupportedProviders::DeBridge).order(
+                &candidates,
+                &[],
+                false,
+                0
+            ),
+            vec![SupportedProviders::DeBridge, SupportedProviders::Across]
+        );
+        // preferred provider not supporting the route
+        assert_eq!(
+            RoutingStrategy::Preferred(SupportedProviders::Across).order(
+                &[SupportedProviders::DeBridge],
+                &[],
+                false,
+                0
+            ),
+            vec![SupportedProviders::DeBridge]
+        );
+    }
+
+    #[test]
+    fn order_second_tier() {
+        let candidates = [
+            SupportedProviders::LiFi,
+            SupportedProviders::Across,
+            SupportedProviders::DeBridge,
+        ];
+        // aggregator quoting the highest output is still attempted last
+        let quotes = [
+            quote(SupportedProviders::LiFi, 1_999_500, 30),
+            quote(SupportedProviders::Across, 1_998_000, 4),
+        ];
+        assert_eq!(
+            RoutingStrategy::Cheapest.order(&candidates, &quotes, false, 0),
+            vec![SupportedProviders::Across, SupportedProviders::LiFi]
+        );
+        assert_eq!(
+            RoutingStrategy::Preferred(SupportedProviders::DeBridge).order(
+                &candidates,
+                &[],
+                false,
+                0
+            ),
+            vec![
+                SupportedProviders::DeBridge,
+                SupportedProviders::Across,
+                SupportedProviders::LiFi
+            ]
+        );
+        // explicitly preferred aggregators are attempted first
+        assert_eq!(
+            RoutingStrategy::Preferred(SupportedProviders::LiFi).order(&candidates, &[], false, 0),
+            vec![
+                SupportedProviders::LiFi,
+                SupportedProviders::Across,
+                SupportedProviders::DeBridge
+            ]
+        );
+    }
+
+    #[test]
+    fn order_weighted() {
+        let candidates = [SupportedProviders::Across, SupportedProviders::DeBridge];
+        let strategy = RoutingStrategy::Weighted(vec![
+            (SupportedProviders::Across, 70),
+            (SupportedProviders::DeBridge, 30),
+        ]);
+        // the picked provider is attempted first, falling back to the remaining candidates
+        assert_eq!(
+            strategy.order(&candidates, &[], false, 69),
+            vec![SupportedProviders::Across, SupportedProviders::DeBridge]
+        );
+        assert_eq!(
+            strategy.order(&candidates, &[], false, 70),
+            vec![SupportedProviders::DeBridge, SupportedProviders::Across]
+        );
+        assert_eq!(
+            strategy.order(&candidates, &[], false, 170),
+            vec![SupportedProviders::DeBridge, SupportedProviders::Across]
+        );
+        // second-tier candidates are still attempted last
+        assert_eq!(
+            strategy.order(
+                &[
+                    SupportedProviders::LiFi,
+                    SupportedProviders::Across,
+                    SupportedProviders::DeBridge
+                ],
+                &[],
+                false,
+                70
+            ),
+            vec![
+                SupportedProviders::DeBridge,
+                SupportedProviders::Across,
+                SupportedProviders::LiFi
+            ]
+        );
+        // weights of providers not supporting the route are ignored
+        assert_eq!(
+            strategy.order(&[SupportedProviders::DeBridge], &[], false, 0),
+            vec![SupportedProviders::DeBridge]
+        );
+        // unweighted candidates are attempted in the default order
+        assert_eq!(
+            strategy.order(
+                &[SupportedProviders::LiFi, SupportedProviders::Cctp],
+                &[],
+                false,
+                0
+            ),
+            vec![SupportedProviders::Cctp, SupportedProviders::LiFi]
+        );
+    }
+}
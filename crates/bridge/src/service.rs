@@ -1,9 +1,41 @@
+use std::{
+    collections::HashMap,
+    hash::{BuildHasher, Hasher},
+    str::FromStr,
+};
+
+use alloy::{
+    primitives::{Address, U256},
+    providers::{Provider, ProviderBuilder, RootProvider},
+};
+use core::future::Future;
+use futures::stream::{FuturesOrdered, StreamExt};
+
 use crate::BridgeProvider;
 
 #[derive(Debug, Clone)]
 pub struct BridgeService {
     across: crate::across::AcrossBridge,
     debridge: crate::debridge::DeBridge,
+    cctp: crate::cctp::CctpBridge,
+    op_stack: crate::op_stack::OpStackBridge,
+    arbitrum: crate::arbitrum::ArbitrumBridge,
+    stargate: crate::stargate::StargateBridge,
+    relay: crate::relay::RelayBridge,
+    lifi: crate::lifi::LiFi,
+    mayan: crate::mayan::MayanBridge,
+    /// Deployment routing strategy; used unless overridden per request
+    strategy: crate::routing::RoutingStrategy,
+    /// Source chain providers, simulating the returned txs
+    providers: HashMap<u32, RootProvider<alloy::transports::http::Http<reqwest::Client>>>,
+    /// Cache of the token storage slots, found by probing for simulations
+    cache: crate::cache::Cache,
+}
+
+impl Default for BridgeService {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl BridgeService {
@@ -11,9 +43,39 @@ impl BridgeService {
         Self {
             across: crate::across::AcrossBridge::new(),
             debridge: crate::debridge::DeBridge::new(),
+            cctp: crate::cctp::CctpBridge::new(),
+            op_stack: crate::op_stack::OpStackBridge::new(),
+            arbitrum: crate::arbitrum::ArbitrumBridge::new(),
+            stargate: crate::stargate::StargateBridge::new(),
+            relay: crate::relay::RelayBridge::new(),
+            lifi: crate::lifi::LiFi::new(),
+            mayan: crate::mayan::MayanBridge::new(),
+            strategy: crate::routing::RoutingStrategy::default(),
+            providers: utils::get_supported_chains()
+                .iter()
+                .map(|(id, chain)| {
+                    let rpc_url = reqwest::Url::parse(chain.rpc_url).unwrap(); // infallible
+                    (*id, ProviderBuilder::new().on_http(rpc_url))
+                })
+                .collect(),
+            cache: crate::cache::Cache::memory(),
         }
     }
 
+    /// Sets the default routing strategy of the service.
+    pub fn with_strategy(mut self, strategy: crate::routing::RoutingStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Sets the cache of the providers' slowly changing upstream responses.
+    pub fn with_cache(mut self, cache: crate::cache::Cache) -> Self {
+        self.across = self.across.with_cache(cache.clone());
+        self.cctp = self.cctp.with_cache(cache.clone());
+        self.cache = cache;
+        self
+    }
+
     pub fn get_supported_chains(&self) -> Vec<&utils::ChainData> {
         let chain_data: Vec<_> = utils::get_supported_chains()
             .iter()
@@ -22,55 +84,513 @@ impl BridgeService {
         chain_data
     }
 
-    pub async fn get_tx(&self, req: &crate::BridgeRequest) -> eyre::Result<crate::BridgeResponse> {
+    /// Returns the providers supporting the route, in default priority order.
+    fn get_route_providers(
+        &self,
+        src_chain_id: u32,
+        dest_chain_id: u32,
+    ) -> eyre::Result<Vec<crate::SupportedProviders>> {
         let _ = utils::get_supported_chains()
-            .get(&req.src_chain_id)
-            .ok_or_else(|| eyre::eyre!("unsupported source chain: {}", req.src_chain_id))?;
+            .get(&src_chain_id)
+            .ok_or(crate::BridgeError::UnsupportedChain(src_chain_id))?;
 
         // if dest chain not in map, return error
         let _ = utils::get_supported_chains()
-            .get(&req.dest_chain_id)
-            .ok_or_else(|| eyre::eyre!("unsupported dest chain: {}", req.dest_chain_id))?;
+            .get(&dest_chain_id)
+            .ok_or(crate::BridgeError::UnsupportedChain(dest_chain_id))?;
 
-        // if src or dest chain is solana; simply use debridge
-        if req.src_chain_id == utils::Chain::Solana as u32
-            || req.dest_chain_id == utils::Chain::Solana as u32
-        {
-            return self.debridge.get_bridging_data(req).await;
+        // solana routes are only supported by DLN, LI.FI and Mayan; Mayan only for solana destinations
+        if src_chain_id == utils::Chain::Solana as u32 {
+            return Ok(vec![
+                crate::SupportedProviders::DeBridge,
+                crate::SupportedProviders::LiFi,
+            ]);
         }
+        if dest_chain_id == utils::Chain::Solana as u32 {
+            return Ok(vec![
+                crate::SupportedProviders::DeBridge,
+                crate::SupportedProviders::Mayan,
+                crate::SupportedProviders::LiFi,
+            ]);
+        }
+        Ok(vec![
+            crate::SupportedProviders::Across,
+            crate::SupportedProviders::Cctp,
+            crate::SupportedProviders::DeBridge,
+            crate::SupportedProviders::OpStack,
+            crate::SupportedProviders::Arbitrum,
+            crate::SupportedProviders::Stargate,
+            crate::SupportedProviders::Relay,
+            crate::SupportedProviders::LiFi,
+        ])
+    }
+
+    async fn get_provider_bridging_data(
+        &self,
+        provider: crate::SupportedProviders,
+        req: &crate::BridgeRequest,
+    ) -> eyre::Result<crate::BridgeResponse> {
+        match provider {
+            crate::SupportedProviders::Across => self.across.get_bridging_data(req).await,
+            crate::SupportedProviders::DeBridge => self.debridge.get_bridging_data(req).await,
+            crate::SupportedProviders::Cctp => self.cctp.get_bridging_data(req).await,
+            crate::SupportedProviders::OpStack => self.op_stack.get_bridging_data(req).await,
+            crate::SupportedProviders::Arbitrum => self.arbitrum.get_bridging_data(req).await,
+            crate::SupportedProviders::Stargate => self.stargate.get_bridging_data(req).await,
+            crate::SupportedProviders::Relay => self.relay.get_bridging_data(req).await,
+            crate::SupportedProviders::LiFi => self.lifi.get_bridging_data(req).await,
+            crate::SupportedProviders::Mayan => self.mayan.get_bridging_data(req).await,
+        }
+    }
 
-        // fire off 2 reqeusts in parallel; 1 to across, the other to debridge; run both futures concurrently
-        let across_fut = self.across.get_bridging_data(req);
-        let debridge_fut = self.debridge.get_bridging_data(req);
-        tokio::pin!(across_fut);
-        tokio::pin!(debridge_fut);
+    async fn get_provider_quote(
+        &self,
+        provider: crate::SupportedProviders,
+        req: &crate::QuoteRequest,
+    ) -> crate::ProviderQuote {
+        let result = match provider {
+            crate::SupportedProviders::Across => self.across.get_quote(req).await,
+            crate::SupportedProviders::DeBridge => self.debridge.get_quote(req).await,
+            crate::SupportedProviders::Cctp => self.cctp.get_quote(req).await,
+            crate::SupportedProviders::OpStack => self.op_stack.get_quote(req).await,
+            crate::SupportedProviders::Arbitrum => self.arbitrum.get_quote(req).await,
+            crate::SupportedProviders::Stargate => self.stargate.get_quote(req).await,
+            crate::SupportedProviders::Relay => self.relay.get_quote(req).await,
+            crate::SupportedProviders::LiFi => self.lifi.get_quote(req).await,
+            crate::SupportedProviders::Mayan => self.mayan.get_quote(req).await,
+        };
+        let result = match result {
+            Ok(quote) => Ok(quote.with_tx_cost(self.estimate_tx_cost(&provider, req).await)),
+            Err(e) => Err(e),
+        };
+        crate::ProviderQuote::new(provider, result)
+    }
+
+    /// Estimates the source tx cost of a provider quote; best-effort, the quote is returned without it if the
+    /// source chain rpc fails.
+    async fn estimate_tx_cost(
+        &self,
+        provider: &crate::SupportedProviders,
+        req: &crate::QuoteRequest,
+    ) -> Option<crate::gas::TxCost> {
+        // solana txs are priced per signature, rather than gas
+        if req.src_chain_id == utils::Chain::Solana as u32 {
+            return None;
+        }
+        let rpc = self.providers.get(&req.src_chain_id)?;
+        crate::gas::estimate_bridging_tx_cost(rpc, req.src_chain_id, provider)
+            .await
+            .map_err(|e| tracing::warn!("failed to estimate quote tx cost: {}", e))
+            .ok()
+    }
 
+    /// Quotes the request with the providers concurrently.
+    async fn get_provider_quotes(
+        &self,
+        providers: &[crate::SupportedProviders],
+        req: &crate::QuoteRequest,
+    ) -> Vec<crate::ProviderQuote> {
+        providers
+            .iter()
+            .map(|provider| self.get_provider_quote(provider.clone(), req))
+            .collect::<FuturesOrdered<_>>()
+            .collect()
+            .await
+    }
+
+    /// Orders the route providers using the request strategy, or the service strategy if not provided.
+    async fn get_ordered_providers(
+        &self,
+        strategy: Option<&crate::routing::RoutingStrategy>,
+        candidates: &[crate::SupportedProviders],
+        req: &crate::QuoteRequest,
+    ) -> (Vec<crate::SupportedProviders>, Vec<crate::ProviderQuote>) {
+        let strategy = strategy.unwrap_or(&self.strategy);
+        let quotes = if strategy.requires_quotes() {
+            self.get_provider_quotes(candidates, req).await
+        } else {
+            vec![]
+        };
+        let seed = std::collections::hash_map::RandomState::new()
+            .build_hasher()
+            .finish();
+        let native_input = utils::is_native_token(&req.src_token);
+        (
+            strategy.order(candidates, &quotes, native_input, seed),
+            quotes,
+        )
+    }
+
+    pub async fn get_tx(&self, req: &crate::BridgeRequest) -> eyre::Result<crate::BridgeResponse> {
+        let candidates = self.get_route_providers(req.src_chain_id, req.dest_chain_id)?;
+        let (providers, _) = self
+            .get_ordered_providers(req.strategy.as_ref(), &candidates, &req.into())
+            .await;
+
+        // providers are attempted in strategy order, a few at a time; the first to succeed in that order is returned
         // TODO: add logging on the path taken by the futures
-        let bridge_result = tokio::select! {
-            across_res = &mut across_fut => {
-                match across_res {
-                  Ok(response) => Ok(response),
-                  // across errored, we wait and fallback to debridge response
-                  Err(_) => debridge_fut.await,
-                }
-            },
-            debridge_res = &mut debridge_fut => {
-                match debridge_res {
-                  // debridge finished first, but we wait for across - as it must be prioritised if succcessful
-                  Ok(response) => across_fut.await.or(Ok(response)),
-                  // debridge errored, we still wait for across
-                  Err(_) => across_fut.await.or_else(|e| Err(e)),
-                }
-            },
+        let mut response = first_ok(
+            providers
+                .into_iter()
+                .map(|provider| self.get_provider_bridging_data(provider, req)),
+            PROVIDER_CONCURRENCY,
+        )
+        .await?;
+
+        // best-effort; the tx is returned with an unavailable simulation if it could not be simulated
+        if req.simulate {
+            let simulation = self.simulate(req, &response).await.unwrap_or_else(|e| {
+                tracing::warn!("failed to simulate tx: {}", e);
+                crate::simulation::Simulation::unavailable(e.to_string())
+            });
+            response.simulation = Some(simulation);
+        }
+        self.fill_tx_gas(req, &mut response).await;
+        Ok(response)
+    }
+
+    /// Fills the gas and fee parameters of the returned tx, so that it can be signed as is, and estimates its
+    /// cost; best-effort, the tx is returned without these if the source chain rpc fails.
+    async fn fill_tx_gas(&self, req: &crate::BridgeRequest, response: &mut crate::BridgeResponse) {
+        let (Some(provider), Ok(caller)) = (
+            self.providers.get(&req.src_chain_id),
+            Address::from_str(&req.src_caller),
+        ) else {
+            return;
         };
-        bridge_result
+        // the simulated gas is only that of the returned tx, if no approval is required first
+        let gas_limit = match (&response.bridge_action, &response.simulation) {
+            (crate::BridgeAction::BridgingTx(_), Some(simulation)) => simulation.gas_limit,
+            _ => None,
+        };
+        let Some(tx) = response.bridge_action.tx_mut() else {
+            return;
+        };
+        if let Err(e) =
+            crate::gas::fill_tx_params(provider, req.src_chain_id, &caller, tx, gas_limit).await
+        {
+            tracing::warn!("failed to fill tx params: {}", e);
+            return;
+        }
+        if tx.gas_limit.is_none() {
+            return;
+        }
+        match crate::gas::get_tx_cost(provider, req.src_chain_id, tx).await {
+            Ok(cost) => response.tx_cost = Some(cost),
+            Err(e) => tracing::warn!("failed to estimate tx cost: {}", e),
+        }
+    }
+
+    /// Simulates the bridging tx from the source chain caller. If an approval or signature is required first,
+    /// the bridging tx is rebuilt as if approved, and simulated with the allowance injected.
+    /// Solana txs and non-EVM callers are returned as an unavailable simulation.
+    async fn simulate(
+        &self,
+        req: &crate::BridgeRequest,
+        response: &crate::BridgeResponse,
+    ) -> eyre::Result<crate::simulation::Simulation> {
+        let spenders = match crate::simulation::SimulationPlan::new(&response.bridge_action)? {
+            crate::simulation::SimulationPlan::BridgingTx => vec![],
+            crate::simulation::SimulationPlan::Approved { spenders } => spenders,
+            crate::simulation::SimulationPlan::Unsupported(reason) => {
+                return Ok(crate::simulation::Simulation::unavailable(reason));
+            }
+        };
+        let Ok(caller) = Address::from_str(&req.src_caller) else {
+            return Ok(crate::simulation::Simulation::unavailable(
+                "unsupported: simulation requires an EVM source chain caller",
+            ));
+        };
+        let provider = self
+            .providers
+            .get(&req.src_chain_id)
+            .ok_or(crate::BridgeError::UnsupportedChain(req.src_chain_id))?;
+
+        let action = if spenders.is_empty() {
+            response.bridge_action.clone()
+        } else {
+            let approved_req = crate::BridgeRequest {
+                assume_approved: true,
+                ..req.clone()
+            };
+            self.get_provider_bridging_data(response.provider.clone(), &approved_req)
+                .await?
+                .bridge_action
+        };
+        let crate::BridgeAction::BridgingTx(tx) = action else {
+            return Err(eyre::eyre!("no bridging tx to simulate"));
+        };
+
+        let token = (!utils::is_native_token(&req.src_token))
+            .then(|| Address::from_str(&req.src_token))
+            .transpose()?;
+        let mut overrides = crate::simulation::SimulationOverrides::default();
+        if let (Some(token), Some(&spender)) = (token, spenders.first()) {
+            let rpc = provider.clone();
+            let slot = self
+                .get_token_slot(req.src_chain_id, &token, "allowance", async move {
+                    crate::simulation::find_allowance_slot(&rpc, &token, &caller, &spender).await
+                })
+                .await?;
+            // the approved spender may differ from the called contract; e.g. token gateways behind a router
+            let bridge = Address::from_str(&tx.to)?;
+            for spender in spenders.iter().chain([&bridge]) {
+                overrides = overrides.with_allowance(&token, &slot, &caller, spender);
+            }
+        }
+        if req.simulate_balance {
+            if let Some(token) = token {
+                let rpc = provider.clone();
+                let slot = self
+                    .get_token_slot(req.src_chain_id, &token, "balance", async move {
+                        crate::simulation::find_balance_slot(&rpc, &token, &caller).await
+                    })
+                    .await?;
+                overrides = overrides.with_token_balance(&token, &slot, &caller, req.src_amount);
+            }
+            overrides = overrides.with_native_balance(&caller, U256::from_str(&tx.value)?);
+        }
+
+        crate::simulation::simulate_tx(provider, &caller, &tx, &overrides).await
+    }
+
+    /// Returns the (cached) storage slot of a token mapping, found by `find` if not cached.
+    async fn get_token_slot(
+        &self,
+        chain_id: u32,
+        token: &Address,
+        mapping: &'static str,
+        find: impl Future<Output = eyre::Result<crate::simulation::MappingSlot>> + Send + 'static,
+    ) -> eyre::Result<crate::simulation::MappingSlot> {
+        self.cache
+            .get_or_fetch(
+                "token_slots",
+                &format!("slots:{}:{}:{}", chain_id, token, mapping),
+                crate::simulation::SLOTS_CACHE_POLICY,
+                move || find,
+            )
+            .await
+    }
+
+    /// Quotes the request, following the same provider priority as `get_tx`.
+    pub async fn get_quote(&self, req: &crate::QuoteRequest) -> eyre::Result<crate::BridgeQuote> {
+        let candidates = self.get_route_providers(req.src_chain_id, req.dest_chain_id)?;
+        let (providers, quotes) = self
+            .get_ordered_providers(req.strategy.as_ref(), &candidates, req)
+            .await;
+
+        // quote based strategies have already quoted the providers
+        if !quotes.is_empty() {
+            return providers
+                .first()
+                .and_then(|provider| {
+                    quotes.into_iter().find_map(|quote| match quote {
+                        crate::ProviderQuote::Quoted(quote) if quote.provider == *provider => {
+                            Some(*quote)
+                        }
+                        _ => None,
+                    })
+                })
+                .ok_or_else(|| eyre::eyre!("no provider quoted the request"));
+        }
+
+        first_ok(
+            providers.into_iter().map(|provider| async move {
+                match self.get_provider_quote(provider, req).await {
+                    crate::ProviderQuote::Quoted(quote) => Ok(*quote),
+                    crate::ProviderQuote::Failed { error, .. } => Err(error.into()),
+                }
+            }),
+            PROVIDER_CONCURRENCY,
+        )
+        .await
+    }
+
+    /// Quotes the request with every provider supporting the route; each provider either returns a quote, or the
+    /// reason it failed. Quotes are ranked by net output amount.
+    pub async fn get_quotes(
+        &self,
+        req: &crate::QuoteRequest,
+    ) -> eyre::Result<Vec<crate::ProviderQuote>> {
+        let candidates = self.get_route_providers(req.src_chain_id, req.dest_chain_id)?;
+        let mut quotes = self.get_provider_quotes(&candidates, req).await;
+        crate::rank_quotes(&mut quotes, utils::is_native_token(&req.src_token));
+        Ok(quotes)
+    }
+
+    /// Returns the normalised status of a DLN order or Across deposit.
+    pub async fn get_status(
+        &self,
+        req: &crate::status::StatusRequest,
+    ) -> eyre::Result<crate::status::OrderStatus> {
+        match req.order_ref()? {
+            crate::status::OrderRef::DeBridge { order_id } => {
+                self.debridge.get_order_status(order_id).await
+            }
+            crate::status::OrderRef::AcrossTxHash { deposit_tx_hash } => {
+                self.across
+                    .get_deposit_status(&crate::across::DepositStatusQueryParams {
+                        origin_chain_id: None,
+                        deposit_id: None,
+                        deposit_tx_hash: Some(deposit_tx_hash),
+                    })
+                    .await
+            }
+            crate::status::OrderRef::AcrossDeposit {
+                origin_chain_id,
+                deposit_id,
+            } => {
+                self.across
+                    .get_deposit_status(&crate::across::DepositStatusQueryParams {
+                        origin_chain_id: Some(origin_chain_id),
+                        deposit_id: Some(deposit_id),
+                        deposit_tx_hash: None,
+                    })
+                    .await
+            }
+        }
+    }
+
+    /// Returns the sender of the tx on the chain, i.e. the `from` address of an EVM tx, or the fee payer of a
+    /// Solana tx; `None` if the tx is not found.
+    pub async fn get_tx_sender(
+        &self,
+        chain_id: u32,
+        tx_hash: &str,
+    ) -> eyre::Result<Option<String>> {
+        let provider = self
+            .providers
+            .get(&chain_id)
+            .ok_or(crate::BridgeError::UnsupportedChain(chain_id))?;
+        if chain_id == utils::Chain::Solana as u32 {
+            let tx: Option<serde_json::Value> = provider
+                .raw_request(
+                    "getTransaction".into(),
+                    (
+                        tx_hash,
+                        serde_json::json!({ "encoding": "json", "maxSupportedTransactionVersion": 0 }),
+                    ),
+                )
+                .await?;
+            return Ok(tx.and_then(|tx| {
+                tx["transaction"]["message"]["accountKeys"][0]
+                    .as_str()
+                    .map(Into::into)
+            }));
+        }
+        let tx = provider
+            .get_transaction_by_hash(alloy::primitives::B256::from_str(tx_hash)?)
+            .await?;
+        Ok(tx.map(|tx| tx.from.to_string()))
     }
 }
 
+/// Max number of providers requested concurrently, when attempting them in order; bounds the upstream requests
+/// of a single call, while a slow or failing provider is not waited for before trying the next ones.
+const PROVIDER_CONCURRENCY: usize = 2;
+
+/// Runs the futures in order, at most `concurrency` at a time, returning the first successful result in order;
+/// i.e. a future is prioritised over the following ones, even if these complete earlier. A following future is
+/// only started once a preceding one has failed. If all fail, the last error is returned.
+async fn first_ok<T>(
+    futures: impl IntoIterator<Item = impl Future<Output = eyre::Result<T>>>,
+    concurrency: usize,
+) -> eyre::Result<T> {
+    let mut futures = futures.into_iter();
+    let mut running: FuturesOrdered<_> = futures.by_ref().take(concurrency.max(1)).collect();
+    let mut last_err = None;
+    while let Some(result) = running.next().await {
+        match result {
+            Ok(response) => return Ok(response),
+            Err(err) => last_err = Some(err),
+        }
+        if let Some(future) = futures.next() {
+            running.push_back(future);
+        }
+    }
+    Err(last_err.unwrap_or_else(|| eyre::eyre!("no provider available for the request")))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn unsupported_route_chain() {
+        let service = BridgeService::new();
+        for (src_chain_id, dest_chain_id, unsupported) in [
+            (10, utils::Chain::Base as u32, 10),
+            (utils::Chain::Base as u32, 10, 10),
+        ] {
+            let err = service
+                .get_route_providers(src_chain_id, dest_chain_id)
+                .unwrap_err();
+            assert_eq!(
+                crate::BridgeError::from_report(&err),
+                crate::BridgeError::UnsupportedChain(unsupported)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn first_ok_in_order() {
+        let delayed = |ms, result: eyre::Result<u32>| async move {
+            tokio::time::sleep(std::time::Duration::from_millis(ms)).await;
+            result
+        };
+        // first future is prioritised, even if completing last
+        let result = first_ok([delayed(20, Ok(1)), delayed(0, Ok(2))], 2).await;
+        assert_eq!(result.unwrap(), 1);
+        // falls back to the following futures
+        let result = first_ok(
+            [delayed(0, Err(eyre::eyre!("failed"))), delayed(20, Ok(2))],
+            2,
+        )
+        .await;
+        assert_eq!(result.unwrap(), 2);
+        // last error is returned
+        let result = first_ok(
+            [
+                delayed(0, Err(eyre::eyre!("1"))),
+                delayed(0, Err(eyre::eyre!("2"))),
+            ],
+            2,
+        )
+        .await;
+        assert_eq!(result.unwrap_err().to_string(), "2");
+    }
+
+    #[tokio::test]
+    async fn first_ok_bounded_concurrency() {
+        let started = std::sync::atomic::AtomicUsize::new(0);
+        let future = |ms, result: eyre::Result<u32>| {
+            let started = &started;
+            async move {
+                started.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                tokio::time::sleep(std::time::Duration::from_millis(ms)).await;
+                result
+            }
+        };
+        // the third future is not started, if one of the first two succeeds
+        let result = first_ok([future(20, Ok(1)), future(0, Ok(2)), future(0, Ok(3))], 2).await;
+        assert_eq!(result.unwrap(), 1);
+        assert_eq!(started.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        // following futures are started as the preceding ones fail
+        started.store(0, std::sync::atomic::Ordering::SeqCst);
+        let result = first_ok(
+            [
+                future(0, Err(eyre::eyre!("1"))),
+                future(0, Err(eyre::eyre!("2"))),
+                future(0, Ok(3)),
+            ],
+            2,
+        )
+        .await;
+        assert_eq!(result.unwrap(), 3);
+        assert_eq!(started.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
     #[tokio::test]
     async fn get_tx_across_bridging_tx_approved_sender() {
         let bridge = BridgeService::new();
@@ -78,14 +598,18 @@ mod tests {
             src_chain_id: utils::Chain::Base as u32, // Base
             src_token: "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913".into(), // USDC Base
             src_caller: "0x000007357111E4789005d4eBfF401a18D99770cE".into(), // caller is recipient
-            src_amount: crate::U256::from(2000_000u32), // 2 USDC
+            src_amount: crate::U256::from(2_000_000u32), // 2 USDC
             src_chain_token_in_sender_permit: None,
             dest_chain_id: utils::Chain::Arbitrum as u32, // Arbitrum
             dest_token: "0xaf88d065e77c8cC2239327C5EDb3A432268e5831".into(), // USDC Arbitrum
             dest_recipient: "0x000007357111E4789005d4eBfF401a18D99770cE".into(), // recipient
             dest_amount: None,
+            permit_deadline: None,
             calldata: None,
+            strategy: None,
             simulate: false,
+            simulate_balance: false,
+            assume_approved: false,
         };
         let response = bridge.get_tx(&request).await;
         assert!(response.is_ok());
@@ -109,14 +633,18 @@ mod tests {
             src_chain_id: utils::Chain::Base as u32, // Base
             src_token: "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913".into(), // USDC Base
             src_caller: "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913".into(), // caller is random account
-            src_amount: crate::U256::from(2000_000u32),                      // 2 USDC
+            src_amount: crate::U256::from(2_000_000u32),                     // 2 USDC
             src_chain_token_in_sender_permit: None,
             dest_chain_id: utils::Chain::Arbitrum as u32, // Arbitrum
             dest_token: "0xaf88d065e77c8cC2239327C5EDb3A432268e5831".into(), // USDC Arbitrum
             dest_recipient: "0x000007357111E4789005d4eBfF401a18D99770cE".into(), // recipient
             dest_amount: None,
+            permit_deadline: None,
             calldata: None,
+            strategy: None,
             simulate: false,
+            simulate_balance: false,
+            assume_approved: false,
         };
         let response = bridge.get_tx(&request).await;
         assert!(response.is_ok());
@@ -145,14 +673,18 @@ mod tests {
             src_chain_id: utils::Chain::Base as u32, // Base
             src_token: "0xd9aAEc86B65D86f6A7B5B1b0c42FFA531710b6CA".into(), // USDCbC Base // NOTE: not supported by across
             src_caller: "0x000007357111E4789005d4eBfF401a18D99770cE".into(), // caller is recipient
-            src_amount: crate::U256::from(2000_000u32),                     // 2 USDC
+            src_amount: crate::U256::from(2_000_000u32),                    // 2 USDC
             src_chain_token_in_sender_permit: None,
             dest_chain_id: utils::Chain::Arbitrum as u32, // Arbitrum
             dest_token: "0xaf88d065e77c8cC2239327C5EDb3A432268e5831".into(), // USDC Arbitrum
             dest_recipient: "0x000007357111E4789005d4eBfF401a18D99770cE".into(), // recipient
             dest_amount: None,
+            permit_deadline: None,
             calldata: None,
+            strategy: None,
             simulate: false,
+            simulate_balance: false,
+            assume_approved: false,
         };
         let response = bridge.get_tx(&request).await;
         assert!(response.is_ok());
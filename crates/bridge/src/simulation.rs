@@ -0,0 +1,536 @@
+use std::str::FromStr;
+
+use alloy::{
+    primitives::{keccak256, Address, Bytes, B256, U256},
+    providers::{network::TransactionBuilder, Provider, RootProvider},
+    rpc::types::{
+        state::{AccountOverride, StateOverride},
+        TransactionRequest,
+    },
+    sol,
+    sol_types::{SolCall, SolValue},
+    transports::{RpcError, TransportError},
+};
+use futures::stream::{self, StreamExt, TryStreamExt};
+
+use crate::revert::RevertError;
+
+sol! {
+    function balanceOf(address account) external view returns (uint256);
+}
+
+/// Buffer (in percent) added to the estimated gas, covering state changes between the simulation and execution
+pub const GAS_LIMIT_BUFFER_PERCENT: u64 = 20;
+
+/// Adds `GAS_LIMIT_BUFFER_PERCENT` to the estimated gas
+pub(crate) fn with_gas_buffer(gas: u128) -> u64 {
+    gas as u64 * (100 + GAS_LIMIT_BUFFER_PERCENT) / 100
+}
+
+/// Number of mapping slot indexes probed per layout, when looking up a token balance/allowance slot
+const MAX_PROBED_SLOT_INDEX: u64 = 32;
+/// Number of concurrent probing calls
+const PROBE_CONCURRENCY: usize = 8;
+/// Arbitrary value written to a probed slot, expected to be read back by `balanceOf`/`allowance`
+const PROBE_VALUE: U256 = U256::from_limbs([0x7e57_5107_0000_7e57, 0, 0, 0]);
+/// Token storage layouts only change with contract upgrades
+pub const SLOTS_CACHE_POLICY: crate::cache::CachePolicy = crate::cache::CachePolicy {
+    ttl: std::time::Duration::from_secs(24 * 60 * 60),
+    stale_ttl: std::time::Duration::ZERO,
+};
+/// Native balance injected on top of the tx value, covering the gas of the simulated tx (1 ETH)
+const GAS_BALANCE: U256 = U256::from_limbs([1_000_000_000_000_000_000, 0, 0, 0]);
+
+/// Storage layout of a mapping; Solidity hashes the key before the slot index, Vyper after
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MappingLayout {
+    Solidity,
+    Vyper,
+}
+
+/// Storage slot of a token mapping (e.g. `balanceOf`), as found by probing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MappingSlot {
+    pub layout: MappingLayout,
+    pub index: u64,
+}
+
+impl MappingSlot {
+    /// Storage key of `mapping[key]`
+    pub fn key(&self, key: &Address) -> B256 {
+        self.hash(self.index_key(), key)
+    }
+
+    /// Storage key of `mapping[key][inner_key]`
+    pub fn nested_key(&self, key: &Address, inner_key: &Address) -> B256 {
+        self.hash(self.key(key), inner_key)
+    }
+
+    fn index_key(&self) -> B256 {
+        B256::from(U256::from(self.index))
+    }
+
+    fn hash(&self, slot: B256, key: &Address) -> B256 {
+        match self.layout {
+            MappingLayout::Solidity => keccak256((*key, slot).abi_encode_params()),
+            MappingLayout::Vyper => keccak256((slot, *key).abi_encode_params()),
+        }
+    }
+
+    fn candidates() -> impl Iterator<Item = Self> {
+        [MappingLayout::Solidity, MappingLayout::Vyper]
+            .into_iter()
+            .flat_map(|layout| (0..MAX_PROBED_SLOT_INDEX).map(move |index| Self { layout, index }))
+    }
+}
+
+/// Finds the token `balanceOf` mapping slot, by overriding candidate slots until the probed value is read back.
+pub async fn find_balance_slot(
+    provider: &RootProvider<alloy::transports::http::Http<reqwest::Client>>,
+    token: &Address,
+    owner: &Address,
+) -> eyre::Result<MappingSlot> {
+    let call = balanceOfCall { account: *owner }.abi_encode();
+    find_slot(provider, token, call.into(), |slot| slot.key(owner))
+        .await?
+        .ok_or_else(|| eyre::eyre!("balance slot of token {} not found", token))
+}
+
+/// Finds the token `allowance` mapping slot, by overriding candidate slots until the probed value is read back.
+pub async fn find_allowance_slot(
+    provider: &RootProvider<alloy::transports::http::Http<reqwest::Client>>,
+    token: &Address,
+    owner: &Address,
+    spender: &Address,
+) -> eyre::Result<MappingSlot> {
+    let call = utils::allowanceCall {
+        owner: *owner,
+        spender: *spender,
+    }
+    .abi_encode();
+    find_slot(provider, token, call.into(), |slot| {
+        slot.nested_key(owner, spender)
+    })
+    .await?
+    .ok_or_else(|| eyre::eyre!("allowance slot of token {} not found", token))
+}
+
+async fn find_slot(
+    provider: &RootProvider<alloy::transports::http::Http<reqwest::Client>>,
+    token: &Address,
+    call: Bytes,
+    storage_key: impl Fn(&MappingSlot) -> B256,
+) -> eyre::Result<Option<MappingSlot>> {
+    let tx = TransactionRequest::default()
+        .with_to(*token)
+        .with_input(call);
+    let probes = MappingSlot::candidates().map(|slot| {
+        let overrides = StateOverride::from([(
+            *token,
+            AccountOverride {
+                state_diff: Some([(storage_key(&slot), B256::from(PROBE_VALUE))].into()),
+                ..Default::default()
+            },
+        )]);
+        let tx = &tx;
+        async move {
+            let response = provider.call(tx).overrides(&overrides).await?;
+            eyre::Ok((U256::try_from_be_slice(&response) == Some(PROBE_VALUE)).then_some(slot))
+        }
+    });
+    // probed in order, stopping at the first slot read back
+    let found = stream::iter(probes)
+        .buffered(PROBE_CONCURRENCY)
+        .try_filter_map(|slot| async move { Ok(slot) });
+    let found = std::pin::pin!(found).try_next().await?;
+    Ok(found)
+}
+
+/// State overrides of a simulation, injecting token allowances/balances and native balances
+#[derive(Debug, Clone, Default)]
+pub struct SimulationOverrides(StateOverride);
+
+impl SimulationOverrides {
+    /// Sets `token.allowance[owner][spender]` to the max amount
+    pub fn with_allowance(
+        mut self,
+        token: &Address,
+        slot: &MappingSlot,
+        owner: &Address,
+        spender: &Address,
+    ) -> Self {
+        self.storage(token)
+            .insert(slot.nested_key(owner, spender), B256::from(U256::MAX));
+        self
+    }
+
+    /// Sets `token.balanceOf[owner]` to `amount`
+    pub fn with_token_balance(
+        mut self,
+        token: &Address,
+        slot: &MappingSlot,
+        owner: &Address,
+        amount: U256,
+    ) -> Self {
+        self.storage(token)
+            .insert(slot.key(owner), B256::from(amount));
+        self
+    }
+
+    /// Sets the native balance of `owner` to `value`, plus the gas balance
+    pub fn with_native_balance(mut self, owner: &Address, value: U256) -> Self {
+        self.0.entry(*owner).or_default().balance = Some(value.saturating_add(GAS_BALANCE));
+        self
+    }
+
+    fn storage(&mut self, token: &Address) -> &mut std::collections::HashMap<B256, B256> {
+        self.0
+            .entry(*token)
+            .or_default()
+            .state_diff
+            .get_or_insert_with(Default::default)
+    }
+}
+
+/// Outcome of simulating the returned tx from the source chain caller
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Simulation {
+    /// Whether the tx executed successfully against the latest block
+    pub success: bool,
+    /// Estimated gas, including `GAS_LIMIT_BUFFER_PERCENT`; set if the tx succeeded, and could be estimated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<u64>,
+    /// Decoded revert reason; set if the tx reverted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revert_reason: Option<String>,
+    /// Stable code of the revert, e.g. `SpokePool.InvalidFillDeadline`; set if the tx reverted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
+    /// Reason the tx could not be simulated, e.g. an rpc failure; the tx may still succeed if set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unavailable_reason: Option<String>,
+}
+
+impl Simulation {
+    fn succeeded(gas: Option<u128>) -> Self {
+        Self {
+            success: true,
+            gas_limit: gas.map(with_gas_buffer),
+            revert_reason: None,
+            error_code: None,
+            unavailable_reason: None,
+        }
+    }
+
+    fn reverted(error: RevertError) -> Self {
+        Self {
+            success: false,
+            gas_limit: None,
+            revert_reason: Some(error.reason),
+            error_code: Some(error.code),
+            unavailable_reason: None,
+        }
+    }
+
+    /// Unsuccessful simulation of a tx that could not be simulated; simulations never fail the request
+    pub fn unavailable(reason: impl Into<String>) -> Self {
+        Self {
+            success: false,
+            gas_limit: None,
+            revert_reason: None,
+            error_code: None,
+            unavailable_reason: Some(reason.into()),
+        }
+    }
+}
+
+/// How the bridging tx of a bridge action is simulated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum SimulationPlan {
+    /// The bridging tx is simulated as returned
+    BridgingTx,
+    /// The bridging tx is rebuilt as if approved, and simulated with the token allowance of the spenders injected
+    Approved { spenders: Vec<Address> },
+    /// The action can not be simulated against an EVM chain
+    Unsupported(&'static str),
+}
+
+impl SimulationPlan {
+    pub(crate) fn new(action: &crate::BridgeAction) -> eyre::Result<Self> {
+        let spender = || {
+            action
+                .spender()?
+                .ok_or_else(|| eyre::eyre!("no spender to be approved"))
+        };
+        Ok(match action {
+            crate::BridgeAction::BridgingTx(_) => Self::BridgingTx,
+            crate::BridgeAction::BridgeApprovalTx(..) | crate::BridgeAction::PermitSignature(_) => {
+                Self::Approved {
+                    spenders: vec![spender()?],
+                }
+            }
+            // permit2 pulls the tokens on behalf of the spender, and must be approved itself
+            crate::BridgeAction::Permit2Signature(_) | crate::BridgeAction::Permit2Tx(..) => {
+                Self::Approved {
+                    spenders: vec![utils::permit2::PERMIT2_ADDRESS, spender()?],
+                }
+            }
+            crate::BridgeAction::SolanaTx(_) => {
+                Self::Unsupported("unsupported: Solana txs are not simulated")
+            }
+        })
+    }
+}
+
+/// Simulates `tx` from `from` with `eth_call` against the latest block, estimating its gas if it succeeds.
+/// Reverts are returned as an unsuccessful simulation; transport errors are returned as errors.
+/// With state overrides, the gas is estimated on a best-effort basis, as not all nodes support overriding
+/// the `eth_estimateGas` state; the gas limit is not set if the estimation fails.
+pub async fn simulate_tx(
+    provider: &RootProvider<alloy::transports::http::Http<reqwest::Client>>,
+    from: &Address,
+    tx: &crate::TxData,
+    overrides: &SimulationOverrides,
+) -> eyre::Result<Simulation> {
+    let tx = tx_request(from, tx)?;
+
+    if overrides.0.is_empty() {
+        if let Err(e) = provider.call(&tx).await {
+            return revert_error(e).map(Simulation::reverted);
+        }
+        return match provider.estimate_gas(&tx).await {
+            Ok(gas) => Ok(Simulation::succeeded(Some(gas))),
+            Err(e) => revert_error(e).map(Simulation::reverted),
+        };
+    }
+
+    if let Err(e) = provider.call(&tx).overrides(&overrides.0).await {
+        return revert_error(e).map(Simulation::reverted);
+    }
+    let gas = provider
+        .estimate_gas(&tx)
+        .overrides(&overrides.0)
+        .await
+        .ok();
+    Ok(Simulation::succeeded(gas))
+}
+
+/// Returns the rpc request of `tx`, called from `from`
+pub(crate) fn tx_request(from: &Address, tx: &crate::TxData) -> eyre::Result<TransactionRequest> {
+    Ok(TransactionRequest::default()
+        .with_from(*from)
+        .with_to(Address::from_str(&tx.to)?)
+        .with_input(Bytes::from_str(&tx.data)?)
+        .with_value(U256::from_str(&tx.value)?))
+}
+
+/// Returns the revert of an execution error response; other errors are returned as is.
+fn revert_error(error: TransportError) -> eyre::Result<RevertError> {
+    if let Some(revert) = RevertError::from_transport_error(&error) {
+        return Ok(revert);
+    }
+    match error {
+        // the node rejected the execution without reverting, e.g. insufficient funds
+        RpcError::ErrorResp(payload) => Ok(RevertError {
+            code: "executionError".into(),
+            reason: payload.message,
+        }),
+        error => Err(error.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{primitives::address, sol_types::SolError};
+
+    use super::*;
+
+    #[test]
+    fn mapping_slot_keys() {
+        let owner = address!("000007357111E4789005d4eBfF401a18D99770cE");
+        let spender = address!("09aea4b2242abC8bb4BB78D537A67a245A7bEC64");
+        let pad = |address: &Address| B256::left_padding_from(address.as_slice());
+        let index = B256::from(U256::from(9));
+
+        // balances[owner]; keccak256(owner . slot)
+        let slot = MappingSlot {
+            layout: MappingLayout::Solidity,
+            index: 9,
+        };
+        let key = keccak256([pad(&owner), index].concat());
+        assert_eq!(slot.key(&owner), key);
+        // allowed[owner][spender]; keccak256(spender . keccak256(owner . slot))
+        assert_eq!(
+            slot.nested_key(&owner, &spender),
+            keccak256([pad(&spender), key].concat())
+        );
+
+        // vyper hashes the slot first; keccak256(slot . owner)
+        let slot = MappingSlot {
+            layout: MappingLayout::Vyper,
+            index: 9,
+        };
+        let key = keccak256([index, pad(&owner)].concat());
+        assert_eq!(slot.key(&owner), key);
+        assert_eq!(
+            slot.nested_key(&owner, &spender),
+            keccak256([key, pad(&spender)].concat())
+        );
+    }
+
+    #[test]
+    fn simulation_overrides() {
+        let token = address!("833589fCD6eDb6E08f4c7C32D4f71b54bdA02913");
+        let owner = address!("000007357111E4789005d4eBfF401a18D99770cE");
+        let spender = address!("09aea4b2242abC8bb4BB78D537A67a245A7bEC64");
+        let slot = MappingSlot {
+            layout: MappingLayout::Solidity,
+            index: 9,
+        };
+
+        let overrides = SimulationOverrides::default()
+            .with_allowance(&token, &slot, &owner, &spender)
+            .with_token_balance(&token, &slot, &owner, U256::from(2_000_000u32))
+            .with_native_balance(&owner, U256::from(1));
+        let token_override = &overrides.0[&token];
+        let state_diff = token_override.state_diff.as_ref().unwrap();
+        assert_eq!(
+            state_diff[&slot.nested_key(&owner, &spender)],
+            B256::from(U256::MAX)
+        );
+        assert_eq!(
+            state_diff[&slot.key(&owner)],
+            B256::from(U256::from(2_000_000u32))
+        );
+        assert_eq!(token_override.balance, None);
+        assert_eq!(
+            overrides.0[&owner].balance,
+            Some(GAS_BALANCE + U256::from(1))
+        );
+    }
+
+    #[test]
+    fn simulation_plan_per_action() {
+        let spender = address!("09aea4b2242abC8bb4BB78D537A67a245A7bEC64");
+        let approval_tx = crate::TxData {
+            data: Bytes::from(
+                utils::approveCall {
+                    spender,
+                    amount: U256::MAX,
+                }
+                .abi_encode(),
+            )
+            .to_string(),
+            to: "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913".into(),
+            value: "0".into(),
+            ..Default::default()
+        };
+        let permit = alloy::dyn_abi::TypedData::from_struct(
+            &utils::permit::Permit {
+                owner: Default::default(),
+                spender,
+                value: U256::from(1),
+                nonce: U256::ZERO,
+                deadline: U256::ZERO,
+            },
+            None,
+        );
+        let permit2 = alloy::dyn_abi::TypedData::from_struct(
+            &utils::permit2::PermitTransferFrom {
+                permitted: utils::permit2::TokenPermissions {
+                    token: Default::default(),
+                    amount: U256::from(1),
+                },
+                spender,
+                nonce: U256::ZERO,
+                deadline: U256::ZERO,
+            },
+            None,
+        );
+        let plan = |action| SimulationPlan::new(&action).unwrap();
+
+        assert_eq!(
+            plan(crate::BridgeAction::BridgingTx(approval_tx.clone())),
+            SimulationPlan::BridgingTx
+        );
+        let approved = SimulationPlan::Approved {
+            spenders: vec![spender],
+        };
+        assert_eq!(
+            plan(crate::BridgeAction::BridgeApprovalTx(
+                crate::SupportedProviders::Relay,
+                approval_tx.clone()
+            )),
+            approved
+        );
+        assert_eq!(plan(crate::BridgeAction::PermitSignature(permit)), approved);
+        // permit2 is approved along with the spender
+        let approved = SimulationPlan::Approved {
+            spenders: vec![utils::permit2::PERMIT2_ADDRESS, spender],
+        };
+        assert_eq!(
+            plan(crate::BridgeAction::Permit2Signature(permit2.clone())),
+            approved
+        );
+        assert_eq!(
+            plan(crate::BridgeAction::Permit2Tx(approval_tx, permit2)),
+            approved
+        );
+        assert!(matches!(
+            plan(crate::BridgeAction::SolanaTx(crate::solana::SolanaTxData {
+                tx: String::new(),
+                signer: String::new(),
+                recent_blockhash: String::new(),
+                blockhash_expires_at: 0,
+                instructions: vec![],
+            })),
+            SimulationPlan::Unsupported(_)
+        ));
+    }
+
+    #[test]
+    fn unavailable_simulation_serde() {
+        let simulation = Simulation::unavailable("connection refused");
+        assert_eq!(
+            serde_json::to_value(&simulation).unwrap(),
+            serde_json::json!({
+                "success": false,
+                "unavailableReason": "connection refused",
+            })
+        );
+    }
+
+    #[test]
+    fn error_response_revert_reason() {
+        let payload = serde_json::from_value(serde_json::json!({
+            "code": 3,
+            "message": "execution reverted: ERC20: insufficient allowance",
+            "data": format!(
+                "0x{}",
+                hex::encode(alloy::sol_types::Revert::from("ERC20: insufficient allowance").abi_encode())
+            ),
+        }))
+        .unwrap();
+        assert_eq!(
+            revert_error(RpcError::ErrorResp(payload)).unwrap().reason,
+            "revert: ERC20: insufficient allowance"
+        );
+
+        // no revert data
+        let payload = serde_json::from_value(serde_json::json!({
+            "code": -32000,
+            "message": "insufficient funds for gas * price + value",
+        }))
+        .unwrap();
+        assert_eq!(
+            revert_error(RpcError::ErrorResp(payload)).unwrap(),
+            RevertError {
+                code: "executionError".into(),
+                reason: "insufficient funds for gas * price + value".into(),
+            }
+        );
+    }
+}
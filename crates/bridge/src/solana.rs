@@ -0,0 +1,230 @@
+use base64::Engine;
+
+/// Recent blockhashes expire after 150 blocks, i.e. about a minute at 400ms per slot
+pub const BLOCKHASH_VALIDITY_SECS: u64 = 60;
+
+/// Programs named in the instruction summary
+const KNOWN_PROGRAMS: &[(&str, &str)] = &[
+    ("11111111111111111111111111111111", "System"),
+    (
+        "ComputeBudget111111111111111111111111111111",
+        "ComputeBudget",
+    ),
+    ("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA", "Token"),
+    ("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb", "Token2022"),
+    (
+        "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL",
+        "AssociatedToken",
+    ),
+    ("src5qyZHqTqecJV4aY6Cb6zDZLMDzrDKKezs22MPHr4", "DlnSource"),
+];
+
+/// Solana tx to be signed by the caller, as returned for solana source chains
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SolanaTxData {
+    /// Base64-encoded serialized versioned tx, as expected by solana wallets
+    pub tx: String,
+    /// Fee payer, required to sign the tx (base58)
+    pub signer: String,
+    /// Recent blockhash of the tx (base58)
+    pub recent_blockhash: String,
+    /// Estimated unix timestamp (in seconds) after which the blockhash expires; the tx must then be requested again
+    pub blockhash_expires_at: u64,
+    pub instructions: Vec<InstructionSummary>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstructionSummary {
+    /// Program id (base58)
+    pub program_id: String,
+    /// Program name, if a known program; e.g. `ComputeBudget`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub program: Option<String>,
+    /// Number of accounts passed to the instruction
+    pub accounts: usize,
+    /// Hex-encoded instruction data
+    pub data: String,
+}
+
+impl SolanaTxData {
+    /// Decodes a serialized (legacy or v0) versioned tx; `now` is the unix timestamp (in seconds) at which the tx
+    /// was built, from which the blockhash expiry is estimated.
+    pub fn decode(tx: &[u8], now: u64) -> eyre::Result<Self> {
+        let mut reader = Reader(tx);
+        let signatures = reader.compact_u16()?;
+        reader.take(signatures * 64)?;
+
+        // versioned messages are prefixed with their version, legacy messages start with the header
+        let prefix = reader.peek()?;
+        if prefix & 0x80 != 0 {
+            let version = prefix & 0x7f;
+            if version != 0 {
+                return Err(eyre::eyre!("unsupported solana tx version: {}", version));
+            }
+            reader.take(1)?;
+        }
+        let required_signatures = reader.u8()?;
+        reader.take(2)?; // read-only account counts
+        let account_keys = (0..reader.compact_u16()?)
+            .map(|_| reader.take(32).map(|key| bs58::encode(key).into_string()))
+            .collect::<eyre::Result<Vec<_>>>()?;
+        let recent_blockhash = bs58::encode(reader.take(32)?).into_string();
+
+        let instructions = (0..reader.compact_u16()?)
+            .map(|_| {
+                let program_index = reader.u8()? as usize;
+                let accounts = reader.compact_u16()?;
+                reader.take(accounts)?;
+                let data_len = reader.compact_u16()?;
+                let data = reader.take(data_len)?;
+                // program ids can not be loaded from address lookup tables
+                let program_id = account_keys
+                    .get(program_index)
+                    .ok_or_else(|| eyre::eyre!("invalid program index: {}", program_index))?;
+                Ok(InstructionSummary {
+                    program_id: program_id.clone(),
+                    program: KNOWN_PROGRAMS
+                        .iter()
+                        .find(|(id, _)| id == program_id)
+                        .map(|(_, name)| name.to_string()),
+                    accounts,
+                    data: hex::encode(data),
+                })
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
+
+        let signer = account_keys
+            .first()
+            .filter(|_| required_signatures > 0)
+            .ok_or_else(|| eyre::eyre!("solana tx has no signer"))?;
+        Ok(Self {
+            tx: base64::engine::general_purpose::STANDARD.encode(tx),
+            signer: signer.clone(),
+            recent_blockhash,
+            blockhash_expires_at: now.saturating_add(BLOCKHASH_VALIDITY_SECS),
+            instructions,
+        })
+    }
+}
+
+/// Cursor over serialized solana tx bytes
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> eyre::Result<&'a [u8]> {
+        if self.0.len() < len {
+            return Err(eyre::eyre!("solana tx is truncated"));
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn peek(&self) -> eyre::Result<u8> {
+        self.0
+            .first()
+            .copied()
+            .ok_or_else(|| eyre::eyre!("solana tx is truncated"))
+    }
+
+    fn u8(&mut self) -> eyre::Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    /// Compact-u16 length; 7 bits per byte, little endian, the high bit set if followed by another byte
+    fn compact_u16(&mut self) -> eyre::Result<usize> {
+        let mut value = 0usize;
+        for i in 0..3 {
+            let byte = self.u8()?;
+            value |= ((byte & 0x7f) as usize) << (i * 7);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(eyre::eyre!("invalid compact-u16 length"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(address: &str) -> Vec<u8> {
+        bs58::decode(address).into_vec().unwrap()
+    }
+
+    /// Serialized v0 tx, paying the compute budget and calling the DLN source program
+    fn v0_tx(payer: &[u8]) -> Vec<u8> {
+        let mut tx = vec![1];
+        tx.extend([0; 64]);
+        tx.extend([0x80, 1, 0, 2, 3]);
+        tx.extend(payer);
+        tx.extend(key(KNOWN_PROGRAMS[1].0));
+        tx.extend(key(KNOWN_PROGRAMS[5].0));
+        tx.extend([7; 32]);
+        // set compute unit limit; create order with the payer account
+        tx.extend([2, 1, 0, 5, 2, 0x40, 0x0d, 0x03, 0x00]);
+        tx.extend([2, 1, 0, 2, 0xaa, 0xbb]);
+        // no address lookup tables
+        tx.push(0);
+        tx
+    }
+
+    #[test]
+    fn decode_v0_tx() {
+        let payer = [1; 32];
+        let tx = v0_tx(&payer);
+        let decoded = SolanaTxData::decode(&tx, 1_700_000_000).unwrap();
+
+        assert_eq!(decoded.signer, bs58::encode(payer).into_string());
+        assert_eq!(
+            decoded.recent_blockhash,
+            bs58::encode([7; 32]).into_string()
+        );
+        assert_eq!(decoded.blockhash_expires_at, 1_700_000_060);
+        assert_eq!(
+            base64::engine::general_purpose::STANDARD
+                .decode(&decoded.tx)
+                .unwrap(),
+            tx
+        );
+        assert_eq!(
+            decoded.instructions,
+            vec![
+                InstructionSummary {
+                    program_id: KNOWN_PROGRAMS[1].0.into(),
+                    program: Some("ComputeBudget".into()),
+                    accounts: 0,
+                    data: "02400d0300".into(),
+                },
+                InstructionSummary {
+                    program_id: KNOWN_PROGRAMS[5].0.into(),
+                    program: Some("DlnSource".into()),
+                    accounts: 1,
+                    data: "aabb".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn decode_invalid_tx() {
+        let tx = v0_tx(&[1; 32]);
+        assert!(SolanaTxData::decode(&tx[..tx.len() - 4], 0).is_err());
+
+        // unsupported message version
+        let mut tx = tx;
+        tx[65] = 0x81;
+        assert!(SolanaTxData::decode(&tx, 0).is_err());
+    }
+
+    #[test]
+    fn compact_u16() {
+        assert_eq!(Reader(&[0x7f]).compact_u16().unwrap(), 0x7f);
+        assert_eq!(Reader(&[0x80, 0x01]).compact_u16().unwrap(), 0x80);
+        assert_eq!(Reader(&[0xff, 0xff, 0x03]).compact_u16().unwrap(), 0xffff);
+        assert!(Reader(&[0x80]).compact_u16().is_err());
+    }
+}
@@ -0,0 +1,579 @@
+use crate::U256;
+use std::{collections::HashMap, str::FromStr};
+
+use alloy::primitives::{address, Address, Bytes};
+use alloy::providers::{ProviderBuilder, RootProvider};
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use eyre::Result;
+
+sol! {
+    /// Stargate v2 pool; an OFT sending the pooled asset to the destination endpoint through LayerZero
+    interface IStargate {
+        struct SendParam {
+            uint32 dstEid;
+            bytes32 to;
+            uint256 amountLD;
+            uint256 minAmountLD;
+            bytes extraOptions;
+            bytes composeMsg;
+            bytes oftCmd;
+        }
+
+        struct MessagingFee {
+            uint256 nativeFee;
+            uint256 lzTokenFee;
+        }
+
+        struct OFTLimit {
+            uint256 minAmountLD;
+            uint256 maxAmountLD;
+        }
+
+        struct OFTFeeDetail {
+            int256 feeAmountLD;
+            string description;
+        }
+
+        struct OFTReceipt {
+            uint256 amountSentLD;
+            uint256 amountReceivedLD;
+        }
+
+        struct MessagingReceipt {
+            bytes32 guid;
+            uint64 nonce;
+            MessagingFee fee;
+        }
+
+        function quoteOFT(SendParam calldata _sendParam)
+            external
+            view
+            returns (OFTLimit memory limit, OFTFeeDetail[] memory oftFeeDetails, OFTReceipt memory receipt);
+
+        function quoteSend(SendParam calldata _sendParam, bool _payInLzToken)
+            external
+            view
+            returns (MessagingFee memory fee);
+
+        function send(SendParam calldata _sendParam, MessagingFee calldata _fee, address _refundAddress)
+            external
+            payable
+            returns (MessagingReceipt memory msgReceipt, OFTReceipt memory oftReceipt);
+    }
+}
+
+/// Taxi mode transfers are sent in their own LayerZero message, delivered once the source tx is confirmed
+const ESTIMATED_DELIVERY_SECS: u64 = 3 * 60;
+
+/// Validity of a quote; pool fees and rewards depend on the pool balances
+const QUOTE_VALIDITY_SECS: u64 = 60;
+
+/// Asset pooled by Stargate; transfers are only possible between pools of the same asset
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StargateAsset {
+    Eth,
+    Usdc,
+    Usdt,
+}
+
+/// Stargate pool of a chain
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StargatePool {
+    pub asset: StargateAsset,
+    /// Pooled token; `None` for the native currency
+    pub token: Option<Address>,
+    pub pool: Address,
+}
+
+/// Stargate deployment of a chain
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StargateChain {
+    /// LayerZero v2 endpoint id, distinct from the chain id
+    pub endpoint_id: u32,
+    pub pools: &'static [StargatePool],
+}
+
+static ETHEREUM_POOLS: &[StargatePool] = &[
+    StargatePool {
+        asset: StargateAsset::Eth,
+        token: None,
+        pool: address!("77b2043768d28E9C9aB44E1aBfC95944bcE57931"),
+    },
+    StargatePool {
+        asset: StargateAsset::Usdc,
+        token: Some(address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48")),
+        pool: address!("c026395860Db2d07ee33e05fE50ed7bD583189C7"),
+    },
+    StargatePool {
+        asset: StargateAsset::Usdt,
+        token: Some(address!("dAC17F958D2ee523a2206206994597C13D831ec7")),
+        pool: address!("933597a323Eb81cAe705C5bC29985172fd5A3973"),
+    },
+];
+
+static ARBITRUM_POOLS: &[StargatePool] = &[
+    StargatePool {
+        asset: StargateAsset::Eth,
+        token: None,
+        pool: address!("A45B5130f36CDcA45667738e2a258AB09f4A5f7F"),
+    },
+    StargatePool {
+        asset: StargateAsset::Usdc,
+        token: Some(address!("af88d065e77c8cC2239327C5EDb3A432268e5831")),
+        pool: address!("e8CDF27AcD73a434D661C84887215F7598e7d0d3"),
+    },
+    StargatePool {
+        asset: StargateAsset::Usdt,
+        token: Some(address!("Fd086bC7CD5C481DCC9C85ebE478A1C0b69FCbb9")),
+        pool: address!("cE8CcA271Ebc0533920C83d39F417ED6A0abB7D0"),
+    },
+];
+
+static BASE_POOLS: &[StargatePool] = &[
+    StargatePool {
+        asset: StargateAsset::Eth,
+        token: None,
+        pool: address!("dc181Bd607330aeeBEF6ea62e03e5e1Fb4B6F7C7"),
+    },
+    StargatePool {
+        asset: StargateAsset::Usdc,
+        token: Some(address!("833589fCD6eDb6E08f4c7C32D4f71b54bdA02913")),
+        pool: address!("27a16dc786820B16E5c9028b75B99F6f604b5d26"),
+    },
+];
+
+/// Returns the Stargate deployment of a supported chain.
+pub fn get_stargate_chain(chain_id: u32) -> Option<StargateChain> {
+    match utils::Chain::try_from(chain_id).ok()? {
+        utils::Chain::Ethereum => Some(StargateChain {
+            endpoint_id: 30101,
+            pools: ETHEREUM_POOLS,
+        }),
+        utils::Chain::Arbitrum => Some(StargateChain {
+            endpoint_id: 30110,
+            pools: ARBITRUM_POOLS,
+        }),
+        utils::Chain::Base => Some(StargateChain {
+            endpoint_id: 30184,
+            pools: BASE_POOLS,
+        }),
+        utils::Chain::Solana => None,
+    }
+}
+
+fn get_pool(chain: &StargateChain, token: &str) -> Result<Option<StargatePool>> {
+    let token = if utils::is_native_token(token) {
+        None
+    } else {
+        Some(Address::from_str(token)?)
+    };
+    Ok(chain.pools.iter().find(|pool| pool.token == token).copied())
+}
+
+/// Resolves the source pool and destination endpoint id of a route; errors if the tokens are not pooled by
+/// Stargate on their chain, or are different assets.
+fn get_route(
+    src_chain_id: u32,
+    src_token: &str,
+    dest_chain_id: u32,
+    dest_token: &str,
+) -> Result<(StargatePool, u32)> {
+    let src_chain = get_stargate_chain(src_chain_id)
+        .ok_or(crate::BridgeError::UnsupportedChain(src_chain_id))?;
+    let dest_chain = get_stargate_chain(dest_chain_id)
+        .ok_or(crate::BridgeError::UnsupportedChain(dest_chain_id))?;
+    if src_chain_id == dest_chain_id {
+        return Err(crate::BridgeError::ProviderRejected(
+            "Stargate: source and destination chains must differ".to_string(),
+        )
+        .into());
+    }
+    match (
+        get_pool(&src_chain, src_token)?,
+        get_pool(&dest_chain, dest_token)?,
+    ) {
+        (Some(src_pool), Some(dest_pool)) if src_pool.asset == dest_pool.asset => {
+            Ok((src_pool, dest_chain.endpoint_id))
+        }
+        _ => Err(crate::BridgeError::ProviderRejected(
+            "Stargate: tokens are not pooled as the same asset".to_string(),
+        )
+        .into()),
+    }
+}
+
+/// Builds a taxi mode (immediate delivery) send param.
+fn get_send_param(
+    dest_endpoint_id: u32,
+    recipient: &Address,
+    amount: U256,
+    min_amount: U256,
+) -> IStargate::SendParam {
+    IStargate::SendParam {
+        dstEid: dest_endpoint_id,
+        // recipients are bytes32 to support non-evm endpoints; evm addresses are left padded
+        to: recipient.into_word(),
+        amountLD: amount,
+        minAmountLD: min_amount,
+        extraOptions: Bytes::new(),
+        composeMsg: Bytes::new(),
+        // empty command for taxi mode; bus mode batches transfers at the cost of latency
+        oftCmd: Bytes::new(),
+    }
+}
+
+/// Builds the send tx; the LayerZero messaging fee (and the amount for native pools) is sent as the tx value.
+fn get_send_tx(
+    pool: &StargatePool,
+    send_param: IStargate::SendParam,
+    native_fee: U256,
+    refund_address: &Address,
+) -> crate::TxData {
+    let value = match pool.token {
+        Some(_) => native_fee,
+        None => native_fee + send_param.amountLD,
+    };
+    let calldata = IStargate::sendCall {
+        _sendParam: send_param,
+        _fee: IStargate::MessagingFee {
+            nativeFee: native_fee,
+            lzTokenFee: U256::ZERO,
+        },
+        _refundAddress: *refund_address,
+    };
+    crate::TxData {
+        data: hex::encode(calldata.abi_encode()),
+        to: pool.pool.to_string(),
+        value: value.to_string(),
+        ..Default::default()
+    }
+}
+
+/// On-chain quote of a transfer
+struct StargateQuote {
+    /// Amount debited from the sender, with the dust (beyond the shared decimals) removed
+    amount_sent: U256,
+    amount_received: U256,
+    native_fee: U256,
+}
+
+#[derive(Debug, Clone)]
+pub struct StargateBridge {
+    providers: HashMap<u32, RootProvider<alloy::transports::http::Http<reqwest::Client>>>,
+}
+
+impl Default for StargateBridge {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StargateBridge {
+    pub fn new() -> Self {
+        let supported_providers = utils::get_supported_chains()
+            .iter()
+            .map(|(id, chain)| {
+                let rpc_url = reqwest::Url::parse(chain.rpc_url).unwrap(); // infallible
+                let provider = ProviderBuilder::new().on_http(rpc_url);
+                (*id, provider)
+            })
+            .collect::<HashMap<_, _>>();
+
+        Self {
+            providers: supported_providers,
+        }
+    }
+
+    /// Quotes the received amount with `quoteOFT`, validating the pool limits, then the LayerZero messaging
+    /// fee of the transfer with `quoteSend`.
+    async fn get_pool_quote(
+        &self,
+        chain_id: u32,
+        pool: &StargatePool,
+        dest_endpoint_id: u32,
+        recipient: &Address,
+        amount: U256,
+    ) -> Result<StargateQuote> {
+        let provider = self
+            .providers
+            .get(&chain_id)
+            .ok_or(crate::BridgeError::UnsupportedChain(chain_id))?;
+
+        let oft_quote = utils::eth_call(
+            provider,
+            &pool.pool,
+            IStargate::quoteOFTCall {
+                _sendParam: get_send_param(dest_endpoint_id, recipient, amount, U256::ZERO),
+            },
+        )
+        .await?;
+        if amount < oft_quote.limit.minAmountLD {
+            return Err(crate::BridgeError::AmountTooLow.into());
+        }
+        if amount > oft_quote.limit.maxAmountLD {
+            return Err(crate::BridgeError::AmountTooHigh.into());
+        }
+
+        let receipt = oft_quote.receipt;
+        let fee = utils::eth_call(
+            provider,
+            &pool.pool,
+            IStargate::quoteSendCall {
+                _sendParam: get_send_param(
+                    dest_endpoint_id,
+                    recipient,
+                    receipt.amountSentLD,
+                    receipt.amountReceivedLD,
+                ),
+                _payInLzToken: false,
+            },
+        )
+        .await?
+        .fee;
+
+        Ok(StargateQuote {
+            amount_sent: receipt.amountSentLD,
+            amount_received: receipt.amountReceivedLD,
+            native_fee: fee.nativeFee,
+        })
+    }
+}
+
+impl crate::BridgeProvider for StargateBridge {
+    async fn get_bridging_data(
+        &self,
+        request: &crate::BridgeRequest,
+    ) -> eyre::Result<crate::BridgeResponse> {
+        let (pool, dest_endpoint_id) = get_route(
+            request.src_chain_id,
+            &request.src_token,
+            request.dest_chain_id,
+            &request.dest_token,
+        )?;
+        if request.calldata.is_some() {
+            return Err(crate::BridgeError::ProviderRejected(
+                "Stargate: destination calldata is not supported".to_string(),
+            )
+            .into());
+        }
+        if request.src_chain_token_in_sender_permit.is_some() {
+            return Err(crate::BridgeError::ProviderRejected(
+                "Stargate: permit signatures are not supported".to_string(),
+            )
+            .into());
+        }
+
+        let caller = Address::from_str(&request.src_caller)?;
+        let recipient = Address::from_str(&request.dest_recipient)?;
+        let quote = self
+            .get_pool_quote(
+                request.src_chain_id,
+                &pool,
+                dest_endpoint_id,
+                &recipient,
+                request.src_amount,
+            )
+            .await?;
+        if let Some(dest_amount) = request.dest_amount {
+            if dest_amount > quote.amount_received {
+                return Err(eyre::eyre!(
+                    "requested dest amount is greater than output amount"
+                ));
+            }
+        }
+
+        let response = |bridge_action| {
+            crate::BridgeResponse::new(crate::SupportedProviders::Stargate, bridge_action)
+                .with_estimated_fill_time_secs(Some(ESTIMATED_DELIVERY_SECS))
+        };
+
+        // the transfer reverts if the pool would deliver less than the requested (or quoted) amount
+        let send_param = get_send_param(
+            dest_endpoint_id,
+            &recipient,
+            quote.amount_sent,
+            request.dest_amount.unwrap_or(quote.amount_received),
+        );
+        // excess messaging fees are refunded to the caller
+        let send_tx = get_send_tx(&pool, send_param, quote.native_fee, &caller);
+
+        // native pool; no allowance is required, the amount is sent as value
+        let Some(token) = pool.token else {
+            return Ok(response(crate::BridgeAction::BridgingTx(send_tx)));
+        };
+
+        let provider = self
+            .providers
+            .get(&request.src_chain_id)
+            .ok_or(crate::BridgeError::UnsupportedChain(request.src_chain_id))?;
+
+        // pools consume neither EIP-2612 nor permit2 permits; an explicit approval is required
+        let allowance_action = request
+            .allowance_action(utils::get_token_allowance_action(
+                provider,
+                &token,
+                &quote.amount_sent,
+                &caller,
+                &pool.pool,
+                &utils::PermitOptions {
+                    spender: None,
+                    permit2_kind: None,
+                    deadline: utils::get_permit_deadline(request.permit_deadline),
+                },
+            ))
+            .await?;
+
+        // if there is an pre-allowance tx/sig/action required, it must be returned
+        // to be executed by the caller
+        if let Some(response) = crate::BridgeResponse::from_allowance_action(
+            crate::SupportedProviders::Stargate,
+            allowance_action,
+        ) {
+            return Ok(response);
+        }
+
+        Ok(response(crate::BridgeAction::BridgingTx(send_tx)))
+    }
+
+    async fn get_quote(&self, request: &crate::QuoteRequest) -> eyre::Result<crate::BridgeQuote> {
+        let (pool, dest_endpoint_id) = get_route(
+            request.src_chain_id,
+            &request.src_token,
+            request.dest_chain_id,
+            &request.dest_token,
+        )?;
+        if request.calldata.is_some() {
+            return Err(crate::BridgeError::ProviderRejected(
+                "Stargate: destination calldata is not supported".to_string(),
+            )
+            .into());
+        }
+
+        // the quote does not depend on the recipient; it is optional for quotes
+        let recipient = request
+            .dest_recipient
+            .as_deref()
+            .map(Address::from_str)
+            .transpose()?
+            .unwrap_or_default();
+        let quote = self
+            .get_pool_quote(
+                request.src_chain_id,
+                &pool,
+                dest_endpoint_id,
+                &recipient,
+                request.src_amount,
+            )
+            .await?;
+
+        Ok(crate::BridgeQuote::new(
+            crate::SupportedProviders::Stargate,
+            quote.amount_sent,
+            quote.amount_received,
+            vec![
+                // pool fee, net of rewards paid for rebalancing the pools
+                crate::QuoteFee {
+                    name: "poolFee".to_string(),
+                    token: request.src_token.clone(),
+                    amount: quote.amount_sent.saturating_sub(quote.amount_received),
+                },
+                crate::QuoteFee {
+                    name: "messagingFee".to_string(),
+                    token: Address::ZERO.to_string(),
+                    amount: quote.native_fee,
+                },
+            ],
+            ESTIMATED_DELIVERY_SECS,
+            utils::get_unix_timestamp().saturating_add(QUOTE_VALIDITY_SECS),
+        )
+        .with_native_fee(quote.native_fee))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn route() {
+        let (pool, dest_endpoint_id) = get_route(
+            utils::Chain::Ethereum as u32,
+            "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+            utils::Chain::Arbitrum as u32,
+            "0xaf88d065e77c8cC2239327C5EDb3A432268e5831",
+        )
+        .unwrap();
+        assert_eq!(pool.asset, StargateAsset::Usdc);
+        assert_eq!(
+            pool.pool,
+            address!("c026395860Db2d07ee33e05fE50ed7bD583189C7")
+        );
+        assert_eq!(dest_endpoint_id, 30110);
+
+        let (pool, _) = get_route(
+            utils::Chain::Base as u32,
+            "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE",
+            utils::Chain::Ethereum as u32,
+            "0x0000000000000000000000000000000000000000",
+        )
+        .unwrap();
+        assert_eq!(pool.asset, StargateAsset::Eth);
+        assert!(pool.token.is_none());
+
+        // USDT is not pooled on base
+        let err = get_route(
+            utils::Chain::Ethereum as u32,
+            "0xdAC17F958D2ee523a2206206994597C13D831ec7",
+            utils::Chain::Base as u32,
+            "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913",
+        )
+        .unwrap_err();
+        assert!(matches!(
+            crate::BridgeError::from_report(&err),
+            crate::BridgeError::ProviderRejected(_)
+        ));
+
+        let err = get_route(
+            utils::Chain::Solana as u32,
+            "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+            utils::Chain::Arbitrum as u32,
+            "0xaf88d065e77c8cC2239327C5EDb3A432268e5831",
+        )
+        .unwrap_err();
+        assert_eq!(
+            crate::BridgeError::from_report(&err),
+            crate::BridgeError::UnsupportedChain(utils::Chain::Solana as u32)
+        );
+    }
+
+    #[test]
+    fn send_tx() {
+        let recipient = address!("000007357111E4789005d4eBfF401a18D99770cE");
+        let chain = get_stargate_chain(utils::Chain::Ethereum as u32).unwrap();
+
+        let usdc_pool = chain.pools[1];
+        let send_param = get_send_param(
+            30110,
+            &recipient,
+            U256::from(2_000_000u32),
+            U256::from(1_999_000u32),
+        );
+        let tx = get_send_tx(&usdc_pool, send_param, U256::from(1_000u32), &recipient);
+        assert_eq!(tx.value, "1000");
+        assert_eq!(tx.to, usdc_pool.pool.to_string());
+        let call = IStargate::sendCall::abi_decode(&hex::decode(&tx.data).unwrap(), true).unwrap();
+        assert_eq!(call._sendParam.dstEid, 30110);
+        assert_eq!(call._sendParam.to, recipient.into_word());
+        assert_eq!(call._sendParam.minAmountLD, U256::from(1_999_000u32));
+        assert!(call._sendParam.oftCmd.is_empty());
+        assert_eq!(call._fee.nativeFee, U256::from(1_000u32));
+        assert_eq!(call._refundAddress, recipient);
+
+        // the native pool amount is sent along the messaging fee
+        let eth_pool = chain.pools[0];
+        let send_param = get_send_param(30110, &recipient, U256::from(1_000_000u32), U256::ZERO);
+        let tx = get_send_tx(&eth_pool, send_param, U256::from(1_000u32), &recipient);
+        assert_eq!(tx.value, "1001000");
+    }
+}
@@ -0,0 +1,135 @@
+use crate::SupportedProviders;
+
+/// Normalised state of a bridging order, across providers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OrderState {
+    /// Deposited on the source chain, not yet filled on the destination chain
+    Pending,
+    /// Funds received by the recipient on the destination chain
+    Filled,
+    /// Not filled before its deadline; funds are to be refunded on the source chain
+    Expired,
+    /// Funds returned to the depositor on the source chain
+    Refunded,
+    /// Cancelled by the order authority; funds are yet to be claimed back on the source chain
+    Cancelled,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderStatus {
+    pub provider: SupportedProviders,
+    /// Provider order id; the DLN order id, or the Across deposit id
+    pub order_id: String,
+    pub state: OrderState,
+    /// Source chain deposit tx hash
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deposit_tx_hash: Option<String>,
+    /// Destination chain fill tx hash
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fill_tx_hash: Option<String>,
+    /// Unix timestamp (in seconds) of the deposit on the source chain
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<u64>,
+    /// Unix timestamp (in seconds) of the fill on the destination chain
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filled_at: Option<u64>,
+}
+
+/// Order lookup; either a DLN order id, or an Across deposit by origin tx hash or deposit id.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusRequest {
+    /// DLN order id, as returned by the bridging response
+    pub order_id: Option<String>,
+    /// Across origin chain id; required with the deposit id
+    pub origin_chain_id: Option<u32>,
+    /// Across deposit id, emitted by the spoke pool deposit
+    pub deposit_id: Option<u32>,
+    /// Across origin chain deposit tx hash
+    pub deposit_tx_hash: Option<String>,
+}
+
+/// Provider order reference resolved from a status request
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrderRef<'a> {
+    DeBridge {
+        order_id: &'a str,
+    },
+    AcrossTxHash {
+        deposit_tx_hash: &'a str,
+    },
+    AcrossDeposit {
+        origin_chain_id: u32,
+        deposit_id: u32,
+    },
+}
+
+impl StatusRequest {
+    pub fn order_ref(&self) -> eyre::Result<OrderRef<'_>> {
+        match self {
+            Self {
+                order_id: Some(order_id),
+                ..
+            } => Ok(OrderRef::DeBridge { order_id }),
+            Self {
+                deposit_tx_hash: Some(deposit_tx_hash),
+                ..
+            } => Ok(OrderRef::AcrossTxHash { deposit_tx_hash }),
+            Self {
+                origin_chain_id: Some(origin_chain_id),
+                deposit_id: Some(deposit_id),
+                ..
+            } => Ok(OrderRef::AcrossDeposit {
+                origin_chain_id: *origin_chain_id,
+                deposit_id: *deposit_id,
+            }),
+            _ => Err(eyre::eyre!(
+                "either an order id, a deposit tx hash, or an origin chain id and deposit id is required"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_request_order_ref() {
+        let request = StatusRequest {
+            order_id: Some("0x01".to_string()),
+            origin_chain_id: None,
+            deposit_id: None,
+            deposit_tx_hash: Some("0x02".to_string()),
+        };
+        assert_eq!(
+            request.order_ref().unwrap(),
+            OrderRef::DeBridge { order_id: "0x01" }
+        );
+
+        let request = StatusRequest {
+            order_id: None,
+            origin_chain_id: Some(8453),
+            deposit_id: Some(1_234),
+            deposit_tx_hash: None,
+        };
+        assert_eq!(
+            request.order_ref().unwrap(),
+            OrderRef::AcrossDeposit {
+                origin_chain_id: 8453,
+                deposit_id: 1_234
+            }
+        );
+
+        // deposit id without the origin chain is ambiguous
+        let request = StatusRequest {
+            order_id: None,
+            origin_chain_id: None,
+            deposit_id: Some(1_234),
+            deposit_tx_hash: None,
+        };
+        assert!(request.order_ref().is_err());
+    }
+}
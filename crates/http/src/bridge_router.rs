@@ -1,39 +1,28 @@
 use axum::{
-    body::{Body, Bytes},
-    extract::{Json, Path, Query, Request, State},
-    http::{HeaderMap, HeaderName, HeaderValue, Method, StatusCode},
-    middleware::{self, Next},
-    response::{IntoResponse, Response},
+    extract::{Json, Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
     routing::get,
     Router,
 };
-use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
-use serde_json::{json, Value};
-use std::{
-    collections::HashMap,
-    convert::Infallible,
-    future::ready,
-    sync::OnceLock,
-    time::{Duration, Instant},
-};
-use tower_http::{
-    cors::{Any, CorsLayer},
-    timeout::TimeoutLayer,
-    trace::TraceLayer,
-};
-use tracing::Span;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use serde_json::json;
 
 pub fn router(state: crate::AppState) -> Router {
     Router::new()
         .route("/chains", get(get_chains))
-        .route("/orders/:account", get(get_account_orders))
+        .route(
+            "/orders/:account",
+            get(get_account_orders).post(attach_order_tx_hash),
+        )
+        .route("/quote", get(get_bridge_quote))
+        .route("/quotes", get(get_bridge_quotes))
+        .route("/status", get(get_order_status))
         .route("/tx", get(get_bridge_tx))
         .with_state(state)
 }
 
 async fn get_chains(
-    State(crate::AppState { bridge_service }): State<crate::AppState>,
+    State(crate::AppState { bridge_service, .. }): State<crate::AppState>,
 ) -> impl IntoResponse {
     let chain_data = bridge_service.get_supported_chains();
 
@@ -41,23 +30,143 @@ async fn get_chains(
     (StatusCode::OK, response)
 }
 
-async fn get_account_orders(Path(account): Path<String>) -> impl IntoResponse {
-    // TODO: get bridging tx's for account from db
+async fn get_account_orders(
+    State(crate::AppState { order_store, .. }): State<crate::AppState>,
+    Path(account): Path<String>,
+    Query(params): Query<bridge::orders::OrdersQuery>,
+) -> impl IntoResponse {
+    order_store
+        .get_account_orders(&account, &params)
+        .await
+        .map_err(order_error_response)
+        .map(|res| (StatusCode::OK, Json(json!({ "response": res }))))
+}
+
+/// Maps an order store error to its response; typed order errors are client errors.
+fn order_error_response(e: eyre::Report) -> (StatusCode, Json<serde_json::Value>) {
+    let status = match e.downcast_ref::<bridge::orders::OrderError>() {
+        Some(bridge::orders::OrderError::InvalidRequest(_)) => StatusCode::BAD_REQUEST,
+        Some(bridge::orders::OrderError::NotFound(_)) => StatusCode::NOT_FOUND,
+        Some(bridge::orders::OrderError::NotBridging(_)) => StatusCode::BAD_REQUEST,
+        Some(bridge::orders::OrderError::NotOwner) => StatusCode::FORBIDDEN,
+        None => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(json!({ "error": e.to_string() })))
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AttachTxHashRequest {
+    /// Order record id, as returned by `/tx`
+    id: i64,
+    tx_hash: String,
+}
+
+/// Attaches the tx hash to the order; the tx must have been sent by the account, proving its ownership.
+async fn attach_order_tx_hash(
+    State(crate::AppState {
+        bridge_service,
+        order_store,
+    }): State<crate::AppState>,
+    Path(account): Path<String>,
+    Json(params): Json<AttachTxHashRequest>,
+) -> impl IntoResponse {
+    order_store
+        .attach_tx_hash(
+            &account,
+            params.id,
+            &params.tx_hash,
+            |chain_id, tx_hash| async move {
+                bridge_service.get_tx_sender(chain_id, &tx_hash).await
+            },
+        )
+        .await
+        .map_err(order_error_response)
+        .map(|_| (StatusCode::OK, Json(json!({ "response": "OK" }))))
+}
+
+/// Maps a bridging error to its response, with its stable code; request, route and revert errors are client
+/// errors, while other failures are upstream (provider or rpc) errors.
+fn bridge_error_response(e: eyre::Report) -> (StatusCode, Json<serde_json::Value>) {
+    let error = bridge::BridgeError::from_report(&e);
+    let status = match error {
+        bridge::BridgeError::UnsupportedChain(_)
+        | bridge::BridgeError::AmountTooLow
+        | bridge::BridgeError::AmountTooHigh => StatusCode::BAD_REQUEST,
+        bridge::BridgeError::ProviderRejected(_) | bridge::BridgeError::Reverted(_) => {
+            StatusCode::UNPROCESSABLE_ENTITY
+        }
+        bridge::BridgeError::Other(_) => StatusCode::BAD_GATEWAY,
+    };
+    (
+        status,
+        Json(json!({ "error": error.to_string(), "code": error.code() })),
+    )
 }
 
 async fn get_bridge_tx(
-    State(crate::AppState { bridge_service }): State<crate::AppState>,
+    State(crate::AppState {
+        bridge_service,
+        order_store,
+    }): State<crate::AppState>,
     Query(params): Query<bridge::BridgeRequest>,
 ) -> impl IntoResponse {
-    let response = bridge_service
+    let res = bridge_service
         .get_tx(&params)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({ "error": e.to_string() })),
-            )
-        })
-        .map(|res| (StatusCode::OK, Json(json!({ "response": res }))));
-    response
+        .map_err(bridge_error_response)?;
+    // recording is best-effort; the bridging response is returned regardless
+    let id = order_store
+        .record(&params, &res)
+        .await
+        .map_err(|e| tracing::warn!("failed to record bridging response: {}", e))
+        .ok();
+    Ok::<_, (StatusCode, Json<serde_json::Value>)>((
+        StatusCode::OK,
+        Json(json!({ "response": res, "id": id })),
+    ))
+}
+
+async fn get_bridge_quote(
+    State(crate::AppState { bridge_service, .. }): State<crate::AppState>,
+    Query(params): Query<bridge::QuoteRequest>,
+) -> impl IntoResponse {
+    bridge_service
+        .get_quote(&params)
+        .await
+        .map_err(bridge_error_response)
+        .map(|res| (StatusCode::OK, Json(json!({ "response": res }))))
+}
+
+async fn get_bridge_quotes(
+    State(crate::AppState { bridge_service, .. }): State<crate::AppState>,
+    Query(params): Query<bridge::QuoteRequest>,
+) -> impl IntoResponse {
+    bridge_service
+        .get_quotes(&params)
+        .await
+        .map_err(bridge_error_response)
+        .map(|res| (StatusCode::OK, Json(json!({ "response": res }))))
+}
+
+async fn get_order_status(
+    State(crate::AppState {
+        bridge_service,
+        order_store,
+    }): State<crate::AppState>,
+    Query(params): Query<bridge::status::StatusRequest>,
+) -> impl IntoResponse {
+    let res = bridge_service.get_status(&params).await.map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "error": e.to_string() })),
+        )
+    })?;
+    if let Err(e) = order_store.update_status(&res).await {
+        tracing::warn!("failed to update recorded order status: {}", e);
+    }
+    Ok::<_, (StatusCode, Json<serde_json::Value>)>((
+        StatusCode::OK,
+        Json(json!({ "response": res })),
+    ))
 }
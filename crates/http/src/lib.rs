@@ -1,20 +1,7 @@
-use std::{
-    convert::Infallible,
-    future::ready,
-    time::{Duration, Instant},
-};
+use std::time::Duration;
 
-use axum::{
-    body::{Body, Bytes},
-    extract::{MatchedPath, Request, State},
-    http::{HeaderMap, HeaderName, HeaderValue, Method, StatusCode},
-    middleware::{self, Next},
-    response::{IntoResponse, Response},
-    routing::get,
-    Json, Router,
-};
-use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
-use serde_json::{json, Value};
+use axum::{body::Bytes, http::HeaderValue, middleware, routing::get, Json, Router};
+use serde_json::json;
 use tower_http::{
     cors::{Any, CorsLayer},
     timeout::TimeoutLayer,
@@ -35,11 +22,17 @@ pub struct ServerConfig {
     pub req_timeout: u8,
     pub metrics_port: u16,
     pub log_level: String,
+    pub routing_strategy: bridge::routing::RoutingStrategy,
+    /// Orders database url; `sqlite://...` or `postgres://...`
+    pub database_url: String,
+    /// Redis url of the upstream responses cache; cached in memory if not set
+    pub redis_url: Option<String>,
 }
 
 #[derive(Debug, Clone)]
 pub struct AppState {
     pub bridge_service: bridge::service::BridgeService,
+    pub order_store: bridge::orders::OrderStore,
 }
 
 pub fn run_server(cfg: ServerConfig) {
@@ -69,8 +62,19 @@ pub fn run_server(cfg: ServerConfig) {
 }
 
 async fn start_main_server(cfg: &ServerConfig) {
+    let cache = match &cfg.redis_url {
+        Some(url) => bridge::cache::Cache::redis(url)
+            .await
+            .expect("failed to connect to redis"),
+        None => bridge::cache::Cache::memory(),
+    };
     let app_state = AppState {
-        bridge_service: bridge::service::BridgeService::new(),
+        bridge_service: bridge::service::BridgeService::new()
+            .with_strategy(cfg.routing_strategy.clone())
+            .with_cache(cache),
+        order_store: bridge::orders::OrderStore::connect(&cfg.database_url)
+            .await
+            .expect("failed to connect to the orders database"),
     };
 
     let app = Router::new()
@@ -1,14 +1,23 @@
-use std::{collections::HashMap, ops::Add, str::FromStr, sync::OnceLock};
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::OnceLock,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use alloy::{
+    dyn_abi::TypedData,
     hex::ToHexExt,
-    primitives::{Address, Bytes, U256},
-    providers::{network::TransactionBuilder, Provider, ProviderBuilder, RootProvider},
+    primitives::{address, Address, Bytes, U256},
+    providers::{network::TransactionBuilder, Provider, RootProvider},
     rpc::types::TransactionRequest,
     sol,
     sol_types::SolCall,
 };
 
+pub mod permit;
+pub mod permit2;
+
 sol! {
     // check approval for caller
     function allowance(address owner, address spender) external view returns (uint256);
@@ -27,6 +36,19 @@ sol! {
     function approve(address spender, uint256 amount) external returns (bool);
 }
 
+/// Sentinel address commonly used to represent the native currency of a chain; the zero address is also accepted.
+pub const NATIVE_TOKEN_SENTINEL: Address = address!("EeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE");
+
+/// Returns true if the token address represents the native currency (zero address or sentinel).
+pub fn is_native_token(token: &str) -> bool {
+    Address::from_str(token)
+        .map(|addr| addr == Address::ZERO || addr == NATIVE_TOKEN_SENTINEL)
+        .unwrap_or(false)
+}
+
+/// Default validity of a permit signature requested from the caller, if no deadline is specified.
+pub const DEFAULT_PERMIT_DEADLINE_SECS: u64 = 30 * 60;
+
 static CHAINS: OnceLock<HashMap<u32, &'static ChainData>> = OnceLock::new();
 
 pub fn get_supported_chains() -> &'static HashMap<u32, &'static ChainData> {
@@ -51,6 +73,18 @@ pub struct ChainData {
     pub id: u32,
     pub name: &'static str,
     pub rpc_url: &'static str,
+    /// Wrapped native currency token (e.g. WETH), if the chain is EVM compatible
+    pub wrapped_native_token: Option<&'static str>,
+    /// OP-stack `L1StandardBridge` on Ethereum, depositing into this chain; if the chain is an OP-stack rollup
+    pub l1_standard_bridge: Option<&'static str>,
+    /// Arbitrum `L1GatewayRouter` on Ethereum, depositing into this chain; if the chain is an Arbitrum rollup
+    pub l1_gateway_router: Option<&'static str>,
+    /// Arbitrum `L2GatewayRouter` on this chain, withdrawing to Ethereum; if the chain is an Arbitrum rollup
+    pub l2_gateway_router: Option<&'static str>,
+    /// Arbitrum `Outbox` on Ethereum, executing the withdrawals from this chain; if the chain is an Arbitrum rollup
+    pub l1_outbox: Option<&'static str>,
+    /// Across `SpokePoolV3Periphery`, consuming permit2 signature transfers for spoke pool deposits; if deployed
+    pub across_spoke_pool_periphery: Option<&'static str>,
 }
 
 #[derive(Debug)]
@@ -75,39 +109,91 @@ impl TryFrom<u32> for Chain {
     }
 }
 
-impl Into<&'static ChainData> for Chain {
-    fn into(self) -> &'static ChainData {
-        match self {
+impl From<Chain> for &'static ChainData {
+    fn from(chain: Chain) -> Self {
+        match chain {
             Chain::Ethereum => &ChainData {
                 id: Chain::Ethereum as u32,
                 name: "Ethereum",
                 rpc_url: "https://eth.llamarpc.com",
+                wrapped_native_token: Some("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"),
+                l1_standard_bridge: None,
+                l1_gateway_router: None,
+                l2_gateway_router: None,
+                l1_outbox: None,
+                across_spoke_pool_periphery: Some("0xED7Bf315Ba2E9Db86b766b8AaC48502298dfe7d3"),
             },
             Chain::Arbitrum => &ChainData {
                 id: Chain::Arbitrum as u32,
                 name: "Arbitrum",
                 rpc_url: "https://arb1.arbitrum.io/rpc",
+                wrapped_native_token: Some("0x82aF49447D8a07e3bd95BD0d56f35241523fBab1"),
+                l1_standard_bridge: None,
+                l1_gateway_router: Some("0x72Ce9c846789fdB6fC1f34aC4AD25Dd9ef7031ef"),
+                l2_gateway_router: Some("0x5288c571Fd7aD117beA99bF60FE0846C4E84F933"),
+                l1_outbox: Some("0x0B9857ae2D4A3DBe74ffE1d7DF045bb7F96E4840"),
+                across_spoke_pool_periphery: Some("0xED7Bf315Ba2E9Db86b766b8AaC48502298dfe7d3"),
             },
             Chain::Base => &ChainData {
                 id: Chain::Base as u32,
                 name: "Base",
                 rpc_url: "https://mainnet.base.org",
+                wrapped_native_token: Some("0x4200000000000000000000000000000000000006"),
+                l1_standard_bridge: Some("0x3154Cf16ccdb4C6d922629664174b904d80F2C35"),
+                l1_gateway_router: None,
+                l2_gateway_router: None,
+                l1_outbox: None,
+                across_spoke_pool_periphery: Some("0xED7Bf315Ba2E9Db86b766b8AaC48502298dfe7d3"),
             },
             Chain::Solana => &ChainData {
                 id: Chain::Solana as u32,
                 name: "Solana",
                 rpc_url: "https://api.mainnet-beta.solana.com",
+                wrapped_native_token: None,
+                l1_standard_bridge: None,
+                l1_gateway_router: None,
+                l2_gateway_router: None,
+                l1_outbox: None,
+                across_spoke_pool_periphery: None,
             },
         }
     }
 }
 
+/// Returns the permit deadline as a unix timestamp; defaults to [`DEFAULT_PERMIT_DEADLINE_SECS`] from now.
+pub fn get_permit_deadline(deadline: Option<u64>) -> U256 {
+    let deadline = deadline
+        .unwrap_or_else(|| get_unix_timestamp().saturating_add(DEFAULT_PERMIT_DEADLINE_SECS));
+    U256::from(deadline)
+}
+
+/// Current unix timestamp in seconds
+pub fn get_unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|now| now.as_secs())
+        .unwrap_or_default()
+}
+
+/// Permit mechanisms consumed by the bridging contracts, used to resolve the allowance action.
+#[derive(Debug, Clone)]
+pub struct PermitOptions {
+    /// Contract consuming signed permits (EIP-2612/permit2) on behalf of the owner;
+    /// permits are not requested if `None`, i.e. an explicit approval is required
+    pub spender: Option<Address>,
+    /// Permit2 transfer mechanism consumed by the permit spender; permit2 is not used if `None`
+    pub permit2_kind: Option<permit2::Permit2Kind>,
+    /// Unix timestamp until which a requested permit signature is valid
+    pub deadline: U256,
+}
+
 pub async fn get_token_allowance_action(
     provider: &RootProvider<alloy::transports::http::Http<reqwest::Client>>,
     token_addr: &Address,
     amount: &U256,
     owner: &Address,
     spender: &Address,
+    permit_options: &PermitOptions,
 ) -> eyre::Result<AllowanceAction> {
     // perform allowance check on token contract for caller and spender
     let allowance = get_allowance(provider, token_addr, owner, spender).await?;
@@ -116,6 +202,11 @@ pub async fn get_token_allowance_action(
         return Ok(AllowanceAction::Ok);
     }
 
+    let Some(permit_spender) = permit_options.spender else {
+        // permits are not consumed by the bridging contract, an explicit approval is required
+        return Ok(get_approval_action(token_addr, spender));
+    };
+
     // check if token contract supports eip-2612 (permit)
     // we do this by explicitly checking that deployed bytecode contains permit func selector
     // TODO: cache this for future operations, assume non-metamorphic token contract
@@ -126,55 +217,115 @@ pub async fn get_token_allowance_action(
     if supports_permit {
         // return back the permit signature to by signed by the caller
         // to give explicit permission to sender
-        return Ok(AllowanceAction::PermitSignature("TODO".into()));
+        let typed_data = permit::get_permit_typed_data(
+            provider,
+            token_addr,
+            owner,
+            &permit_spender,
+            amount,
+            &permit_options.deadline,
+        )
+        .await?;
+        return Ok(AllowanceAction::PermitSignature(typed_data));
     }
 
-    // TODO: check if chain contains deployment of canonical permit2 contract
-    let permit2_contract_addr = Some("some-addr");
-    if let Some(permit2_contract_addr) = permit2_contract_addr {
-        let permit2_contract_addr = Address::from_str(permit2_contract_addr)?;
-        // check if canonical permit2 contract has atleast the allowance
-        let permit2_allowance =
-            get_allowance(provider, token_addr, owner, &permit2_contract_addr).await?;
-        if permit2_allowance >= *amount {
-            // token has explicit allowance for permit2 contract from owner
-            // return back permit2 signature to by signed by the caller
-            // to give explicit permission to sender
-            return Ok(AllowanceAction::Permit2Signature("TODO".into()));
-        }
-
-        // construct TxData to give canonical permit2 contract max permissions
-        // also provide permit2 signature to caller so that they can sign and provide it
-        // in a future http call (save roundtrip to get bridging tx)
-        let data: Bytes = approveCall {
-            spender: permit2_contract_addr,
-            amount: U256::MAX,
-        }
-        .abi_encode()
-        .into();
-        let permit2_tx = TxData {
-            to: token_addr.to_string(),
-            data: data.to_string(),
-            value: "0".into(),
+    // check if the spender consumes permit2 permits, and chain contains deployment of canonical permit2 contract
+    let permit2_contract_addr = match permit_options.permit2_kind {
+        Some(_) => permit2::get_permit2_address(provider).await?,
+        None => None,
+    };
+    if let (Some(permit2_kind), Some(permit2_contract_addr)) =
+        (permit_options.permit2_kind, permit2_contract_addr)
+    {
+        let typed_data = permit2::get_permit2_typed_data(
+            provider,
+            permit2_kind,
+            token_addr,
+            amount,
+            owner,
+            &permit_spender,
+            &permit_options.deadline,
+        )
+        .await?;
+        let Some(typed_data) = typed_data else {
+            // spender is able to transfer via its existing permit2 allowance
+            return Ok(AllowanceAction::Ok);
         };
-        return Ok(AllowanceAction::Permit2Tx(
-            permit2_tx,
-            "TODO: permit2-signature".to_string(),
-        ));
+
+        return get_permit2_allowance_action(
+            provider,
+            token_addr,
+            amount,
+            owner,
+            &permit2_contract_addr,
+            typed_data,
+        )
+        .await;
     };
 
     // at this point, no canonical permit2 exists on-chain.. simply give explicit max-allowance to the spender..
+    Ok(get_approval_action(token_addr, spender))
+}
+
+/// Returns the permit2 payload to be signed by the owner, preceded by a max approval of the permit2 contract
+/// if the owner has not approved it for at least `amount`.
+pub async fn get_permit2_allowance_action(
+    provider: &RootProvider<alloy::transports::http::Http<reqwest::Client>>,
+    token_addr: &Address,
+    amount: &U256,
+    owner: &Address,
+    permit2_contract_addr: &Address,
+    typed_data: TypedData,
+) -> eyre::Result<AllowanceAction> {
+    // check if canonical permit2 contract has atleast the allowance
+    let permit2_allowance =
+        get_allowance(provider, token_addr, owner, permit2_contract_addr).await?;
+    if permit2_allowance >= *amount {
+        // token has explicit allowance for permit2 contract from owner
+        // return back permit2 signature to by signed by the caller
+        // to give explicit permission to sender
+        return Ok(AllowanceAction::Permit2Signature(typed_data));
+    }
+
+    // construct TxData to give canonical permit2 contract max permissions
+    // also provide permit2 signature to caller so that they can sign and provide it
+    // in a future http call (save roundtrip to get bridging tx)
+    let permit2_tx = get_approval_tx(token_addr, permit2_contract_addr);
+    Ok(AllowanceAction::Permit2Tx(permit2_tx, typed_data))
+}
+
+/// Explicit approval tx to give max allowance to the spender
+fn get_approval_action(token_addr: &Address, spender: &Address) -> AllowanceAction {
+    AllowanceAction::ApprovalTx(get_approval_tx(token_addr, spender))
+}
+
+fn get_approval_tx(token_addr: &Address, spender: &Address) -> TxData {
     let data: Bytes = approveCall {
         spender: *spender,
         amount: U256::MAX,
     }
     .abi_encode()
     .into();
-    return Ok(AllowanceAction::ApprovalTx(TxData {
+    TxData {
         to: token_addr.to_string(),
         data: data.to_string(),
         value: "0".into(),
-    }));
+        ..Default::default()
+    }
+}
+
+/// Performs an `eth_call` of a view function on the given contract and decodes its return data
+pub async fn eth_call<C: SolCall>(
+    provider: &RootProvider<alloy::transports::http::Http<reqwest::Client>>,
+    contract_addr: &Address,
+    call: C,
+) -> eyre::Result<C::Return> {
+    let tx = TransactionRequest::default()
+        .with_to(*contract_addr)
+        .with_input::<Bytes>(call.abi_encode().into());
+    let response = provider.call(&tx).await?;
+    C::abi_decode_returns(&response, true)
+        .map_err(|e| eyre::eyre!("failed to decode call return: {e}"))
 }
 
 /// Simply utilizes the provider to call the allowance mapping on an erc20
@@ -200,12 +351,34 @@ pub async fn get_allowance(
     Ok(U256::from_str(&response.to_string())?)
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-#[cfg_attr(test, derive(PartialEq))]
+/// EVM transaction data to be executed by the caller.
+/// Shared between allowance actions and bridging actions, so that either can be returned as-is.
+/// Gas and EIP-1559 fee parameters are optional; they are filled from the source chain, when available,
+/// so that the tx can be signed as is.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TxData {
-    data: String,
-    to: String,
-    value: String,
+    /// Hex-encoded calldata
+    pub data: String,
+    /// Contract address to call
+    pub to: String,
+    /// Native value (in wei) to send along with the tx
+    pub value: String,
+    /// Chain id of the source chain, the tx is to be executed on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<u64>,
+    /// Gas limit, including a buffer over the estimated gas
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<u64>,
+    /// Max fee per gas (in wei)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_gas: Option<String>,
+    /// Max priority fee per gas (in wei)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_priority_fee_per_gas: Option<String>,
+    /// Pending nonce of the caller
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u64>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -213,18 +386,18 @@ pub enum AllowanceAction {
     /// No approvals or signatures signing is required.
     /// The spender has already been approved the desired/specified amount.
     Ok,
-    /// EIP-2612 payload, to be signed for BridgingTx.
+    /// EIP-2612 payload (EIP-712 typed data), to be signed for BridgingTx.
     /// If the source token supports EIP-2612, the caller must sign this data, for a bridging tx to be returned.
-    PermitSignature(String),
-    /// Permit2 payload, to be signed for BridgingTx
+    PermitSignature(TypedData),
+    /// Permit2 payload (EIP-712 typed data), to be signed for BridgingTx
     /// If source token doesnt support eip-2612 and the bridging contract is not approved,
     /// but the caller has approved the canonical permit2 contract, the caller must sign this data.
-    Permit2Signature(String),
+    Permit2Signature(TypedData),
     /// Permit2 tx, to be called before BridgingTx
     /// If source token doesnt support eip-2612 and the bridging contract is not approved,
     /// and the caller has not approved the canonical permit2 contract, the caller must call this tx
-    /// before the bridging tx.
-    Permit2Tx(TxData, String),
+    /// before the bridging tx; the permit2 payload is to be signed once the tx has been executed.
+    Permit2Tx(TxData, TypedData),
     /// Explicit approval tx to give max allowance for the spender.
     ApprovalTx(TxData),
 }
@@ -234,12 +407,63 @@ mod tests {
     use super::*;
     // use alloy::node_bindings::Anvil;
 
+    #[test]
+    fn native_token() {
+        assert!(is_native_token(
+            "0x0000000000000000000000000000000000000000"
+        ));
+        assert!(is_native_token(
+            "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE"
+        ));
+        assert!(is_native_token(
+            "0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee"
+        ));
+        assert!(!is_native_token(
+            "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913"
+        ));
+        assert!(!is_native_token(
+            "So11111111111111111111111111111111111111112"
+        ));
+    }
+
     #[test]
     fn permit_function_selector() {
         // cast sig "permit(address,address,uint256,uint256,uint8,bytes32,bytes32)"
         assert_eq!(permitCall::SELECTOR.encode_hex(), "d505accf");
     }
 
+    #[test]
+    fn tx_data_serde() {
+        let tx = TxData {
+            data: "0x".into(),
+            to: "0x09aea4b2242abC8bb4BB78D537A67a245A7bEC64".into(),
+            value: "0".into(),
+            ..Default::default()
+        };
+        // unset params are omitted
+        assert_eq!(
+            serde_json::to_value(&tx).unwrap(),
+            serde_json::json!({
+                "data": "0x",
+                "to": "0x09aea4b2242abC8bb4BB78D537A67a245A7bEC64",
+                "value": "0",
+            })
+        );
+
+        let tx = TxData {
+            chain_id: Some(8453),
+            gas_limit: Some(25_200),
+            max_fee_per_gas: Some("1000000".into()),
+            max_priority_fee_per_gas: Some("1000".into()),
+            nonce: Some(7),
+            ..tx
+        };
+        let value = serde_json::to_value(&tx).unwrap();
+        assert_eq!(value["gasLimit"], 25_200);
+        assert_eq!(value["maxFeePerGas"], "1000000");
+        assert_eq!(serde_json::from_value::<TxData>(value).unwrap(), tx);
+    }
+
     // #[tokio::test]
     // async fn get_token_allowance_action() {
     //     Anvil::new()
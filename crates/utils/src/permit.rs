@@ -0,0 +1,269 @@
+use std::{borrow::Cow, str::FromStr};
+
+use alloy::{
+    dyn_abi::TypedData,
+    hex,
+    primitives::{Address, Signature, U256},
+    providers::{Provider, RootProvider},
+    sol,
+    sol_types::{Eip712Domain, SolStruct},
+};
+
+sol! {
+    /// EIP-2612 permit struct, as signed by the token owner
+    #[derive(Debug, serde::Serialize, serde::Deserialize)]
+    struct Permit {
+        address owner;
+        address spender;
+        uint256 value;
+        uint256 nonce;
+        uint256 deadline;
+    }
+
+    function name() external view returns (string);
+    function version() external view returns (string);
+    function nonces(address owner) external view returns (uint256);
+    function DOMAIN_SEPARATOR() external view returns (bytes32);
+
+    // EIP-5267 domain retrieval
+    function eip712Domain() external view returns (
+        bytes1 fields,
+        string name,
+        string version,
+        uint256 chainId,
+        address verifyingContract,
+        bytes32 salt,
+        uint256[] extensions
+    );
+}
+
+/// Versions attempted when the token neither implements `version()` nor EIP-5267;
+/// the resolved domain must always match the on-chain `DOMAIN_SEPARATOR()`.
+const FALLBACK_DOMAIN_VERSIONS: [&str; 2] = ["1", "2"];
+
+/// Builds the EIP-2612 permit typed data (`eth_signTypedData_v4` payload) to be signed by the owner,
+/// giving the spender an allowance of `value` until `deadline`.
+pub async fn get_permit_typed_data(
+    provider: &RootProvider<alloy::transports::http::Http<reqwest::Client>>,
+    token_addr: &Address,
+    owner: &Address,
+    spender: &Address,
+    value: &U256,
+    deadline: &U256,
+) -> eyre::Result<TypedData> {
+    let (domain, nonce) = tokio::try_join!(
+        get_permit_domain(provider, token_addr),
+        crate::eth_call(provider, token_addr, noncesCall { owner: *owner }),
+    )?;
+    let permit = Permit {
+        owner: *owner,
+        spender: *spender,
+        value: *value,
+        nonce: nonce._0,
+        deadline: *deadline,
+    };
+    Ok(TypedData::from_struct(&permit, Some(domain)))
+}
+
+/// Resolves the EIP-712 domain of a permit-capable token.
+/// EIP-5267 `eip712Domain()` is preferred, otherwise the domain is constructed from `name()` and `version()`.
+/// The resolved domain is validated against the token's on-chain `DOMAIN_SEPARATOR()`.
+pub async fn get_permit_domain(
+    provider: &RootProvider<alloy::transports::http::Http<reqwest::Client>>,
+    token_addr: &Address,
+) -> eyre::Result<Eip712Domain> {
+    let (chain_id, domain_separator) = tokio::try_join!(
+        async { Ok::<_, eyre::Report>(provider.get_chain_id().await?) },
+        crate::eth_call(provider, token_addr, DOMAIN_SEPARATORCall {}),
+    )?;
+    let domain_separator = domain_separator._0;
+
+    if let Ok(domain) = crate::eth_call(provider, token_addr, eip712DomainCall {}).await {
+        let domain = Eip712Domain::new(
+            (domain.fields[0] & 0x01 != 0).then_some(Cow::Owned(domain.name)),
+            (domain.fields[0] & 0x02 != 0).then_some(Cow::Owned(domain.version)),
+            (domain.fields[0] & 0x04 != 0).then_some(domain.chainId),
+            (domain.fields[0] & 0x08 != 0).then_some(domain.verifyingContract),
+            (domain.fields[0] & 0x10 != 0).then_some(domain.salt),
+        );
+        if domain.separator() == domain_separator {
+            return Ok(domain);
+        }
+    }
+
+    let name = crate::eth_call(provider, token_addr, nameCall {}).await?._0;
+    let versions = match crate::eth_call(provider, token_addr, versionCall {}).await {
+        Ok(version) => vec![version._0],
+        Err(_) => FALLBACK_DOMAIN_VERSIONS.map(String::from).to_vec(),
+    };
+    versions
+        .into_iter()
+        .map(|version| {
+            Eip712Domain::new(
+                Some(Cow::Owned(name.clone())),
+                Some(Cow::Owned(version)),
+                Some(U256::from(chain_id)),
+                Some(*token_addr),
+                None,
+            )
+        })
+        .find(|domain| domain.separator() == domain_separator)
+        .ok_or_else(|| {
+            eyre::eyre!(
+                "unable to resolve EIP-712 domain for token {}: domain separator {} mismatch",
+                token_addr,
+                domain_separator
+            )
+        })
+}
+
+/// Signed EIP-2612 permit envelope, as provided by the caller:
+/// `abi.encodePacked(uint256 amount, uint256 deadline, bytes signature)`.
+/// This is the same envelope format consumed by DLN (`srcChainTokenInSenderPermit`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PermitEnvelope {
+    /// Permitted amount, i.e. the `value` of the signed permit
+    pub amount: U256,
+    pub deadline: U256,
+    pub signature: Signature,
+}
+
+impl FromStr for PermitEnvelope {
+    type Err = eyre::Report;
+
+    fn from_str(envelope: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(envelope)?;
+        if bytes.len() != 32 + 32 + 65 {
+            return Err(eyre::eyre!(
+                "invalid permit envelope length: expected 129 bytes, got {}",
+                bytes.len()
+            ));
+        }
+        Ok(Self {
+            amount: U256::from_be_slice(&bytes[..32]),
+            deadline: U256::from_be_slice(&bytes[32..64]),
+            signature: Signature::try_from(&bytes[64..])?,
+        })
+    }
+}
+
+impl PermitEnvelope {
+    /// ECDSA `v` value (27/28) as expected by the token `permit` function
+    pub fn v(&self) -> u8 {
+        27 + self.signature.v().y_parity_byte()
+    }
+}
+
+/// Verifies that the permit envelope was signed by the owner, for the spender to spend `value` of the token;
+/// the permitted amount of the envelope must be `value`.
+/// The permit is reconstructed from the token's current on-chain nonce and EIP-712 domain.
+pub async fn verify_permit(
+    provider: &RootProvider<alloy::transports::http::Http<reqwest::Client>>,
+    token_addr: &Address,
+    owner: &Address,
+    spender: &Address,
+    value: &U256,
+    envelope: &PermitEnvelope,
+) -> eyre::Result<()> {
+    if envelope.amount != *value {
+        return Err(eyre::eyre!(
+            "permit amount {} does not match the amount {}",
+            envelope.amount,
+            value
+        ));
+    }
+    if envelope.deadline <= U256::from(crate::get_unix_timestamp()) {
+        return Err(eyre::eyre!(
+            "permit deadline {} has passed",
+            envelope.deadline
+        ));
+    }
+
+    let (domain, nonce) = tokio::try_join!(
+        get_permit_domain(provider, token_addr),
+        crate::eth_call(provider, token_addr, noncesCall { owner: *owner }),
+    )?;
+    let permit = Permit {
+        owner: *owner,
+        spender: *spender,
+        value: *value,
+        nonce: nonce._0,
+        deadline: envelope.deadline,
+    };
+    let signer = envelope
+        .signature
+        .recover_address_from_prehash(&permit.eip712_signing_hash(&domain))?;
+    if signer != *owner {
+        return Err(eyre::eyre!(
+            "invalid permit signature: signer {} does not match owner {} for spender {} and value {}",
+            signer,
+            owner,
+            spender,
+            value
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::address;
+
+    #[test]
+    fn permit_typed_data() {
+        // USDC on base
+        let domain = Eip712Domain::new(
+            Some("USD Coin".into()),
+            Some("2".into()),
+            Some(U256::from(8453)),
+            Some(address!("833589fCD6eDb6E08f4c7C32D4f71b54bdA02913")),
+            None,
+        );
+        let permit = Permit {
+            owner: address!("000007357111E4789005d4eBfF401a18D99770cE"),
+            spender: address!("09aea4b2242abC8bb4BB78D537A67a245A7bEC64"),
+            value: U256::from(2_000_000u32),
+            nonce: U256::ZERO,
+            deadline: U256::from(1_700_000_000u64),
+        };
+        let typed_data = TypedData::from_struct(&permit, Some(domain));
+        assert_eq!(typed_data.primary_type, "Permit");
+        assert_eq!(
+            typed_data.eip712_signing_hash().unwrap(),
+            alloy::sol_types::SolStruct::eip712_signing_hash(&permit, &typed_data.domain)
+        );
+
+        let json = serde_json::to_value(&typed_data).unwrap();
+        assert_eq!(json["primaryType"], "Permit");
+        assert_eq!(json["domain"]["name"], "USD Coin");
+        assert!(json["types"]["EIP712Domain"].is_array());
+        assert!(json["types"]["Permit"].is_array());
+    }
+
+    #[test]
+    fn permit_envelope() {
+        let amount = U256::from(2_000_000u32);
+        let deadline = U256::from(1_700_000_000u64);
+        let signature = Signature::from_rs_and_parity(U256::from(1), U256::from(2), 28u64).unwrap();
+        let envelope = format!(
+            "0x{}{}{}",
+            hex::encode(amount.to_be_bytes::<32>()),
+            hex::encode(deadline.to_be_bytes::<32>()),
+            hex::encode(signature.as_bytes())
+        );
+        let envelope = PermitEnvelope::from_str(&envelope).unwrap();
+        assert_eq!(envelope.amount, amount);
+        assert_eq!(envelope.deadline, deadline);
+        assert_eq!(envelope.signature, signature);
+        assert_eq!(envelope.v(), 28);
+
+        // missing amount
+        assert!(PermitEnvelope::from_str(&format!(
+            "0x{}{}",
+            hex::encode(deadline.to_be_bytes::<32>()),
+            hex::encode(signature.as_bytes())
+        ))
+        .is_err());
+    }
+}
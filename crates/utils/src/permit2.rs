@@ -0,0 +1,343 @@
+use std::{borrow::Cow, str::FromStr};
+
+use alloy::{
+    dyn_abi::TypedData,
+    hex,
+    primitives::{address, aliases::U160, Address, Signature, U256},
+    providers::{Provider, RootProvider},
+    sol,
+    sol_types::{Eip712Domain, SolStruct},
+};
+
+/// Canonical Uniswap Permit2 deployment; deployed via create2 to the same address on all supported chains
+pub const PERMIT2_ADDRESS: Address = address!("000000000022D473030F116dDEE9F6B43aC78BA3");
+
+/// Max number of nonce bitmap words searched for an unused signature-transfer nonce
+const MAX_NONCE_BITMAP_WORDS: u64 = 16;
+
+sol! {
+    #[derive(Debug, serde::Serialize, serde::Deserialize)]
+    struct TokenPermissions {
+        address token;
+        uint256 amount;
+    }
+
+    /// Permit2 signature-transfer permit; a one-time permit consumed by the spender
+    #[derive(Debug, serde::Serialize, serde::Deserialize)]
+    struct PermitTransferFrom {
+        TokenPermissions permitted;
+        address spender;
+        uint256 nonce;
+        uint256 deadline;
+    }
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize)]
+    struct PermitDetails {
+        address token;
+        uint160 amount;
+        uint48 expiration;
+        uint48 nonce;
+    }
+
+    /// Permit2 allowance-transfer permit; sets the spender allowance on the permit2 contract
+    #[derive(Debug, serde::Serialize, serde::Deserialize)]
+    struct PermitSingle {
+        PermitDetails details;
+        address spender;
+        uint256 sigDeadline;
+    }
+
+    interface IPermit2 {
+        function allowance(address user, address token, address spender) external view returns (uint160 amount, uint48 expiration, uint48 nonce);
+        function nonceBitmap(address owner, uint256 wordPos) external view returns (uint256);
+    }
+}
+
+/// The Permit2 transfer mechanism consumed by the spender.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Permit2Kind {
+    /// Spender transfers tokens through a permit2 allowance, set using a signed `PermitSingle`
+    AllowanceTransfer,
+    /// Spender transfers tokens using a one-time signed `PermitTransferFrom`
+    SignatureTransfer,
+}
+
+/// Returns the canonical permit2 contract address, if deployed on the provider's chain.
+pub async fn get_permit2_address(
+    provider: &RootProvider<alloy::transports::http::Http<reqwest::Client>>,
+) -> eyre::Result<Option<Address>> {
+    let code = provider.get_code_at(PERMIT2_ADDRESS).latest().await?;
+    Ok((!code.is_empty()).then_some(PERMIT2_ADDRESS))
+}
+
+/// EIP-712 domain of the permit2 contract, signing both signature-transfer and allowance-transfer permits
+pub fn get_permit2_domain(chain_id: u64, permit2_addr: &Address) -> Eip712Domain {
+    Eip712Domain::new(
+        Some(Cow::Borrowed("Permit2")),
+        None,
+        Some(U256::from(chain_id)),
+        Some(*permit2_addr),
+        None,
+    )
+}
+
+/// Builds the permit2 payload (`eth_signTypedData_v4`) to be signed by the owner, for the spender to transfer `amount`.
+/// Returns `None` if the spender already has a sufficient, non-expired permit2 allowance; no signature is required.
+pub async fn get_permit2_typed_data(
+    provider: &RootProvider<alloy::transports::http::Http<reqwest::Client>>,
+    kind: Permit2Kind,
+    token_addr: &Address,
+    amount: &U256,
+    owner: &Address,
+    spender: &Address,
+    deadline: &U256,
+) -> eyre::Result<Option<TypedData>> {
+    let chain_id = provider.get_chain_id().await?;
+    let domain = get_permit2_domain(chain_id, &PERMIT2_ADDRESS);
+
+    match kind {
+        Permit2Kind::AllowanceTransfer => {
+            let allowance = crate::eth_call(
+                provider,
+                &PERMIT2_ADDRESS,
+                IPermit2::allowanceCall {
+                    user: *owner,
+                    token: *token_addr,
+                    spender: *spender,
+                },
+            )
+            .await?;
+            if allowance.amount >= *amount && allowance.expiration > crate::get_unix_timestamp() {
+                return Ok(None);
+            }
+            let permit = get_permit_single(token_addr, amount, allowance.nonce, spender, deadline)?;
+            Ok(Some(TypedData::from_struct(&permit, Some(domain))))
+        }
+        Permit2Kind::SignatureTransfer => {
+            let nonce = get_unused_nonce(provider, owner).await?;
+            let permit = PermitTransferFrom {
+                permitted: TokenPermissions {
+                    token: *token_addr,
+                    amount: *amount,
+                },
+                spender: *spender,
+                nonce,
+                deadline: *deadline,
+            };
+            Ok(Some(TypedData::from_struct(&permit, Some(domain))))
+        }
+    }
+}
+
+fn get_permit_single(
+    token_addr: &Address,
+    amount: &U256,
+    nonce: u64,
+    spender: &Address,
+    deadline: &U256,
+) -> eyre::Result<PermitSingle> {
+    if *amount > U256::from(U160::MAX) {
+        return Err(eyre::eyre!("permit2 amount exceeds uint160"));
+    }
+    let expiration: u64 = deadline
+        .try_into()
+        .map_err(|_| eyre::eyre!("permit2 expiration exceeds uint48"))?;
+    Ok(PermitSingle {
+        details: PermitDetails {
+            token: *token_addr,
+            amount: *amount,
+            expiration,
+            nonce,
+        },
+        spender: *spender,
+        sigDeadline: *deadline,
+    })
+}
+
+/// Finds the first unused signature-transfer nonce of the owner, by searching the permit2 nonce bitmap.
+pub async fn get_unused_nonce(
+    provider: &RootProvider<alloy::transports::http::Http<reqwest::Client>>,
+    owner: &Address,
+) -> eyre::Result<U256> {
+    for word_pos in 0..MAX_NONCE_BITMAP_WORDS {
+        let bitmap = crate::eth_call(
+            provider,
+            &PERMIT2_ADDRESS,
+            IPermit2::nonceBitmapCall {
+                owner: *owner,
+                wordPos: U256::from(word_pos),
+            },
+        )
+        .await?
+        ._0;
+        if let Some(nonce) = get_unused_nonce_in_word(word_pos, bitmap) {
+            return Ok(nonce);
+        }
+    }
+    Err(eyre::eyre!("no unused permit2 nonce found for {}", owner))
+}
+
+/// Nonces are `wordPos << 8 | bitPos`, a set bit in the word bitmap marks the nonce as used.
+fn get_unused_nonce_in_word(word_pos: u64, bitmap: U256) -> Option<U256> {
+    if bitmap == U256::MAX {
+        return None;
+    }
+    let bit_pos = (!bitmap).trailing_zeros();
+    Some((U256::from(word_pos) << 8) | U256::from(bit_pos))
+}
+
+/// Fails if the signature-transfer nonce of the owner has been used, i.e. its bit is set in the nonce bitmap.
+pub async fn check_unused_nonce(
+    provider: &RootProvider<alloy::transports::http::Http<reqwest::Client>>,
+    owner: &Address,
+    nonce: &U256,
+) -> eyre::Result<()> {
+    let bitmap = crate::eth_call(
+        provider,
+        &PERMIT2_ADDRESS,
+        IPermit2::nonceBitmapCall {
+            owner: *owner,
+            wordPos: *nonce >> 8,
+        },
+    )
+    .await?;
+    if bitmap._0.bit(nonce.byte(0) as usize) {
+        return Err(eyre::eyre!("permit2 nonce {} already used", nonce));
+    }
+    Ok(())
+}
+
+/// Signed permit2 signature-transfer envelope, as provided by the caller:
+/// `abi.encodePacked(uint256 nonce, uint256 deadline, bytes signature)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Permit2Envelope {
+    pub nonce: U256,
+    pub deadline: U256,
+    pub signature: Signature,
+}
+
+impl FromStr for Permit2Envelope {
+    type Err = eyre::Report;
+
+    fn from_str(envelope: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(envelope)?;
+        if bytes.len() != 32 + 32 + 65 {
+            return Err(eyre::eyre!(
+                "invalid permit2 envelope length: expected 129 bytes, got {}",
+                bytes.len()
+            ));
+        }
+        Ok(Self {
+            nonce: U256::from_be_slice(&bytes[..32]),
+            deadline: U256::from_be_slice(&bytes[32..64]),
+            signature: Signature::try_from(&bytes[64..])?,
+        })
+    }
+}
+
+/// Verifies that the permit2 signature-transfer envelope was signed by the owner, for the spender to transfer `amount`
+/// of the token, and that its nonce has not been used. Returns the signed permit.
+pub async fn verify_permit_transfer_from(
+    provider: &RootProvider<alloy::transports::http::Http<reqwest::Client>>,
+    token_addr: &Address,
+    amount: &U256,
+    owner: &Address,
+    spender: &Address,
+    envelope: &Permit2Envelope,
+) -> eyre::Result<PermitTransferFrom> {
+    if envelope.deadline <= U256::from(crate::get_unix_timestamp()) {
+        return Err(eyre::eyre!(
+            "permit2 deadline {} has passed",
+            envelope.deadline
+        ));
+    }
+
+    let (chain_id, _) = tokio::try_join!(
+        async { Ok::<_, eyre::Report>(provider.get_chain_id().await?) },
+        check_unused_nonce(provider, owner, &envelope.nonce),
+    )?;
+
+    let permit = PermitTransferFrom {
+        permitted: TokenPermissions {
+            token: *token_addr,
+            amount: *amount,
+        },
+        spender: *spender,
+        nonce: envelope.nonce,
+        deadline: envelope.deadline,
+    };
+    let domain = get_permit2_domain(chain_id, &PERMIT2_ADDRESS);
+    let signer = envelope
+        .signature
+        .recover_address_from_prehash(&permit.eip712_signing_hash(&domain))?;
+    if signer != *owner {
+        return Err(eyre::eyre!(
+            "invalid permit2 signature: signer {} does not match owner {} for spender {} and amount {}",
+            signer,
+            owner,
+            spender,
+            amount
+        ));
+    }
+    Ok(permit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unused_nonce_in_word() {
+        assert_eq!(get_unused_nonce_in_word(0, U256::ZERO), Some(U256::ZERO));
+        assert_eq!(
+            get_unused_nonce_in_word(0, U256::from(0b0111)),
+            Some(U256::from(3))
+        );
+        assert_eq!(
+            get_unused_nonce_in_word(2, U256::from(1)),
+            Some(U256::from(513))
+        );
+        assert_eq!(get_unused_nonce_in_word(0, U256::MAX), None);
+    }
+
+    #[test]
+    fn permit_single_typed_data() {
+        let token = address!("833589fCD6eDb6E08f4c7C32D4f71b54bdA02913");
+        let spender = address!("09aea4b2242abC8bb4BB78D537A67a245A7bEC64");
+        let permit = get_permit_single(
+            &token,
+            &U256::from(2_000_000u32),
+            1,
+            &spender,
+            &U256::from(1_700_000_000u64),
+        )
+        .unwrap();
+        let typed_data =
+            TypedData::from_struct(&permit, Some(get_permit2_domain(8453, &PERMIT2_ADDRESS)));
+        assert_eq!(typed_data.primary_type, "PermitSingle");
+        assert_eq!(
+            typed_data.eip712_signing_hash().unwrap(),
+            alloy::sol_types::SolStruct::eip712_signing_hash(&permit, &typed_data.domain)
+        );
+
+        // permit2 amounts are bound to uint160
+        assert!(get_permit_single(&token, &U256::MAX, 0, &spender, &U256::ZERO).is_err());
+    }
+
+    #[test]
+    fn permit2_envelope() {
+        let nonce = U256::from(513);
+        let deadline = U256::from(1_700_000_000u64);
+        let signature = Signature::from_rs_and_parity(U256::from(1), U256::from(2), 27u64).unwrap();
+        let envelope = format!(
+            "0x{}{}{}",
+            hex::encode(nonce.to_be_bytes::<32>()),
+            hex::encode(deadline.to_be_bytes::<32>()),
+            hex::encode(signature.as_bytes())
+        );
+        let envelope = Permit2Envelope::from_str(&envelope).unwrap();
+        assert_eq!(envelope.nonce, nonce);
+        assert_eq!(envelope.deadline, deadline);
+        assert_eq!(envelope.signature, signature);
+    }
+}